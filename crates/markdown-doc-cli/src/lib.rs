@@ -1,14 +1,23 @@
+use std::collections::BTreeSet;
+use std::ops::ControlFlow;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::Result;
 use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
-use markdown_doc_config::{Config, LoadOptions};
+use markdown_doc_config::{CatalogTimestamp, Config, LoadOptions, TocInsertPosition};
 use markdown_doc_core::MarkdownDoc;
 use markdown_doc_format::{CatalogFormat, LintFormat, ValidateFormat};
-use markdown_doc_ops::OperationError;
+use markdown_doc_ops::refactor::rewrite::FileMove;
 use markdown_doc_ops::{
-    CatalogOptions, CatalogOutcome, LintOptions, LintOutcome, MvFileStatus, MvOptions, RefsOptions,
-    ScanOptions, TocMode, TocOptions, TocOutcome, ValidateOptions, ValidateOutcome,
+    load_move_map, GraphEdgeKind, GraphExportOptions, OperationError, OutboundLink,
+    OutboundOptions, OutboundStatus,
+};
+use markdown_doc_ops::{
+    CatalogMode, CatalogOptions, CatalogOutcome, ChangedSince, FileStats, LintOptions, LintOutcome,
+    MergeOptions, MvFileStatus, MvOptions, MvOutcome, RefsOptions, RenameHeadingOptions,
+    ScanOptions, SearchOptions, SnippetLine, SplitOptions, StatsOptions, StatsReport, TocMode,
+    TocOptions, TocOutcome, ValidateOptions, ValidateOutcome, WatchOptions,
 };
 use serde_json::json;
 
@@ -26,7 +35,15 @@ pub fn run() -> Result<i32> {
         Command::Validate(args) => handle_validate(ops, args),
         Command::Toc(args) => handle_toc(ops, args),
         Command::Refs(args) => handle_refs(ops, args),
+        Command::Search(args) => handle_search(ops, args),
+        Command::Watch(args) => handle_watch(ops, args),
         Command::Mv(args) => handle_mv(ops, args),
+        Command::Split(args) => handle_split(ops, args),
+        Command::Merge(args) => handle_merge(ops, args),
+        Command::RenameHeading(args) => handle_rename_heading(ops, args),
+        Command::Graph(args) => handle_graph(ops, args),
+        Command::Dupes(args) => handle_dupes(ops, args),
+        Command::Stats(args) => handle_stats(ops, args),
     }
 }
 
@@ -37,9 +54,20 @@ fn handle_catalog(ops: &markdown_doc_ops::Operations, args: CatalogArgs) -> Resu
         format,
         output,
         no_ignore,
-        ..
+        check,
+        diff,
+        timestamp,
+        regen,
     } = args;
 
+    let mode = if check {
+        CatalogMode::Check
+    } else if diff {
+        CatalogMode::Diff
+    } else {
+        CatalogMode::Update
+    };
+
     let format = match format.unwrap_or(CatalogFormatValue::Markdown) {
         CatalogFormatValue::Markdown => CatalogFormat::Markdown,
         CatalogFormatValue::Json => CatalogFormat::Json,
@@ -48,29 +76,48 @@ fn handle_catalog(ops: &markdown_doc_ops::Operations, args: CatalogArgs) -> Resu
     let scan = ScanOptions {
         paths: path,
         staged,
+        changed_since: None,
         respect_ignore: !no_ignore,
     };
 
     let write_to_disk = matches!(format, CatalogFormat::Markdown);
+    let timestamp = timestamp.map(|value| match value {
+        CatalogTimestampValue::Now => CatalogTimestamp::Now,
+        CatalogTimestampValue::Git => CatalogTimestamp::Git,
+        CatalogTimestampValue::Omit => CatalogTimestamp::Omit,
+    });
     let options = CatalogOptions {
         scan,
         format,
         output_path: output,
         write_to_disk,
+        mode,
+        timestamp,
+        regen,
     };
 
-    let CatalogOutcome { rendered, .. } = ops.catalog(options)?;
+    let CatalogOutcome {
+        rendered,
+        exit_code,
+        ..
+    } = ops.catalog(options)?;
 
     emit(&rendered)?;
-    Ok(0)
+    Ok(exit_code)
 }
 
 fn handle_lint(ops: &markdown_doc_ops::Operations, args: LintArgs) -> Result<i32> {
     let LintArgs {
         path,
         staged,
+        since,
+        changed_from,
         format,
         no_ignore,
+        baseline,
+        write_baseline,
+        add_words,
+        no_cache,
     } = args;
 
     let format = match format.unwrap_or(LintFormatValue::Plain) {
@@ -82,10 +129,18 @@ fn handle_lint(ops: &markdown_doc_ops::Operations, args: LintArgs) -> Result<i32
     let scan = ScanOptions {
         paths: path,
         staged,
+        changed_since: changed_since(since, changed_from),
         respect_ignore: !no_ignore,
     };
 
-    let options = LintOptions { scan, format };
+    let options = LintOptions {
+        scan,
+        format,
+        baseline,
+        write_baseline,
+        add_words,
+        cache: !no_cache,
+    };
     let LintOutcome {
         rendered,
         exit_code,
@@ -96,10 +151,18 @@ fn handle_lint(ops: &markdown_doc_ops::Operations, args: LintArgs) -> Result<i32
     Ok(exit_code)
 }
 
+fn changed_since(since: Option<String>, changed_from: Option<String>) -> Option<ChangedSince> {
+    since
+        .map(ChangedSince::Revision)
+        .or(changed_from.map(ChangedSince::MergeBase))
+}
+
 fn handle_validate(ops: &markdown_doc_ops::Operations, args: ValidateArgs) -> Result<i32> {
     let ValidateArgs {
         path,
         staged,
+        since,
+        changed_from,
         format,
         schema,
         quiet,
@@ -114,6 +177,7 @@ fn handle_validate(ops: &markdown_doc_ops::Operations, args: ValidateArgs) -> Re
     let scan = ScanOptions {
         paths: path,
         staged,
+        changed_since: changed_since(since, changed_from),
         respect_ignore: !no_ignore,
     };
     let options = ValidateOptions {
@@ -151,12 +215,16 @@ fn handle_toc(ops: &markdown_doc_ops::Operations, args: TocArgs) -> Result<i32>
         check,
         update,
         diff,
+        insert,
+        at,
         no_ignore,
         quiet,
     } = args;
 
     let mode = if update {
         TocMode::Update
+    } else if insert {
+        TocMode::Insert
     } else if diff {
         TocMode::Diff
     } else {
@@ -168,10 +236,21 @@ fn handle_toc(ops: &markdown_doc_ops::Operations, args: TocArgs) -> Result<i32>
     let scan = ScanOptions {
         paths: path,
         staged,
+        changed_since: None,
         respect_ignore: !no_ignore,
     };
 
-    let options = TocOptions { scan, mode, quiet };
+    let insert_at = at.map(|position| match position {
+        TocPositionValue::AfterIntro => TocInsertPosition::AfterIntro,
+        TocPositionValue::BeforeFirstSection => TocInsertPosition::BeforeFirstSection,
+        TocPositionValue::ContentsHeading => TocInsertPosition::ContentsHeading,
+    });
+    let options = TocOptions {
+        scan,
+        mode,
+        quiet,
+        insert_at,
+    };
     let TocOutcome {
         rendered,
         exit_code,
@@ -188,6 +267,8 @@ fn handle_toc(ops: &markdown_doc_ops::Operations, args: TocArgs) -> Result<i32>
 fn handle_refs(ops: &markdown_doc_ops::Operations, args: RefsArgs) -> Result<i32> {
     let RefsArgs {
         pattern,
+        from,
+        aggregate,
         path,
         staged,
         format,
@@ -203,9 +284,15 @@ fn handle_refs(ops: &markdown_doc_ops::Operations, args: RefsArgs) -> Result<i32
     let scan = ScanOptions {
         paths: path,
         staged,
+        changed_since: None,
         respect_ignore: !no_ignore,
     };
 
+    let Some(pattern) = pattern else {
+        let from = from.expect("clap requires PATTERN or --from");
+        return handle_refs_from(ops, scan, from, aggregate, format);
+    };
+
     let options = RefsOptions {
         scan,
         pattern,
@@ -273,13 +360,279 @@ fn handle_refs(ops: &markdown_doc_ops::Operations, args: RefsArgs) -> Result<i32
     }
 }
 
+fn handle_refs_from(
+    ops: &markdown_doc_ops::Operations,
+    scan: ScanOptions,
+    from: PathBuf,
+    aggregate: bool,
+    format: RefsFormat,
+) -> Result<i32> {
+    let report = match ops.outbound(OutboundOptions {
+        scan,
+        from: from.clone(),
+        aggregate,
+    }) {
+        Ok(report) => report,
+        Err(OperationError::InvalidInput(message)) => {
+            eprintln!("{message}");
+            return Ok(1);
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    match format {
+        RefsFormat::Json => {
+            let links = |links: &[OutboundLink]| {
+                links
+                    .iter()
+                    .map(|link| {
+                        json!({
+                            "file": link.source,
+                            "line": link.line,
+                            "kind": link.kind.as_str(),
+                            "target": link.raw_target,
+                            "target_path": link.target_path,
+                            "target_anchor": link.target_anchor,
+                            "status": link.status.as_str(),
+                            "display": link.display,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let payload = json!({
+                "from": from,
+                "unresolved_count": report.unresolved_count,
+                "groups": report
+                    .groups
+                    .iter()
+                    .map(|group| json!({
+                        "path": group.path,
+                        "resolved": links(&group.resolved),
+                        "unresolved": links(&group.unresolved),
+                        "external": links(&group.external),
+                    }))
+                    .collect::<Vec<_>>(),
+            });
+            println!("{}", serde_json::to_string_pretty(&payload)?);
+        }
+        RefsFormat::Plain => {
+            for (idx, group) in report.groups.iter().enumerate() {
+                if idx > 0 {
+                    println!();
+                }
+                println!("{}", group.path.display());
+                for (label, links) in [
+                    ("resolved", &group.resolved),
+                    ("unresolved", &group.unresolved),
+                    ("external", &group.external),
+                ] {
+                    if links.is_empty() {
+                        continue;
+                    }
+                    println!("  {label} ({}):", links.len());
+                    for link in links {
+                        let status = match link.status {
+                            OutboundStatus::MissingFile | OutboundStatus::MissingAnchor => {
+                                format!(" [{}]", link.status.as_str())
+                            }
+                            _ => String::new(),
+                        };
+                        println!(
+                            "    {}:{} -> {}{status} | {}",
+                            link.source.display(),
+                            link.line,
+                            link.raw_target,
+                            link.display.trim()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(if report.unresolved_count > 0 { 1 } else { 0 })
+}
+
+fn handle_search(ops: &markdown_doc_ops::Operations, args: SearchArgs) -> Result<i32> {
+    let SearchArgs {
+        query,
+        query_flag,
+        path,
+        staged,
+        limit,
+        format,
+        index,
+        no_index_refresh,
+        no_ignore,
+    } = args;
+
+    let Some(query) = query_flag.or(query) else {
+        eprintln!("A search query is required (QUERY or --query).");
+        return Ok(1);
+    };
+    let format = format.unwrap_or(SearchFormatValue::Plain);
+
+    let options = SearchOptions {
+        scan: ScanOptions {
+            paths: path,
+            staged,
+            changed_since: None,
+            respect_ignore: !no_ignore,
+        },
+        query,
+        limit,
+        index_path: index,
+        refresh: !no_index_refresh,
+    };
+
+    match ops.search(options) {
+        Ok(outcome) => {
+            match format {
+                SearchFormatValue::Json => {
+                    let payload = json!({
+                        "query": outcome.query,
+                        "results": outcome
+                            .results
+                            .iter()
+                            .map(|result| {
+                                json!({
+                                    "file": result.path,
+                                    "line": result.line,
+                                    "heading": result.heading,
+                                    "anchor": result.anchor,
+                                    "score": result.score,
+                                    "terms": result.terms,
+                                    "snippet": result
+                                        .snippet
+                                        .iter()
+                                        .map(|line| {
+                                            json!({
+                                                "line": line.line,
+                                                "text": line.text,
+                                                "highlights": line.highlights,
+                                            })
+                                        })
+                                        .collect::<Vec<_>>(),
+                                })
+                            })
+                            .collect::<Vec<_>>(),
+                        "index": {
+                            "path": outcome.index.path,
+                            "files": outcome.index.files,
+                            "sections": outcome.index.sections,
+                            "refreshed": outcome.index.refreshed,
+                            "bytes": outcome.index.bytes,
+                        },
+                        "duration_ms": outcome.duration.as_millis(),
+                    });
+                    println!("{}", serde_json::to_string_pretty(&payload)?);
+                }
+                SearchFormatValue::Plain => {
+                    if outcome.results.is_empty() {
+                        println!("No results for '{}'.", outcome.query);
+                    } else {
+                        for result in &outcome.results {
+                            let heading = result.heading.as_deref().unwrap_or("(preamble)");
+                            let anchor = result
+                                .anchor
+                                .as_ref()
+                                .map(|anchor| format!(" #{anchor}"))
+                                .unwrap_or_default();
+                            println!(
+                                "{}:{} {}{} (score {:.2})",
+                                result.path.display(),
+                                result.line,
+                                heading,
+                                anchor,
+                                result.score
+                            );
+                            for line in &result.snippet {
+                                println!("    {}: {}", line.line, highlight_snippet(line));
+                            }
+                        }
+                        println!(
+                            "{} result(s) for '{}' in {} ms (index: {} files, {} refreshed)",
+                            outcome.results.len(),
+                            outcome.query,
+                            outcome.duration.as_millis(),
+                            outcome.index.files,
+                            outcome.index.refreshed
+                        );
+                    }
+                }
+            }
+
+            Ok(outcome.exit_code)
+        }
+        Err(OperationError::Io { path, source }) => {
+            eprintln!("I/O error on {}: {}", path.display(), source);
+            Ok(4)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn handle_watch(ops: &markdown_doc_ops::Operations, args: WatchArgs) -> Result<i32> {
+    let WatchArgs {
+        lint,
+        toc,
+        catalog,
+        path,
+        no_ignore,
+        debounce,
+    } = args;
+
+    // Without a selection every task runs.
+    let all = !(lint || toc || catalog);
+    let options = WatchOptions {
+        scan: ScanOptions {
+            paths: path,
+            staged: false,
+            changed_since: None,
+            respect_ignore: !no_ignore,
+        },
+        lint: lint || all,
+        toc: toc || all,
+        catalog: catalog || all,
+        debounce: Duration::from_millis(debounce),
+    };
+
+    let mut session = ops.watch_session(options);
+    emit(&session.scan()?.rendered)?;
+    session.run(|report| {
+        println!();
+        println!("{}", report.rendered);
+        ControlFlow::Continue(())
+    })?;
+    Ok(0)
+}
+
+/// Wrap matched terms in `**` for plain output.
+fn highlight_snippet(line: &SnippetLine) -> String {
+    let mut rendered = String::with_capacity(line.text.len());
+    let mut cursor = 0;
+    for &(start, end) in &line.highlights {
+        rendered.push_str(&line.text[cursor..start]);
+        rendered.push_str("**");
+        rendered.push_str(&line.text[start..end]);
+        rendered.push_str("**");
+        cursor = end;
+    }
+    rendered.push_str(&line.text[cursor..]);
+    rendered
+}
+
 fn handle_mv(ops: &markdown_doc_ops::Operations, args: MvArgs) -> Result<i32> {
     let MvArgs {
         source,
         destination,
+        map,
         dry_run,
         force,
         no_backup,
+        git,
+        no_git,
+        allow_dirty,
         quiet,
         json,
         no_ignore,
@@ -288,21 +641,125 @@ fn handle_mv(ops: &markdown_doc_ops::Operations, args: MvArgs) -> Result<i32> {
     let scan = ScanOptions {
         paths: Vec::new(),
         staged: false,
+        changed_since: None,
         respect_ignore: !no_ignore,
     };
 
-    let options = MvOptions {
-        scan,
-        source,
-        destination,
+    let moves = match (map, source, destination) {
+        (Some(map), _, _) => load_move_map(&map),
+        (None, Some(from), Some(to)) => Ok(vec![FileMove { from, to }]),
+        _ => unreachable!("clap requires SOURCE and DEST without --map"),
+    };
+
+    let result = moves.and_then(|moves| {
+        ops.mv(MvOptions {
+            scan,
+            moves,
+            dry_run,
+            force,
+            create_backup: !no_backup,
+            git: match (git, no_git) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            allow_dirty,
+            quiet,
+            json,
+        })
+    });
+
+    report_mv_outcome(result, json, quiet)
+}
+
+fn handle_split(ops: &markdown_doc_ops::Operations, args: SplitArgs) -> Result<i32> {
+    let SplitArgs {
+        file,
+        section,
+        to,
+        stub,
+        dry_run,
+        no_backup,
+        quiet,
+        json,
+        no_ignore,
+    } = args;
+
+    let result = ops.split(SplitOptions {
+        scan: ScanOptions {
+            respect_ignore: !no_ignore,
+            ..ScanOptions::default()
+        },
+        source: file,
+        section,
+        destination: to,
+        stub,
         dry_run,
-        force,
         create_backup: !no_backup,
+    });
+    report_mv_outcome(result, json, quiet)
+}
+
+fn handle_merge(ops: &markdown_doc_ops::Operations, args: MergeArgs) -> Result<i32> {
+    let MergeArgs {
+        file,
+        into,
+        dry_run,
+        no_backup,
         quiet,
         json,
-    };
+        no_ignore,
+    } = args;
+
+    let result = ops.merge(MergeOptions {
+        scan: ScanOptions {
+            respect_ignore: !no_ignore,
+            ..ScanOptions::default()
+        },
+        source: file,
+        destination: into,
+        dry_run,
+        create_backup: !no_backup,
+    });
+    report_mv_outcome(result, json, quiet)
+}
+
+fn handle_rename_heading(
+    ops: &markdown_doc_ops::Operations,
+    args: RenameHeadingArgs,
+) -> Result<i32> {
+    let RenameHeadingArgs {
+        file,
+        old,
+        new,
+        dry_run,
+        no_backup,
+        quiet,
+        json,
+        no_ignore,
+    } = args;
+
+    let result = ops.rename_heading(RenameHeadingOptions {
+        scan: ScanOptions {
+            respect_ignore: !no_ignore,
+            ..ScanOptions::default()
+        },
+        path: file,
+        heading: old,
+        new_heading: new,
+        dry_run,
+        create_backup: !no_backup,
+    });
+    report_mv_outcome(result, json, quiet)
+}
 
-    match ops.mv(options) {
+/// Render the per-file changes shared by `mv`, `split`, `merge`, and `rename-heading`.
+fn report_mv_outcome(
+    result: Result<MvOutcome, OperationError>,
+    json: bool,
+    quiet: bool,
+) -> Result<i32> {
+    match result {
         Ok(outcome) => {
             if json {
                 let payload = json!({
@@ -315,10 +772,13 @@ fn handle_mv(ops: &markdown_doc_ops::Operations, args: MvArgs) -> Result<i32> {
                                 MvFileStatus::Updated => "updated",
                                 MvFileStatus::Relocated => "relocated",
                                 MvFileStatus::Unchanged => "unchanged",
+                                MvFileStatus::Created => "created",
+                                MvFileStatus::Removed => "removed",
                             },
                             "diff": change.diff
                         })
-                    }).collect::<Vec<_>>()
+                    }).collect::<Vec<_>>(),
+                    "dirty": outcome.dirty,
                 });
                 println!("{}", serde_json::to_string_pretty(&payload)?);
             } else if !quiet {
@@ -337,6 +797,12 @@ fn handle_mv(ops: &markdown_doc_ops::Operations, args: MvArgs) -> Result<i32> {
                         MvFileStatus::Unchanged => {
                             println!("ℹ️  no changes for {}", change.original_path.display());
                         }
+                        MvFileStatus::Created => {
+                            println!("🆕 created {}", change.output_path.display());
+                        }
+                        MvFileStatus::Removed => {
+                            println!("🗑️  removed {}", change.original_path.display());
+                        }
                     }
                     if let Some(diff) = &change.diff {
                         print!("{diff}");
@@ -346,6 +812,17 @@ fn handle_mv(ops: &markdown_doc_ops::Operations, args: MvArgs) -> Result<i32> {
                     }
                 }
             }
+            if !json && !outcome.dirty.is_empty() {
+                eprintln!(
+                    "⚠️  uncommitted changes in {}; a real run needs a clean tree or --allow-dirty",
+                    outcome
+                        .dirty
+                        .iter()
+                        .map(|path| format!("'{}'", path.display()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
             Ok(outcome.exit_code)
         }
         Err(OperationError::InvalidInput(message)) => {
@@ -360,6 +837,443 @@ fn handle_mv(ops: &markdown_doc_ops::Operations, args: MvArgs) -> Result<i32> {
     }
 }
 
+fn handle_graph(ops: &markdown_doc_ops::Operations, args: GraphArgs) -> Result<i32> {
+    let GraphArgs {
+        path,
+        staged,
+        orphans,
+        format,
+        anchors,
+        include,
+        root,
+        depth,
+        no_ignore,
+    } = args;
+
+    let scan = ScanOptions {
+        paths: path,
+        staged,
+        changed_since: None,
+        respect_ignore: !no_ignore,
+    };
+    let format = format.unwrap_or(GraphFormatValue::Plain);
+
+    if orphans {
+        let report = ops.orphans(scan)?;
+        match format {
+            GraphFormatValue::Json => {
+                let payload = json!({
+                    "roots": report.roots,
+                    "orphans": report.orphans,
+                    "unreachable": report.unreachable,
+                });
+                println!("{}", serde_json::to_string_pretty(&payload)?);
+            }
+            GraphFormatValue::Plain => {
+                for orphan in &report.orphans {
+                    println!("{} (no inbound links)", orphan.display());
+                }
+                for entry in &report.unreachable {
+                    println!("{} (unreachable from roots)", entry.display());
+                }
+                if report.is_empty() {
+                    println!("✅ No orphaned or unreachable documents");
+                } else {
+                    println!(
+                        "\n{} orphaned, {} unreachable",
+                        report.orphans.len(),
+                        report.unreachable.len()
+                    );
+                }
+            }
+            GraphFormatValue::Dot | GraphFormatValue::Mermaid | GraphFormatValue::Graphml => {
+                eprintln!("--orphans supports only plain and json output");
+                return Ok(1);
+            }
+        }
+        return Ok(if report.is_empty() { 0 } else { 1 });
+    }
+
+    let export = match ops.export_graph(GraphExportOptions {
+        scan,
+        anchors,
+        include,
+        root,
+        depth,
+    }) {
+        Ok(export) => export,
+        Err(OperationError::InvalidInput(message)) => {
+            eprintln!("{message}");
+            return Ok(1);
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    // Plain and JSON keep one edge per source/target pair, whatever the link kind.
+    let pairs: BTreeSet<(&str, &str)> = export
+        .edges
+        .iter()
+        .filter(|edge| edge.kind != GraphEdgeKind::Contains)
+        .map(|edge| (edge.source.as_str(), edge.target.as_str()))
+        .collect();
+    match format {
+        GraphFormatValue::Json => {
+            let payload = json!({
+                "nodes": export
+                    .nodes
+                    .iter()
+                    .map(|node| json!({
+                        "id": node.id,
+                        "kind": node.kind.as_str(),
+                        "path": node.path,
+                        "anchor": node.anchor,
+                        "label": node.label,
+                    }))
+                    .collect::<Vec<_>>(),
+                "edges": pairs
+                    .iter()
+                    .map(|(source, target)| json!({ "source": source, "target": target }))
+                    .collect::<Vec<_>>(),
+            });
+            println!("{}", serde_json::to_string_pretty(&payload)?);
+        }
+        GraphFormatValue::Dot => print!("{}", export.to_dot()),
+        GraphFormatValue::Mermaid => print!("{}", export.to_mermaid()),
+        GraphFormatValue::Graphml => print!("{}", export.to_graphml()),
+        GraphFormatValue::Plain => {
+            for (source, target) in &pairs {
+                println!("{source} -> {target}");
+            }
+        }
+    }
+    Ok(0)
+}
+
+fn handle_stats(ops: &markdown_doc_ops::Operations, args: StatsArgs) -> Result<i32> {
+    let StatsArgs {
+        path,
+        staged,
+        format,
+        top,
+        summary,
+        no_header,
+        no_ignore,
+    } = args;
+
+    let report = ops.stats(StatsOptions {
+        scan: ScanOptions {
+            paths: path,
+            staged,
+            changed_since: None,
+            respect_ignore: !no_ignore,
+        },
+        top,
+    })?;
+
+    match format.unwrap_or(StatsFormatValue::Plain) {
+        StatsFormatValue::Json => {
+            let mut payload = json!({ "summary": stats_summary_json(&report) });
+            if !summary {
+                payload["files"] = report.files.iter().map(stats_file_json).collect();
+                payload["largest_sections"] = report
+                    .largest_sections
+                    .iter()
+                    .map(|section| {
+                        json!({
+                            "path": section.path,
+                            "heading": section.heading,
+                            "anchor": section.anchor,
+                            "line": section.line,
+                            "words": section.words,
+                        })
+                    })
+                    .collect();
+                payload["oldest"] = report
+                    .oldest
+                    .iter()
+                    .map(|file| json!({ "path": file.path, "last_modified": file.last_modified }))
+                    .collect();
+            }
+            println!("{}", serde_json::to_string_pretty(&payload)?);
+        }
+        StatsFormatValue::Csv => {
+            let totals = &report.summary;
+            if summary {
+                if !no_header {
+                    println!(
+                        "generated_at,files,words,h1,h2,h3,h4,h5,h6,internal_links,external_links,broken_links,inbound_links,orphans,lint_findings,lint_density,schema_conforming,schema_conformance"
+                    );
+                }
+                println!(
+                    "{},{},{},{},{},{},{},{},{},{},{:.2},{},{:.4}",
+                    report.generated_at,
+                    totals.files,
+                    totals.words,
+                    csv_headings(&totals.headings),
+                    totals.links.internal,
+                    totals.links.external,
+                    totals.links.broken,
+                    totals.inbound_links,
+                    totals.orphans,
+                    totals.lint_findings,
+                    totals.lint_density,
+                    totals.schema_conforming,
+                    totals.schema_conformance,
+                );
+            } else {
+                if !no_header {
+                    println!(
+                        "path,words,h1,h2,h3,h4,h5,h6,internal_links,external_links,broken_links,inbound_links,orphan,lint_findings,schema,schema_conforms,last_modified"
+                    );
+                }
+                for file in &report.files {
+                    println!(
+                        "{},{},{},{},{},{},{},{},{},{},{},{}",
+                        csv_field(&file.path.display().to_string()),
+                        file.words,
+                        csv_headings(&file.headings),
+                        file.links.internal,
+                        file.links.external,
+                        file.links.broken,
+                        file.inbound_links,
+                        file.orphan,
+                        file.lint_findings,
+                        csv_field(&file.schema),
+                        file.schema_conforms,
+                        file.last_modified.as_deref().unwrap_or(""),
+                    );
+                }
+            }
+        }
+        StatsFormatValue::Plain => print!("{}", render_stats_plain(&report, summary)),
+    }
+
+    Ok(0)
+}
+
+fn stats_summary_json(report: &StatsReport) -> serde_json::Value {
+    let totals = &report.summary;
+    json!({
+        "generated_at": report.generated_at,
+        "files": totals.files,
+        "words": totals.words,
+        "headings": headings_json(&totals.headings),
+        "links": {
+            "internal": totals.links.internal,
+            "external": totals.links.external,
+            "broken": totals.links.broken,
+        },
+        "inbound_links": totals.inbound_links,
+        "orphans": totals.orphans,
+        "lint_findings": totals.lint_findings,
+        "lint_density": (totals.lint_density * 100.0).round() / 100.0,
+        "schema_conforming": totals.schema_conforming,
+        "schema_conformance": (totals.schema_conformance * 10000.0).round() / 10000.0,
+    })
+}
+
+fn stats_file_json(file: &FileStats) -> serde_json::Value {
+    json!({
+        "path": file.path,
+        "words": file.words,
+        "headings": headings_json(&file.headings),
+        "links": {
+            "internal": file.links.internal,
+            "external": file.links.external,
+            "broken": file.links.broken,
+        },
+        "inbound_links": file.inbound_links,
+        "orphan": file.orphan,
+        "lint_findings": file.lint_findings,
+        "schema": file.schema,
+        "schema_conforms": file.schema_conforms,
+        "last_modified": file.last_modified,
+    })
+}
+
+fn headings_json(headings: &[usize; 6]) -> serde_json::Value {
+    json!({
+        "h1": headings[0],
+        "h2": headings[1],
+        "h3": headings[2],
+        "h4": headings[3],
+        "h5": headings[4],
+        "h6": headings[5],
+    })
+}
+
+fn csv_headings(headings: &[usize; 6]) -> String {
+    headings
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quote a CSV field when it contains a delimiter, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_stats_plain(report: &StatsReport, summary_only: bool) -> String {
+    let totals = &report.summary;
+    let mut out = String::new();
+    out.push_str(&format!(
+        "📊 Documentation stats ({} files)\n\n",
+        totals.files
+    ));
+    out.push_str(&format!("  Words:              {}\n", totals.words));
+    out.push_str(&format!(
+        "  Headings:           {}\n",
+        totals
+            .headings
+            .iter()
+            .enumerate()
+            .map(|(depth, count)| format!("h{} {}", depth + 1, count))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    ));
+    out.push_str(&format!(
+        "  Links:              {} internal, {} external, {} broken\n",
+        totals.links.internal, totals.links.external, totals.links.broken
+    ));
+    out.push_str(&format!("  Inbound links:      {}\n", totals.inbound_links));
+    out.push_str(&format!("  Orphans:            {}\n", totals.orphans));
+    out.push_str(&format!(
+        "  Lint findings:      {} ({:.2} per 1,000 words)\n",
+        totals.lint_findings, totals.lint_density
+    ));
+    out.push_str(&format!(
+        "  Schema conformance: {}/{} ({:.1}%)\n",
+        totals.schema_conforming,
+        totals.files,
+        totals.schema_conformance * 100.0
+    ));
+    if summary_only {
+        return out;
+    }
+
+    if !report.largest_sections.is_empty() {
+        out.push_str("\nLargest sections:\n");
+        for section in &report.largest_sections {
+            out.push_str(&format!(
+                "  {}:{} {} #{} ({} words)\n",
+                section.path.display(),
+                section.line,
+                section.heading,
+                section.anchor,
+                section.words
+            ));
+        }
+    }
+    if !report.oldest.is_empty() {
+        out.push_str("\nOldest documents:\n");
+        for file in &report.oldest {
+            out.push_str(&format!(
+                "  {} ({})\n",
+                file.path.display(),
+                file.last_modified.as_deref().unwrap_or("unknown")
+            ));
+        }
+    }
+    if !report.files.is_empty() {
+        out.push_str("\nPer file (words, links int/ext/broken, inbound, findings, schema):\n");
+        for file in &report.files {
+            out.push_str(&format!(
+                "  {} — {} words, {}/{}/{} links, {} inbound, {} findings, {} {}{}\n",
+                file.path.display(),
+                file.words,
+                file.links.internal,
+                file.links.external,
+                file.links.broken,
+                file.inbound_links,
+                file.lint_findings,
+                file.schema,
+                if file.schema_conforms { "✅" } else { "❌" },
+                if file.orphan { " (orphan)" } else { "" }
+            ));
+        }
+    }
+    out
+}
+
+fn handle_dupes(ops: &markdown_doc_ops::Operations, args: DupesArgs) -> Result<i32> {
+    let DupesArgs {
+        path,
+        staged,
+        format,
+        no_ignore,
+    } = args;
+
+    let scan = ScanOptions {
+        paths: path,
+        staged,
+        changed_since: None,
+        respect_ignore: !no_ignore,
+    };
+    let report = ops.duplicates(scan)?;
+
+    match format.unwrap_or(DupesFormatValue::Plain) {
+        DupesFormatValue::Json => {
+            let clusters: Vec<_> = report
+                .clusters
+                .iter()
+                .map(|cluster| {
+                    json!({
+                        "heading": cluster.heading,
+                        "similarity": (cluster.similarity * 100.0).round() / 100.0,
+                        "sections": cluster
+                            .sections
+                            .iter()
+                            .map(|section| {
+                                json!({
+                                    "path": section.path,
+                                    "anchor": section.anchor,
+                                    "line": section.line,
+                                    "byte_range": {
+                                        "start": section.byte_range.start,
+                                        "end": section.byte_range.end,
+                                    },
+                                })
+                            })
+                            .collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            let payload = json!({ "clusters": clusters });
+            println!("{}", serde_json::to_string_pretty(&payload)?);
+        }
+        DupesFormatValue::Plain => {
+            for cluster in &report.clusters {
+                println!(
+                    "'{}' ({:.0}% similar)",
+                    cluster.heading,
+                    cluster.similarity * 100.0
+                );
+                for section in &cluster.sections {
+                    println!(
+                        "  {}:{} #{}",
+                        section.path.display(),
+                        section.line,
+                        section.anchor
+                    );
+                }
+            }
+            if report.is_empty() {
+                println!("✅ No duplicate sections");
+            } else {
+                println!("\n{} duplicate cluster(s)", report.clusters.len());
+            }
+        }
+    }
+
+    Ok(if report.is_empty() { 0 } else { 1 })
+}
+
 fn emit(content: &str) -> Result<()> {
     print!("{}", content);
     if !content.ends_with('\n') {
@@ -392,8 +1306,24 @@ enum Command {
     Toc(TocArgs),
     /// List references to files or anchors
     Refs(RefsArgs),
-    /// Move or rename a Markdown file and update references
+    /// Search document sections using the persistent index
+    Search(SearchArgs),
+    /// Re-run lint, TOC, and catalog updates as files change
+    Watch(WatchArgs),
+    /// Move or rename Markdown files, directories, or assets and update references
     Mv(MvArgs),
+    /// Move a section into another document and redirect links to its anchors
+    Split(SplitArgs),
+    /// Fold a document into another and redirect links to it
+    Merge(MergeArgs),
+    /// Rename a heading and update links to its anchor
+    RenameHeading(RenameHeadingArgs),
+    /// Inspect the document link graph
+    Graph(GraphArgs),
+    /// Report near-duplicate sections across documents
+    Dupes(DupesArgs),
+    /// Report documentation health metrics
+    Stats(StatsArgs),
 }
 
 #[derive(Args)]
@@ -407,15 +1337,23 @@ struct CatalogArgs {
     /// Output format (markdown or json)
     #[arg(long, value_enum)]
     format: Option<CatalogFormatValue>,
-    /// Override catalog output path (markdown only)
+    /// Override catalog output path (written for markdown, compared by --check/--diff)
     #[arg(long)]
     output: Option<PathBuf>,
     /// Disable `.markdown-doc-ignore` filtering
     #[arg(long = "no-ignore")]
     no_ignore: bool,
-    /// Force regeneration (reserved for future use)
+    /// Exit non-zero when the catalog on disk is stale (ignores the timestamp)
+    #[arg(long, conflicts_with_all = ["diff", "regen"])]
+    check: bool,
+    /// Print a unified diff against the catalog on disk and exit non-zero when stale
+    #[arg(long, conflicts_with_all = ["check", "regen"])]
+    diff: bool,
+    /// Timestamp source (defaults to `catalog.timestamp`)
+    #[arg(long, value_enum, value_name = "SOURCE")]
+    timestamp: Option<CatalogTimestampValue>,
+    /// Rewrite the catalog even when only its timestamp would change
     #[arg(long)]
-    #[allow(unused)]
     regen: bool,
 }
 
@@ -424,15 +1362,33 @@ struct LintArgs {
     /// Restrict linting to specific paths
     #[arg(long = "path", value_name = "PATH", action = ArgAction::Append)]
     path: Vec<PathBuf>,
-    /// Limit linting to staged files
+    /// Limit linting to staged files (checks their staged contents)
     #[arg(long)]
     staged: bool,
+    /// Limit linting to files changed since REV (committed or not)
+    #[arg(long, value_name = "REV", conflicts_with_all = ["staged", "changed_from"])]
+    since: Option<String>,
+    /// Limit linting to files changed on this branch since it forked from BASE
+    #[arg(long = "changed-from", value_name = "BASE", conflicts_with = "staged")]
+    changed_from: Option<String>,
     /// Select lint output format
     #[arg(long, value_enum)]
     format: Option<LintFormatValue>,
     /// Disable `.markdown-doc-ignore` filtering
     #[arg(long = "no-ignore")]
     no_ignore: bool,
+    /// Suppress findings recorded in a baseline file; only new findings fail
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+    /// Record current findings into the baseline (`--baseline` or `.markdown-doc-baseline.json`)
+    #[arg(long = "write-baseline")]
+    write_baseline: bool,
+    /// Append words flagged by the `spelling` rule to the project word list
+    #[arg(long = "add-words")]
+    add_words: bool,
+    /// Ignore and do not update the lint cache under `.markdown-doc/cache`
+    #[arg(long = "no-cache")]
+    no_cache: bool,
 }
 
 #[derive(Args)]
@@ -443,6 +1399,12 @@ struct ValidateArgs {
     /// Limit validation to staged files
     #[arg(long)]
     staged: bool,
+    /// Limit validation to files changed since REV (committed or not)
+    #[arg(long, value_name = "REV", conflicts_with_all = ["staged", "changed_from"])]
+    since: Option<String>,
+    /// Limit validation to files changed on this branch since it forked from BASE
+    #[arg(long = "changed-from", value_name = "BASE", conflicts_with = "staged")]
+    changed_from: Option<String>,
     /// Select validate output format
     #[arg(long, value_enum)]
     format: Option<ValidateFormatValue>,
@@ -466,14 +1428,20 @@ struct TocArgs {
     #[arg(long)]
     staged: bool,
     /// Report differences without modifying files (default)
-    #[arg(long, conflicts_with_all = ["update", "diff"])]
+    #[arg(long, conflicts_with_all = ["update", "diff", "insert"])]
     check: bool,
     /// Rewrite TOC blocks in place
-    #[arg(long, conflicts_with_all = ["check", "diff"])]
+    #[arg(long, conflicts_with_all = ["check", "diff", "insert"])]
     update: bool,
     /// Print unified diffs for out-of-sync TOCs
-    #[arg(long, conflicts_with_all = ["check", "update"])]
+    #[arg(long, conflicts_with_all = ["check", "update", "insert"])]
     diff: bool,
+    /// Rewrite TOC blocks and add one to files without markers
+    #[arg(long, conflicts_with_all = ["check", "update", "diff"])]
+    insert: bool,
+    /// Where --insert places new blocks (defaults to `lint.toc.insert_at`)
+    #[arg(long, value_enum, value_name = "POSITION", requires = "insert")]
+    at: Option<TocPositionValue>,
     /// Disable `.markdown-doc-ignore` filtering
     #[arg(long = "no-ignore")]
     no_ignore: bool,
@@ -485,8 +1453,14 @@ struct TocArgs {
 #[derive(Args)]
 struct RefsArgs {
     /// Pattern to match (path, glob, or anchor slug)
-    #[arg(value_name = "PATTERN")]
-    pattern: String,
+    #[arg(value_name = "PATTERN", required_unless_present = "from")]
+    pattern: Option<String>,
+    /// List the links made by this file (or every document under this directory)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["pattern", "anchor_only"])]
+    from: Option<PathBuf>,
+    /// With --from DIR, report all documents as a single group
+    #[arg(long, requires = "from")]
+    aggregate: bool,
     /// Restrict search to specific paths
     #[arg(long = "path", value_name = "PATH", action = ArgAction::Append)]
     path: Vec<PathBuf>,
@@ -504,14 +1478,75 @@ struct RefsArgs {
     no_ignore: bool,
 }
 
+#[derive(Args)]
+struct SearchArgs {
+    /// Search query
+    #[arg(value_name = "QUERY")]
+    query: Option<String>,
+    /// Search query (alternative to the positional argument)
+    #[arg(
+        short = 'q',
+        long = "query",
+        value_name = "QUERY",
+        conflicts_with = "query"
+    )]
+    query_flag: Option<String>,
+    /// Restrict results to specific paths
+    #[arg(long = "path", value_name = "PATH", action = ArgAction::Append)]
+    path: Vec<PathBuf>,
+    /// Restrict results to staged files
+    #[arg(long)]
+    staged: bool,
+    /// Maximum number of results
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    limit: usize,
+    /// Select search output format
+    #[arg(long, value_enum)]
+    format: Option<SearchFormatValue>,
+    /// Index location (overrides `search.index`)
+    #[arg(long, value_name = "PATH")]
+    index: Option<PathBuf>,
+    /// Use the existing index without re-parsing changed files
+    #[arg(long = "no-index-refresh")]
+    no_index_refresh: bool,
+    /// Disable `.markdown-doc-ignore` filtering
+    #[arg(long = "no-ignore")]
+    no_ignore: bool,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    /// Re-lint changed documents and the documents linking to them
+    #[arg(long)]
+    lint: bool,
+    /// Update TOC blocks in changed documents
+    #[arg(long)]
+    toc: bool,
+    /// Regenerate the catalog after changes
+    #[arg(long)]
+    catalog: bool,
+    /// Restrict watching to specific paths
+    #[arg(long = "path", value_name = "PATH", action = ArgAction::Append)]
+    path: Vec<PathBuf>,
+    /// Disable `.markdown-doc-ignore` filtering
+    #[arg(long = "no-ignore")]
+    no_ignore: bool,
+    /// Quiet period in milliseconds before a batch of changes is processed
+    #[arg(long, value_name = "MS", default_value_t = 300)]
+    debounce: u64,
+}
+
 #[derive(Args)]
 struct MvArgs {
-    /// Source Markdown file (relative to project root)
-    #[arg(value_name = "SOURCE")]
-    source: PathBuf,
-    /// Destination Markdown file
-    #[arg(value_name = "DEST")]
-    destination: PathBuf,
+    /// Source file or directory (relative to project root)
+    #[arg(value_name = "SOURCE", required_unless_present = "map")]
+    source: Option<PathBuf>,
+    /// Destination file or directory
+    #[arg(value_name = "DEST", required_unless_present = "map")]
+    destination: Option<PathBuf>,
+    /// Apply a batch of moves from a CSV (`source,destination`) or JSON mapping file
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["source", "destination"])]
+    map: Option<PathBuf>,
     /// Preview changes without writing
     #[arg(long = "dry-run")]
     dry_run: bool,
@@ -521,6 +1556,100 @@ struct MvArgs {
     /// Disable .bak backups for modified files
     #[arg(long = "no-backup")]
     no_backup: bool,
+    /// Move with `git mv` and stage updated files instead of writing backups
+    /// (default when inside a git repository)
+    #[arg(long, conflicts_with = "no_git")]
+    git: bool,
+    /// Move with plain file renames even inside a git repository
+    #[arg(long = "no-git")]
+    no_git: bool,
+    /// In git mode, allow touching files with uncommitted changes
+    #[arg(long = "allow-dirty")]
+    allow_dirty: bool,
+    /// Suppress per-file logs (errors still printed)
+    #[arg(long = "quiet")]
+    quiet: bool,
+    /// Emit machine-readable JSON summary
+    #[arg(long = "json")]
+    json: bool,
+    /// Ignore `.markdown-doc-ignore` patterns
+    #[arg(long = "no-ignore")]
+    no_ignore: bool,
+}
+
+#[derive(Args)]
+struct SplitArgs {
+    /// Markdown file containing the section
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+    /// Heading regex (case-insensitive) or `#anchor` selecting the section to move
+    #[arg(long, value_name = "PATTERN")]
+    section: String,
+    /// Markdown file receiving the section (created if missing, appended otherwise)
+    #[arg(long, value_name = "FILE")]
+    to: PathBuf,
+    /// Keep the heading in FILE with a link to the section's new location
+    #[arg(long)]
+    stub: bool,
+    /// Preview changes without writing
+    #[arg(long = "dry-run")]
+    dry_run: bool,
+    /// Disable .bak backups for modified files
+    #[arg(long = "no-backup")]
+    no_backup: bool,
+    /// Suppress per-file logs (errors still printed)
+    #[arg(long = "quiet")]
+    quiet: bool,
+    /// Emit machine-readable JSON summary
+    #[arg(long = "json")]
+    json: bool,
+    /// Ignore `.markdown-doc-ignore` patterns
+    #[arg(long = "no-ignore")]
+    no_ignore: bool,
+}
+
+#[derive(Args)]
+struct MergeArgs {
+    /// Markdown file to fold into another and remove
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+    /// Existing Markdown file the content is appended to
+    #[arg(long, value_name = "FILE")]
+    into: PathBuf,
+    /// Preview changes without writing
+    #[arg(long = "dry-run")]
+    dry_run: bool,
+    /// Disable .bak backups for modified files
+    #[arg(long = "no-backup")]
+    no_backup: bool,
+    /// Suppress per-file logs (errors still printed)
+    #[arg(long = "quiet")]
+    quiet: bool,
+    /// Emit machine-readable JSON summary
+    #[arg(long = "json")]
+    json: bool,
+    /// Ignore `.markdown-doc-ignore` patterns
+    #[arg(long = "no-ignore")]
+    no_ignore: bool,
+}
+
+#[derive(Args)]
+struct RenameHeadingArgs {
+    /// Markdown file containing the heading
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+    /// Current heading text, or `#anchor`
+    #[arg(value_name = "OLD")]
+    old: String,
+    /// New heading text
+    #[arg(value_name = "NEW")]
+    new: String,
+    /// Preview changes without writing
+    #[arg(long = "dry-run")]
+    dry_run: bool,
+    /// Disable .bak backups for modified files
+    #[arg(long = "no-backup")]
+    no_backup: bool,
     /// Suppress per-file logs (errors still printed)
     #[arg(long = "quiet")]
     quiet: bool,
@@ -532,12 +1661,105 @@ struct MvArgs {
     no_ignore: bool,
 }
 
+#[derive(Args)]
+struct GraphArgs {
+    /// Restrict the graph to specific paths
+    #[arg(long = "path", value_name = "PATH", action = ArgAction::Append)]
+    path: Vec<PathBuf>,
+    /// Limit the graph to staged files
+    #[arg(long)]
+    staged: bool,
+    /// Report documents with no inbound links or unreachable from `lint.orphan_roots`
+    #[arg(long)]
+    orphans: bool,
+    /// Select graph output format
+    #[arg(long, value_enum)]
+    format: Option<GraphFormatValue>,
+    /// Add anchor nodes and point links with a known fragment at them
+    #[arg(long)]
+    anchors: bool,
+    /// Keep only documents matching this glob (repeatable)
+    #[arg(long = "include", value_name = "GLOB", action = ArgAction::Append)]
+    include: Vec<String>,
+    /// Keep only documents reachable from this document
+    #[arg(long, value_name = "FILE")]
+    root: Option<PathBuf>,
+    /// Maximum number of link hops from `--root`
+    #[arg(long, value_name = "N", requires = "root")]
+    depth: Option<usize>,
+    /// Disable `.markdown-doc-ignore` filtering
+    #[arg(long = "no-ignore")]
+    no_ignore: bool,
+}
+
+#[derive(Args)]
+struct DupesArgs {
+    /// Restrict the comparison to specific paths
+    #[arg(long = "path", value_name = "PATH", action = ArgAction::Append)]
+    path: Vec<PathBuf>,
+    /// Limit the comparison to staged files
+    #[arg(long)]
+    staged: bool,
+    /// Select dupes output format
+    #[arg(long, value_enum)]
+    format: Option<DupesFormatValue>,
+    /// Disable `.markdown-doc-ignore` filtering
+    #[arg(long = "no-ignore")]
+    no_ignore: bool,
+}
+
+#[derive(Args)]
+struct StatsArgs {
+    /// Restrict metrics to specific paths
+    #[arg(long = "path", value_name = "PATH", action = ArgAction::Append)]
+    path: Vec<PathBuf>,
+    /// Limit metrics to staged files
+    #[arg(long)]
+    staged: bool,
+    /// Select stats output format
+    #[arg(long, value_enum)]
+    format: Option<StatsFormatValue>,
+    /// Number of largest sections and oldest documents to list
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    top: usize,
+    /// Only report aggregate metrics
+    #[arg(long)]
+    summary: bool,
+    /// Omit the CSV header row (for appending to a history file)
+    #[arg(long = "no-header")]
+    no_header: bool,
+    /// Disable `.markdown-doc-ignore` filtering
+    #[arg(long = "no-ignore")]
+    no_ignore: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StatsFormatValue {
+    Plain,
+    Json,
+    Csv,
+}
+
 #[derive(Clone, Copy, ValueEnum)]
 enum CatalogFormatValue {
     Markdown,
     Json,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum CatalogTimestampValue {
+    Now,
+    Git,
+    Omit,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TocPositionValue {
+    AfterIntro,
+    BeforeFirstSection,
+    ContentsHeading,
+}
+
 #[derive(Clone, Copy, ValueEnum)]
 enum LintFormatValue {
     Plain,
@@ -551,12 +1773,34 @@ enum ValidateFormatValue {
     Json,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormatValue {
+    Plain,
+    Json,
+    Dot,
+    Mermaid,
+    Graphml,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DupesFormatValue {
+    Plain,
+    Json,
+}
+
 #[derive(Clone, Copy, ValueEnum)]
 enum RefsFormatValue {
     Plain,
     Json,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum SearchFormatValue {
+    #[value(alias = "text")]
+    Plain,
+    Json,
+}
+
 enum RefsFormat {
     Plain,
     Json,
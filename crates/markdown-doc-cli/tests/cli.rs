@@ -67,6 +67,90 @@ fn catalog_json_streams_to_stdout_without_file() {
     assert!(!temp.path().join("DOC_CATALOG.md").exists());
 }
 
+#[test]
+fn catalog_check_ignores_timestamp_and_reports_stale_catalog() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), "README.md", "# Overview\n\n## Setup\n");
+
+    let run = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+        cmd.current_dir(temp.path())
+            .arg("catalog")
+            .args(args)
+            .assert()
+    };
+
+    run(&[]).success();
+    let catalog_path = temp.path().join("DOC_CATALOG.md");
+    let written = fs::read_to_string(&catalog_path).expect("read catalog");
+    assert!(
+        !written.contains("[DOC_CATALOG.md]"),
+        "catalog lists itself"
+    );
+
+    run(&["--check"])
+        .success()
+        .stdout(predicate::str::contains("DOC_CATALOG.md is up to date"));
+    // Regenerating an unchanged tree leaves the file (and its timestamp) alone.
+    run(&[]).success();
+    assert_eq!(fs::read_to_string(&catalog_path).unwrap(), written);
+
+    setup_file(temp.path(), "README.md", "# Overview\n\n## Install\n");
+    run(&["--check"])
+        .code(1)
+        .stdout(predicate::str::contains("DOC_CATALOG.md is out of date"));
+    run(&["--diff"]).code(1).stdout(predicate::str::contains(
+        "-  - [Setup](#setup)\n+  - [Install](#install)",
+    ));
+    assert_eq!(fs::read_to_string(&catalog_path).unwrap(), written);
+
+    fs::remove_file(&catalog_path).expect("remove catalog");
+    run(&["--check"])
+        .code(1)
+        .stdout(predicate::str::contains("DOC_CATALOG.md is missing"));
+}
+
+#[test]
+fn catalog_timestamp_can_be_omitted_or_forced() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), "README.md", "# Overview\n");
+    setup_file(
+        temp.path(),
+        ".markdown-doc.toml",
+        "[catalog]\ntimestamp = \"omit\"\n",
+    );
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["catalog", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("last_updated").not());
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .arg("catalog")
+        .assert()
+        .success();
+    let catalog = fs::read_to_string(temp.path().join("DOC_CATALOG.md")).unwrap();
+    assert!(catalog.starts_with("# Documentation Catalog\n\n## Catalog\n"));
+
+    // A catalog with a timestamp differs from one without, even under --check.
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["catalog", "--check", "--timestamp", "now"])
+        .assert()
+        .code(1);
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["catalog", "--regen", "--timestamp", "now"])
+        .assert()
+        .success();
+    let catalog = fs::read_to_string(temp.path().join("DOC_CATALOG.md")).unwrap();
+    assert!(catalog.contains("Last updated: "));
+}
+
 #[test]
 fn lint_broken_links_reports_errors() {
     let temp = TempDir::new().expect("tempdir");
@@ -84,6 +168,48 @@ fn lint_broken_links_reports_errors() {
         .stdout(predicate::str::contains("Broken link to 'missing.md'"));
 }
 
+#[test]
+fn lint_sarif_describes_custom_rules_without_placeholders() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        temp.path(),
+        ".markdown-doc.toml",
+        r#"
+        [[lint.custom]]
+        id = "no-todo"
+        pattern = "\\bTODO\\b"
+        message = "Remove '{match}' before publishing"
+        "#,
+    );
+    setup_file(temp.path(), "README.md", "# Title\n\nTODO: write this.\n");
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    let output = cmd
+        .current_dir(temp.path())
+        .args(["lint", "--format", "sarif"])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let sarif: Value = serde_json::from_slice(&output).expect("valid sarif");
+    let rules = sarif["runs"][0]["tool"]["driver"]["rules"]
+        .as_array()
+        .expect("rules");
+    let rule = rules
+        .iter()
+        .find(|rule| rule["id"] == "no-todo")
+        .expect("custom rule listed");
+    assert_eq!(
+        rule["fullDescription"]["text"],
+        "Remove '\\bTODO\\b' before publishing"
+    );
+    assert_eq!(
+        sarif["runs"][0]["results"][0]["message"]["text"],
+        "Remove 'TODO' before publishing"
+    );
+}
+
 #[test]
 fn validate_reports_missing_section() {
     let temp = TempDir::new().expect("tempdir");
@@ -188,6 +314,38 @@ fn toc_diff_outputs_unified_diff() {
         .stdout(predicate::str::contains("+- [Details](#details)"));
 }
 
+#[test]
+fn toc_insert_adds_block_before_first_section() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), "doc.md", "# Title\n\nIntro.\n\n## Details\n");
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args([
+            "toc",
+            "--path",
+            "doc.md",
+            "--insert",
+            "--at",
+            "before-first-section",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("inserted TOC into doc.md"));
+
+    let updated = fs::read_to_string(temp.path().join("doc.md")).expect("read doc");
+    assert_eq!(
+        updated,
+        "# Title\n\nIntro.\n\n<!-- toc -->\n- [Details](#details)\n<!-- tocstop -->\n\n## Details\n"
+    );
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["toc", "--at", "after-intro"])
+        .assert()
+        .failure();
+}
+
 #[test]
 fn mv_dry_run_outputs_diff() {
     let temp = TempDir::new().expect("tempdir");
@@ -278,6 +436,253 @@ fn mv_updates_files_and_links() {
     );
 }
 
+#[test]
+fn mv_applies_map_file_in_one_run() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), ".markdown-doc.toml", "");
+    setup_file(
+        temp.path(),
+        "README.md",
+        "# Index\n\n[Intro](intro.md) and ![logo](logo.png).\n",
+    );
+    setup_file(temp.path(), "intro.md", "# Intro\n");
+    setup_file(temp.path(), "logo.png", "png");
+    setup_file(
+        temp.path(),
+        "moves.json",
+        r#"{"intro.md": "docs/intro.md", "logo.png": "docs/img/logo.png"}"#,
+    );
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["mv", "--map", "moves.json", "--no-backup"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "📦 moved logo.png -> docs/img/logo.png",
+        ));
+
+    let readme = fs::read_to_string(temp.path().join("README.md")).expect("read readme");
+    assert_eq!(
+        readme,
+        "# Index\n\n[Intro](docs/intro.md) and ![logo](docs/img/logo.png).\n"
+    );
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["mv", "--map", "moves.json"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("does not exist"));
+}
+
+#[test]
+fn mv_git_refuses_dirty_referrers_unless_allowed() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), "README.md", "# Index\n\n[Intro](intro.md)\n");
+    setup_file(temp.path(), "intro.md", "# Intro\n");
+    for args in [
+        &["init", "-q"][..],
+        &["add", "."][..],
+        &[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-q",
+            "-m",
+            "init",
+        ][..],
+    ] {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(temp.path())
+            .args(args)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+    setup_file(
+        temp.path(),
+        "README.md",
+        "# Index\n\nRead [Intro](intro.md)\n",
+    );
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["mv", "intro.md", "docs/intro.md", "--git"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "uncommitted changes in 'README.md'",
+        ));
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["mv", "intro.md", "docs/intro.md", "--git", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("docs/intro.md"))
+        .stderr(predicate::str::contains(
+            "uncommitted changes in 'README.md'",
+        ));
+    assert!(temp.path().join("intro.md").exists());
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["mv", "intro.md", "docs/intro.md", "--git", "--allow-dirty"])
+        .assert()
+        .success();
+    assert!(temp.path().join("docs/intro.md").exists());
+    assert!(!temp.path().join("README.md.bak").exists());
+}
+
+#[test]
+fn split_and_merge_round_trip_section() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), ".markdown-doc.toml", "");
+    let readme = "# Project\n\n## Setup\n\nInstall it.\n\n## Usage\n\nSee [setup](#setup).\n";
+    setup_file(temp.path(), "README.md", readme);
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    let output = cmd
+        .current_dir(temp.path())
+        .args([
+            "split",
+            "README.md",
+            "--section",
+            "setup",
+            "--to",
+            "setup.md",
+            "--no-backup",
+            "--json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&output).expect("valid json");
+    assert_eq!(json["files"][1]["original"], "setup.md");
+    assert_eq!(json["files"][1]["status"], "created");
+    assert_eq!(
+        fs::read_to_string(temp.path().join("setup.md")).expect("read setup"),
+        "# Setup\n\nInstall it.\n"
+    );
+    assert!(fs::read_to_string(temp.path().join("README.md"))
+        .expect("read readme")
+        .contains("See [setup](setup.md#setup)."));
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["merge", "setup.md", "--into", "README.md", "--no-backup"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("🗑️  removed setup.md"));
+    assert_eq!(
+        fs::read_to_string(temp.path().join("README.md")).expect("read readme"),
+        "# Project\n\n## Usage\n\nSee [setup](#setup).\n\n## Setup\n\nInstall it.\n"
+    );
+}
+
+#[test]
+fn rename_heading_dry_run_json_then_apply() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), ".markdown-doc.toml", "");
+    setup_file(
+        temp.path(),
+        "README.md",
+        "# Project\n\n## Setup\n\nSee [usage](#usage).\n\n## Usage\n\nRun it.\n",
+    );
+    setup_file(
+        temp.path(),
+        "guide.md",
+        "# Guide\n\n[Use it](README.md#usage).\n",
+    );
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    let output = cmd
+        .current_dir(temp.path())
+        .args([
+            "rename-heading",
+            "README.md",
+            "Usage",
+            "Running",
+            "--dry-run",
+            "--json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&output).expect("valid json");
+    assert_eq!(json["dry_run"], true);
+    assert_eq!(json["files"][0]["original"], "README.md");
+    assert_eq!(json["files"][0]["status"], "updated");
+    assert_eq!(json["files"][1]["original"], "guide.md");
+    assert!(json["files"][1]["diff"]
+        .as_str()
+        .expect("diff")
+        .contains("+[Use it](README.md#running)."));
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args([
+            "rename-heading",
+            "README.md",
+            "Usage",
+            "Running",
+            "--no-backup",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✏️  updated guide.md"));
+    assert_eq!(
+        fs::read_to_string(temp.path().join("README.md")).expect("read readme"),
+        "# Project\n\n## Setup\n\nSee [usage](#running).\n\n## Running\n\nRun it.\n"
+    );
+}
+
+#[test]
+fn refs_from_groups_outbound_links() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), ".markdown-doc.toml", "");
+    setup_file(
+        temp.path(),
+        "README.md",
+        "# Index\n\n[Guide](guide.md#setup) [old](old.md) [web](https://example.com)\n",
+    );
+    setup_file(temp.path(), "guide.md", "# Guide\n\n## Setup\n");
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["refs", "--from", "README.md"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("  resolved (1):"))
+        .stdout(predicate::str::contains(
+            "README.md:3 -> old.md [missing-file]",
+        ))
+        .stdout(predicate::str::contains("  external (1):"));
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    let output = cmd
+        .current_dir(temp.path())
+        .args(["refs", "--from", ".", "--aggregate", "--format", "json"])
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&output).expect("valid json");
+    assert_eq!(json["unresolved_count"], 1);
+    assert_eq!(json["groups"][0]["path"], ".");
+    assert_eq!(json["groups"][0]["resolved"][0]["target_anchor"], "setup");
+    assert_eq!(json["groups"][0]["unresolved"][0]["status"], "missing-file");
+}
+
 #[test]
 fn refs_plain_reports_matches() {
     let temp = TempDir::new().expect("tempdir");
@@ -356,3 +761,245 @@ fn refs_json_outputs_expected_structure() {
         "intro.md#overview"
     );
 }
+
+#[test]
+fn stats_reports_json_and_csv_metrics() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), ".markdown-doc.toml", "");
+    setup_file(
+        temp.path(),
+        "README.md",
+        "# Project\n\nSee the [guide](guide.md).\n",
+    );
+    setup_file(
+        temp.path(),
+        "guide.md",
+        "# Guide\n\n## Setup\n\nRead [upstream](https://example.com) and [gone](gone.md).\n",
+    );
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    let output = cmd
+        .current_dir(temp.path())
+        .args(["stats", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: Value = serde_json::from_slice(&output).expect("valid json");
+    assert_eq!(json["summary"]["files"], 2);
+    assert_eq!(json["summary"]["headings"]["h1"], 2);
+    assert_eq!(json["summary"]["headings"]["h2"], 1);
+    assert_eq!(
+        json["summary"]["links"],
+        serde_json::json!({"internal": 2, "external": 1, "broken": 1})
+    );
+    assert_eq!(json["files"][1]["path"], "guide.md");
+    assert_eq!(json["files"][1]["inbound_links"], 1);
+    assert_eq!(json["largest_sections"][0]["anchor"], "setup");
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    let output = cmd
+        .current_dir(temp.path())
+        .args(["stats", "--format", "csv"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let csv = String::from_utf8(output).expect("utf8");
+    let lines: Vec<_> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("path,words,h1,h2,h3,h4,h5,h6,internal_links"));
+    assert!(lines[2].starts_with("guide.md,6,1,1,0,0,0,0,1,1,1,1,false,"));
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["stats", "--format", "csv", "--summary", "--no-header"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"^\S+Z,2,").unwrap())
+        .stdout(predicate::function(|out: &str| out.lines().count() == 1));
+}
+
+#[test]
+fn search_json_reports_sections_and_index_stats() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), ".markdown-doc.toml", "");
+    setup_file(
+        temp.path(),
+        "guide.md",
+        "# Guide\n\n## Rollback\n\nRestore the snapshot.\n",
+    );
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    let output = cmd
+        .current_dir(temp.path())
+        .args(["search", "-q", "snapshot", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: Value = serde_json::from_slice(&output).expect("valid json");
+    let first = &json["results"][0];
+    assert_eq!(first["file"], "guide.md");
+    assert_eq!(first["anchor"], "rollback");
+    assert_eq!(first["line"], 3);
+    assert_eq!(
+        first["snippet"][0],
+        serde_json::json!({"line": 5, "text": "Restore the snapshot.", "highlights": [[12, 20]]})
+    );
+    assert_eq!(json["index"]["files"], 1);
+    assert_eq!(json["index"]["refreshed"], 1);
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["search", "rollback"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "guide.md:3 Rollback #rollback (score",
+        ))
+        .stdout(predicate::str::contains("0 refreshed"));
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["search", "absent"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("No results for 'absent'."));
+}
+
+#[test]
+fn graph_orphans_json_lists_unlinked_documents() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), "README.md", "# Home\n\n[Guide](guide.md)\n");
+    setup_file(temp.path(), "guide.md", "# Guide\n");
+    setup_file(temp.path(), "old.md", "# Old\n");
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    let output = cmd
+        .current_dir(temp.path())
+        .args(["graph", "--orphans", "--format", "json"])
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+
+    let payload: Value = serde_json::from_slice(&output).expect("json output");
+    assert_eq!(payload["roots"], serde_json::json!(["README.md"]));
+    assert_eq!(payload["orphans"], serde_json::json!(["old.md"]));
+    assert_eq!(payload["unreachable"], serde_json::json!([]));
+}
+
+#[test]
+fn graph_exports_dot_and_json_with_filters() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(temp.path(), ".markdown-doc.toml", "");
+    setup_file(
+        temp.path(),
+        "README.md",
+        "# Home\n\n[Guide](docs/guide.md#setup)\n",
+    );
+    setup_file(
+        temp.path(),
+        "docs/guide.md",
+        "# Guide\n\n## Setup\n\n[Deep](deep.md)\n",
+    );
+    setup_file(temp.path(), "docs/deep.md", "# Deep\n");
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["graph", "--format", "dot", "--anchors"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("digraph docs {"))
+        .stdout(predicate::str::contains(
+            "\"README.md\" -> \"docs/guide.md#setup\";",
+        ));
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    let output = cmd
+        .current_dir(temp.path())
+        .args([
+            "graph",
+            "--format",
+            "json",
+            "--root",
+            "README.md",
+            "--depth",
+            "1",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&output).expect("valid json");
+    let nodes: Vec<_> = json["nodes"]
+        .as_array()
+        .expect("nodes")
+        .iter()
+        .map(|node| node["id"].as_str().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(nodes, vec!["README.md", "docs/guide.md"]);
+    assert_eq!(
+        json["edges"],
+        serde_json::json!([{ "source": "README.md", "target": "docs/guide.md" }])
+    );
+
+    // Plain output lists each source/target pair once, whatever mix of links joins them.
+    setup_file(
+        temp.path(),
+        "README.md",
+        "# Home\n\n[Guide](docs/guide.md#setup), [again][g], ![logo](docs/guide.md).\n\n[g]: docs/guide.md\n",
+    );
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["graph"])
+        .assert()
+        .success()
+        .stdout("README.md -> docs/guide.md\ndocs/guide.md -> docs/deep.md\n");
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    cmd.current_dir(temp.path())
+        .args(["graph", "--orphans", "--format", "mermaid"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("plain and json"));
+}
+
+#[test]
+fn dupes_json_lists_clusters_with_byte_ranges() {
+    let temp = TempDir::new().expect("tempdir");
+    let body = "Clone the repository, install the toolchain, and run the setup script to \
+prepare fixtures before running the full test suite locally.";
+    setup_file(temp.path(), "a.md", &format!("# A\n\n## Setup\n\n{body}\n"));
+    setup_file(temp.path(), "b.md", &format!("# B\n\n## Setup\n\n{body}\n"));
+
+    let mut cmd = Command::cargo_bin("markdown-doc").expect("binary");
+    let output = cmd
+        .current_dir(temp.path())
+        .args(["dupes", "--format", "json"])
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+
+    let payload: Value = serde_json::from_slice(&output).expect("json output");
+    let clusters = payload["clusters"].as_array().expect("clusters");
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0]["heading"], "setup");
+    assert_eq!(clusters[0]["similarity"], 1.0);
+    let sections = clusters[0]["sections"].as_array().expect("sections");
+    assert_eq!(sections[0]["path"], "a.md");
+    assert_eq!(sections[0]["anchor"], "setup");
+    assert_eq!(sections[0]["line"], 3);
+    assert_eq!(sections[0]["byte_range"]["start"], 5);
+    assert_eq!(sections[1]["path"], "b.md");
+}
@@ -12,8 +12,10 @@ use std::fmt;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use globset::{Glob, GlobMatcher};
+use regex::Regex;
 use serde::Deserialize;
 use thiserror::Error;
 
@@ -25,6 +27,7 @@ pub struct Config {
     pub project: ProjectSettings,
     pub catalog: CatalogSettings,
     pub lint: LintSettings,
+    pub search: SearchSettings,
     pub schemas: SchemaSettings,
     pub sources: ConfigSources,
 }
@@ -35,6 +38,59 @@ pub struct ProjectSettings {
     pub name: Option<String>,
     pub root: PathBuf,
     pub exclude: PatternList,
+    /// Heading slug algorithm shared by the parser, lint, TOC, catalog, and refs.
+    pub anchor_dialect: AnchorDialect,
+}
+
+/// Renderer whose heading anchor (slug) rules documents are written against.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AnchorDialect {
+    /// github-slugger: punctuation dropped, each space becomes `-`, duplicates get `-1`, `-2`.
+    #[default]
+    Github,
+    /// GitLab: like GitHub, but runs of hyphens collapse to one.
+    Gitlab,
+    /// Python-Markdown `toc` (mkdocs): ASCII-folded, hyphen runs collapsed, duplicates get `_1`, `_2`.
+    Mkdocs,
+    /// Pandoc `auto_identifiers`: keeps `_ - .`, drops everything before the first letter.
+    Pandoc,
+    /// Azure DevOps wiki: lowercased with spaces replaced by `-`, punctuation kept.
+    AzureDevops,
+}
+
+impl AnchorDialect {
+    pub const ALL: [AnchorDialect; 5] = [
+        AnchorDialect::Github,
+        AnchorDialect::Gitlab,
+        AnchorDialect::Mkdocs,
+        AnchorDialect::Pandoc,
+        AnchorDialect::AzureDevops,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnchorDialect::Github => "github",
+            AnchorDialect::Gitlab => "gitlab",
+            AnchorDialect::Mkdocs => "mkdocs",
+            AnchorDialect::Pandoc => "pandoc",
+            AnchorDialect::AzureDevops => "azure-devops",
+        }
+    }
+}
+
+impl std::str::FromStr for AnchorDialect {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "github" => Ok(AnchorDialect::Github),
+            "gitlab" => Ok(AnchorDialect::Gitlab),
+            "mkdocs" | "python-markdown" => Ok(AnchorDialect::Mkdocs),
+            "pandoc" => Ok(AnchorDialect::Pandoc),
+            "azure-devops" => Ok(AnchorDialect::AzureDevops),
+            _ => Err(()),
+        }
+    }
 }
 
 /// Settings that govern the catalog command.
@@ -43,6 +99,118 @@ pub struct CatalogSettings {
     pub output: PathBuf,
     pub include: PatternList,
     pub exclude: PatternList,
+    /// Metadata recorded for each document and section, in configuration order.
+    pub fields: Vec<CatalogField>,
+    /// Source of the catalog's `Last updated` timestamp.
+    pub timestamp: CatalogTimestamp,
+    /// Template used for markdown output; the built-in layout when `None`.
+    pub template: Option<PathBuf>,
+    /// File name of per-directory catalogs (e.g. `INDEX.md`); when set, one catalog is
+    /// written into every directory containing documents instead of `output`.
+    pub directory_index: Option<String>,
+}
+
+/// Settings that govern the search command.
+#[derive(Clone, Debug)]
+pub struct SearchSettings {
+    /// Location of the persistent search index.
+    pub index: PathBuf,
+}
+
+/// Source of the timestamp written at the top of the catalog.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CatalogTimestamp {
+    /// Time of generation; changes on every run.
+    #[default]
+    Now,
+    /// Latest commit time among the catalogued documents (omitted outside git).
+    Git,
+    /// No timestamp, so regenerating an unchanged tree is byte-identical.
+    Omit,
+}
+
+impl CatalogTimestamp {
+    pub const ALL: [CatalogTimestamp; 3] = [
+        CatalogTimestamp::Now,
+        CatalogTimestamp::Git,
+        CatalogTimestamp::Omit,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CatalogTimestamp::Now => "now",
+            CatalogTimestamp::Git => "git",
+            CatalogTimestamp::Omit => "omit",
+        }
+    }
+}
+
+impl std::str::FromStr for CatalogTimestamp {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "now" => Ok(CatalogTimestamp::Now),
+            "git" => Ok(CatalogTimestamp::Git),
+            "omit" => Ok(CatalogTimestamp::Omit),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Optional metadata the catalog can record alongside each heading.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CatalogField {
+    /// First sentence of the section's introductory paragraph.
+    Summary,
+    /// Word count of the section, including nested sections.
+    Words,
+    /// Rough LLM token estimate (about four characters per token).
+    Tokens,
+    /// First and last line of the section.
+    Lines,
+    /// Last commit time of the document, or its modification time outside git.
+    Modified,
+    /// Top-level scalar fields of the document's YAML front matter.
+    FrontMatter,
+}
+
+impl CatalogField {
+    pub const ALL: [CatalogField; 6] = [
+        CatalogField::Summary,
+        CatalogField::Words,
+        CatalogField::Tokens,
+        CatalogField::Lines,
+        CatalogField::Modified,
+        CatalogField::FrontMatter,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CatalogField::Summary => "summary",
+            CatalogField::Words => "words",
+            CatalogField::Tokens => "tokens",
+            CatalogField::Lines => "lines",
+            CatalogField::Modified => "modified",
+            CatalogField::FrontMatter => "front-matter",
+        }
+    }
+}
+
+impl std::str::FromStr for CatalogField {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "summary" => Ok(CatalogField::Summary),
+            "words" => Ok(CatalogField::Words),
+            "tokens" => Ok(CatalogField::Tokens),
+            "lines" => Ok(CatalogField::Lines),
+            "modified" => Ok(CatalogField::Modified),
+            "front-matter" => Ok(CatalogField::FrontMatter),
+            _ => Err(()),
+        }
+    }
 }
 
 /// Settings covering lint behaviour and rule configuration.
@@ -55,18 +223,35 @@ pub struct LintSettings {
     pub max_heading_depth: u8,
     pub ignore: Vec<LintIgnore>,
     pub toc: TocSettings,
+    pub custom: Vec<CustomLintRule>,
+    pub orphans: OrphanSettings,
+    pub style: StyleSettings,
+    pub spelling: SpellingSettings,
+    pub duplicates: DuplicateSettings,
 }
 
 impl LintSettings {
     /// Returns the effective severity for `rule`, defaulting to `Error`.
+    ///
+    /// Custom rules fall back to the severity declared in their definition
+    /// before the `*` wildcard applies.
     pub fn severity_for(&self, rule: LintRule) -> SeverityLevel {
         self.severity
             .get(&rule)
             .copied()
+            .or_else(|| self.custom_rule(&rule).map(|custom| custom.severity))
             .or(self.severity_wildcard)
             .unwrap_or(SeverityLevel::Error)
     }
 
+    /// Look up the `[[lint.custom]]` definition backing `rule`, if any.
+    pub fn custom_rule(&self, rule: &LintRule) -> Option<&CustomLintRule> {
+        match rule {
+            LintRule::Custom(id) => self.custom.iter().find(|custom| *custom.id == **id),
+            _ => None,
+        }
+    }
+
     /// Returns the effective severity for `rule` when evaluating `path`.
     pub fn severity_for_path(&self, path: &Path, rule: LintRule) -> SeverityLevel {
         for override_entry in self.severity_overrides.iter().rev() {
@@ -84,7 +269,7 @@ impl LintSettings {
 
     /// Determine whether the rule is enabled in any scope.
     pub fn is_rule_enabled(&self, rule: LintRule) -> bool {
-        if self.severity_for(rule) != SeverityLevel::Ignore {
+        if self.severity_for(rule.clone()) != SeverityLevel::Ignore {
             return true;
         }
 
@@ -104,6 +289,75 @@ impl LintSettings {
     }
 }
 
+/// Project-specific lint rule declared via `[[lint.custom]]`.
+#[derive(Clone, Debug)]
+pub struct CustomLintRule {
+    pub id: Arc<str>,
+    pub target: CustomRuleTarget,
+    pub pattern: Option<Regex>,
+    pub require: bool,
+    pub max_lines: Option<usize>,
+    pub paths: PatternList,
+    pub message: String,
+    pub severity: SeverityLevel,
+    pub source: ConfigSource,
+    matchers: Vec<GlobMatcher>,
+}
+
+impl CustomLintRule {
+    /// Lint rule identifier used in findings, severity maps, and ignores.
+    pub fn rule(&self) -> LintRule {
+        LintRule::Custom(self.id.clone())
+    }
+
+    /// Determine whether the rule is scoped to `path` (all paths when no globs are set).
+    pub fn applies_to(&self, path: &Path) -> bool {
+        self.matchers.is_empty() || self.matchers.iter().any(|matcher| matcher.is_match(path))
+    }
+
+    /// Render the configured message, substituting `{match}` with the matched text.
+    pub fn render_message(&self, matched: &str) -> String {
+        self.message.replace("{match}", matched)
+    }
+}
+
+/// Document element a custom lint rule pattern is evaluated against.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CustomRuleTarget {
+    Line,
+    Heading,
+    Link,
+    CodeBlock,
+    Section,
+}
+
+impl CustomRuleTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CustomRuleTarget::Line => "line",
+            CustomRuleTarget::Heading => "heading",
+            CustomRuleTarget::Link => "link",
+            CustomRuleTarget::CodeBlock => "code-block",
+            CustomRuleTarget::Section => "section",
+        }
+    }
+}
+
+impl std::str::FromStr for CustomRuleTarget {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "line" => Ok(CustomRuleTarget::Line),
+            "heading" => Ok(CustomRuleTarget::Heading),
+            "link" => Ok(CustomRuleTarget::Link),
+            "code-block" => Ok(CustomRuleTarget::CodeBlock),
+            "section" => Ok(CustomRuleTarget::Section),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Path-scoped severity override rules.
 #[derive(Clone, Debug)]
 pub struct LintSeverityOverride {
@@ -114,11 +368,232 @@ pub struct LintSeverityOverride {
     pub source: ConfigSource,
 }
 
-/// Configuration governing TOC marker detection.
+/// Configuration governing TOC marker detection and generation.
 #[derive(Clone, Debug)]
 pub struct TocSettings {
     pub start_marker: String,
     pub end_marker: String,
+    /// Defaults for every block; start markers may override them inline.
+    pub format: TocFormat,
+    /// Leave files without TOC markers alone instead of reporting them as errors.
+    pub skip_missing: bool,
+    /// Where `toc --insert` places a new block.
+    pub insert_at: TocInsertPosition,
+}
+
+/// Placement of a TOC block added by `toc --insert`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TocInsertPosition {
+    /// After the H1 and the paragraph that follows it.
+    #[default]
+    AfterIntro,
+    /// Immediately before the first heading below the H1.
+    BeforeFirstSection,
+    /// Under a `Contents` heading, added before the first section when missing.
+    ContentsHeading,
+}
+
+impl TocInsertPosition {
+    pub const ALL: [TocInsertPosition; 3] = [
+        TocInsertPosition::AfterIntro,
+        TocInsertPosition::BeforeFirstSection,
+        TocInsertPosition::ContentsHeading,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TocInsertPosition::AfterIntro => "after-intro",
+            TocInsertPosition::BeforeFirstSection => "before-first-section",
+            TocInsertPosition::ContentsHeading => "contents-heading",
+        }
+    }
+}
+
+impl std::str::FromStr for TocInsertPosition {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "after-intro" => Ok(TocInsertPosition::AfterIntro),
+            "before-first-section" => Ok(TocInsertPosition::BeforeFirstSection),
+            "contents-heading" => Ok(TocInsertPosition::ContentsHeading),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Which headings a TOC block lists and how it renders them (`[lint.toc]`).
+#[derive(Clone, Debug)]
+pub struct TocFormat {
+    /// Shallowest heading level listed; entries at this level are not indented.
+    pub min_depth: usize,
+    /// Deepest heading level listed.
+    pub max_depth: usize,
+    /// Render an ordered list (`1.`) instead of bullets.
+    pub numbered: bool,
+    /// Bullet marker (`-`, `*`, or `+`) for unordered lists.
+    pub bullet: char,
+    /// Headings whose text matches any of these patterns are omitted.
+    pub exclude: Vec<Regex>,
+    /// List only headings that appear after the block.
+    pub after_block: bool,
+}
+
+impl Default for TocFormat {
+    fn default() -> Self {
+        TocFormat {
+            min_depth: 2,
+            max_depth: 6,
+            numbered: false,
+            bullet: '-',
+            exclude: Vec::new(),
+            after_block: false,
+        }
+    }
+}
+
+/// Entry points and allowlist used for orphan/unreachable document detection.
+#[derive(Clone, Debug)]
+pub struct OrphanSettings {
+    /// Documents treated as reachable entry points. Globs match project-relative
+    /// Markdown paths; `.yml`/`.yaml` entries are read as mkdocs configs and their
+    /// `nav` pages become roots.
+    pub roots: PatternList,
+    /// Documents never reported as orphaned or unreachable.
+    pub allow: PatternList,
+}
+
+/// Options for the formatting/style lint rules (`[lint.style]`).
+#[derive(Clone, Debug)]
+pub struct StyleSettings {
+    /// Maximum line length enforced by `line-length`.
+    pub max_line_length: usize,
+    /// Permit exactly two trailing spaces (a Markdown hard break) in `trailing-whitespace`.
+    pub allow_hard_breaks: bool,
+    /// Characters disallowed at the end of headings by `heading-punctuation`.
+    pub heading_punctuation: String,
+    /// Bullet marker required by `list-marker-style`.
+    pub list_marker: ListMarkerStyle,
+    /// Emphasis delimiter required by `emphasis-style`.
+    pub emphasis: EmphasisStyle,
+}
+
+impl Default for StyleSettings {
+    fn default() -> Self {
+        StyleSettings {
+            max_line_length: 100,
+            allow_hard_breaks: true,
+            heading_punctuation: ".,;:!".into(),
+            list_marker: ListMarkerStyle::Consistent,
+            emphasis: EmphasisStyle::Consistent,
+        }
+    }
+}
+
+/// Project dictionaries for the `spelling` rule (`[lint.spelling]`).
+#[derive(Clone, Debug)]
+pub struct SpellingSettings {
+    /// Extra words accepted in addition to the bundled dictionary (case-insensitive).
+    pub words: Vec<String>,
+    /// Word list files (one word per line, `#` comments) relative to the project root.
+    /// Missing files are ignored; `--add-words` appends to the first entry.
+    pub word_files: Vec<PathBuf>,
+}
+
+impl Default for SpellingSettings {
+    fn default() -> Self {
+        SpellingSettings {
+            words: Vec::new(),
+            word_files: vec![PathBuf::from(".markdown-doc-words")],
+        }
+    }
+}
+
+/// Options for cross-file near-duplicate section detection (`[lint.duplicates]`).
+#[derive(Clone, Debug)]
+pub struct DuplicateSettings {
+    /// Minimum estimated Jaccard similarity (0-1] for two section bodies to match.
+    pub threshold: f64,
+    /// Sections with fewer body words are never compared.
+    pub min_words: usize,
+}
+
+impl Default for DuplicateSettings {
+    fn default() -> Self {
+        DuplicateSettings {
+            threshold: 0.7,
+            min_words: 20,
+        }
+    }
+}
+
+/// Bullet list marker policy.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ListMarkerStyle {
+    /// Every bullet must match the first marker used in the document.
+    Consistent,
+    Dash,
+    Asterisk,
+    Plus,
+}
+
+impl ListMarkerStyle {
+    /// The required marker character, if fixed.
+    pub fn marker(&self) -> Option<char> {
+        match self {
+            ListMarkerStyle::Consistent => None,
+            ListMarkerStyle::Dash => Some('-'),
+            ListMarkerStyle::Asterisk => Some('*'),
+            ListMarkerStyle::Plus => Some('+'),
+        }
+    }
+}
+
+impl std::str::FromStr for ListMarkerStyle {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "consistent" => Ok(ListMarkerStyle::Consistent),
+            "dash" => Ok(ListMarkerStyle::Dash),
+            "asterisk" => Ok(ListMarkerStyle::Asterisk),
+            "plus" => Ok(ListMarkerStyle::Plus),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Emphasis/strong delimiter policy.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EmphasisStyle {
+    /// Every emphasis must match the first delimiter used in the document.
+    Consistent,
+    Asterisk,
+    Underscore,
+}
+
+impl EmphasisStyle {
+    /// The required delimiter character, if fixed.
+    pub fn delimiter(&self) -> Option<char> {
+        match self {
+            EmphasisStyle::Consistent => None,
+            EmphasisStyle::Asterisk => Some('*'),
+            EmphasisStyle::Underscore => Some('_'),
+        }
+    }
+}
+
+impl std::str::FromStr for EmphasisStyle {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "consistent" => Ok(EmphasisStyle::Consistent),
+            "asterisk" => Ok(EmphasisStyle::Asterisk),
+            "underscore" => Ok(EmphasisStyle::Underscore),
+            _ => Err(()),
+        }
+    }
 }
 
 /// Resolved schema configuration providing template definitions and pattern precedence.
@@ -209,7 +684,9 @@ impl PatternList {
 }
 
 /// Supported lint rules. Keep the list in sync with the specification.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+///
+/// `Custom` carries the identifier of a rule declared under `[[lint.custom]]`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum LintRule {
     BrokenLinks,
     BrokenAnchors,
@@ -217,6 +694,19 @@ pub enum LintRule {
     HeadingHierarchy,
     RequiredSections,
     TocSync,
+    UnusedSuppressions,
+    Orphans,
+    LineLength,
+    TrailingWhitespace,
+    HeadingPunctuation,
+    ListMarkerStyle,
+    EmphasisStyle,
+    BlanksAroundHeadings,
+    BlanksAroundFences,
+    FencedCodeLanguage,
+    Spelling,
+    DuplicateSections,
+    Custom(Arc<str>),
 }
 
 impl LintRule {
@@ -227,9 +717,21 @@ impl LintRule {
         LintRule::HeadingHierarchy,
         LintRule::RequiredSections,
         LintRule::TocSync,
+        LintRule::UnusedSuppressions,
+        LintRule::Orphans,
+        LintRule::LineLength,
+        LintRule::TrailingWhitespace,
+        LintRule::HeadingPunctuation,
+        LintRule::ListMarkerStyle,
+        LintRule::EmphasisStyle,
+        LintRule::BlanksAroundHeadings,
+        LintRule::BlanksAroundFences,
+        LintRule::FencedCodeLanguage,
+        LintRule::Spelling,
+        LintRule::DuplicateSections,
     ];
 
-    pub fn as_str(self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             LintRule::BrokenLinks => "broken-links",
             LintRule::BrokenAnchors => "broken-anchors",
@@ -237,8 +739,26 @@ impl LintRule {
             LintRule::HeadingHierarchy => "heading-hierarchy",
             LintRule::RequiredSections => "required-sections",
             LintRule::TocSync => "toc-sync",
+            LintRule::UnusedSuppressions => "unused-suppressions",
+            LintRule::Orphans => "orphans",
+            LintRule::LineLength => "line-length",
+            LintRule::TrailingWhitespace => "trailing-whitespace",
+            LintRule::HeadingPunctuation => "heading-punctuation",
+            LintRule::ListMarkerStyle => "list-marker-style",
+            LintRule::EmphasisStyle => "emphasis-style",
+            LintRule::BlanksAroundHeadings => "blanks-around-headings",
+            LintRule::BlanksAroundFences => "blanks-around-fences",
+            LintRule::FencedCodeLanguage => "fenced-code-language",
+            LintRule::Spelling => "spelling",
+            LintRule::DuplicateSections => "duplicate-sections",
+            LintRule::Custom(id) => id,
         }
     }
+
+    /// Returns true for rules declared under `[[lint.custom]]`.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, LintRule::Custom(_))
+    }
 }
 
 impl fmt::Display for LintRule {
@@ -258,6 +778,18 @@ impl std::str::FromStr for LintRule {
             "heading-hierarchy" => Ok(LintRule::HeadingHierarchy),
             "required-sections" => Ok(LintRule::RequiredSections),
             "toc-sync" => Ok(LintRule::TocSync),
+            "unused-suppressions" => Ok(LintRule::UnusedSuppressions),
+            "orphans" => Ok(LintRule::Orphans),
+            "line-length" => Ok(LintRule::LineLength),
+            "trailing-whitespace" => Ok(LintRule::TrailingWhitespace),
+            "heading-punctuation" => Ok(LintRule::HeadingPunctuation),
+            "list-marker-style" => Ok(LintRule::ListMarkerStyle),
+            "emphasis-style" => Ok(LintRule::EmphasisStyle),
+            "blanks-around-headings" => Ok(LintRule::BlanksAroundHeadings),
+            "blanks-around-fences" => Ok(LintRule::BlanksAroundFences),
+            "fenced-code-language" => Ok(LintRule::FencedCodeLanguage),
+            "spelling" => Ok(LintRule::Spelling),
+            "duplicate-sections" => Ok(LintRule::DuplicateSections),
             _ => Err(()),
         }
     }
@@ -466,6 +998,7 @@ impl Config {
             project: config.project,
             catalog: config.catalog,
             lint: config.lint,
+            search: config.search,
             schemas: config.schemas,
             sources: ConfigSources {
                 working_directory: working_dir,
@@ -540,6 +1073,10 @@ fn defaults_layer(source: ConfigSource) -> PartialConfig {
             vec!["**/node_modules/**".into(), "**/vendor/**".into()],
             source.clone(),
         )),
+        fields: None,
+        timestamp: None,
+        template: None,
+        directory_index: None,
     };
 
     let lint = LintPartial {
@@ -547,6 +1084,11 @@ fn defaults_layer(source: ConfigSource) -> PartialConfig {
         max_heading_depth: Some(Located::new(4, source.clone())),
         toc_start_marker: Some(Located::new("<!-- toc -->".into(), source.clone())),
         toc_end_marker: Some(Located::new("<!-- tocstop -->".into(), source.clone())),
+        orphan_roots: Some(Located::new(
+            vec!["README.md".into(), "DOC_CATALOG.md".into()],
+            source.clone(),
+        )),
+        orphan_allow: Some(Located::new(Vec::new(), source.clone())),
         ..LintPartial::default()
     };
 
@@ -564,10 +1106,18 @@ fn defaults_layer(source: ConfigSource) -> PartialConfig {
     let mut schemas = SchemasPartial::default();
     schemas.entries.insert("default".into(), default_schema);
 
+    let search = SearchPartial {
+        index: Some(Located::new(
+            PathBuf::from(".markdown-doc/index.json"),
+            source,
+        )),
+    };
+
     PartialConfig {
         project: Some(project),
         catalog: Some(catalog),
         lint: Some(lint),
+        search: Some(search),
         schemas: Some(schemas),
     }
 }
@@ -593,6 +1143,7 @@ struct PartialConfig {
     project: Option<ProjectPartial>,
     catalog: Option<CatalogPartial>,
     lint: Option<LintPartial>,
+    search: Option<SearchPartial>,
     schemas: Option<SchemasPartial>,
 }
 
@@ -602,6 +1153,7 @@ impl PartialConfig {
             project: None,
             catalog: None,
             lint: None,
+            search: None,
             schemas: None,
         }
     }
@@ -628,6 +1180,13 @@ impl PartialConfig {
             }
         }
 
+        if let Some(other_search) = other.search.take() {
+            match &mut self.search {
+                Some(search) => search.merge(other_search),
+                None => self.search = Some(other_search),
+            }
+        }
+
         if let Some(other_schemas) = other.schemas.take() {
             match &mut self.schemas {
                 Some(schemas) => schemas.merge(other_schemas),
@@ -653,6 +1212,28 @@ impl PartialConfig {
             "project.exclude",
             &mut errors,
         );
+        let mut anchor_dialect = AnchorDialect::default();
+        if let Some(dialect) = project_partial.anchor_dialect {
+            match dialect.value.parse() {
+                Ok(value) => anchor_dialect = value,
+                Err(_) => errors.push(
+                    ConfigValidationError::new(
+                        Some(dialect.source),
+                        format!(
+                            "unknown anchor_dialect '{}' (expected github, gitlab, mkdocs, pandoc, or azure-devops)",
+                            dialect.value
+                        ),
+                    )
+                    .with_context("project"),
+                ),
+            }
+        }
+
+        let search_partial = self.search.unwrap_or_default();
+        let search_index = search_partial
+            .index
+            .as_ref()
+            .map_or_else(|| PathBuf::from(".markdown-doc/index.json"), resolve_path);
 
         let catalog_partial = self.catalog.unwrap_or_default();
         let catalog_output_loc = catalog_partial.output.unwrap_or_else(|| {
@@ -673,6 +1254,50 @@ impl PartialConfig {
             "catalog.exclude_patterns",
             &mut errors,
         );
+        let catalog_fields = parse_catalog_fields(catalog_partial.fields, &mut errors);
+        let catalog_template = catalog_partial.template.as_ref().map(resolve_path);
+        let catalog_directory_index = catalog_partial.directory_index.and_then(|name| {
+            let valid = !name.value.trim().is_empty()
+                && !name.value.contains(['/', '\\'])
+                && name.value != "."
+                && name.value != "..";
+            if valid {
+                Some(name.value)
+            } else {
+                errors.push(
+                    ConfigValidationError::new(
+                        Some(name.source),
+                        format!(
+                            "directory_index '{}' must be a file name without directories",
+                            name.value
+                        ),
+                    )
+                    .with_context("catalog.directory_index"),
+                );
+                None
+            }
+        });
+        let mut catalog_timestamp = CatalogTimestamp::default();
+        if let Some(timestamp) = catalog_partial.timestamp {
+            match timestamp.value.parse() {
+                Ok(value) => catalog_timestamp = value,
+                Err(()) => errors.push(
+                    ConfigValidationError::new(
+                        Some(timestamp.source),
+                        format!(
+                            "unknown catalog timestamp '{}' (expected {})",
+                            timestamp.value,
+                            CatalogTimestamp::ALL
+                                .iter()
+                                .map(CatalogTimestamp::as_str)
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    )
+                    .with_context("catalog.timestamp"),
+                ),
+            }
+        }
 
         let lint_partial = self.lint.unwrap_or_default();
         let rules_loc = lint_partial.rules.unwrap_or_else(|| {
@@ -681,7 +1306,9 @@ impl PartialConfig {
                 ConfigSource::default(PathBuf::from(".")),
             )
         });
-        let rules = parse_rules(rules_loc, &mut errors);
+        let custom = parse_custom_rules(lint_partial.custom, &mut errors);
+        let custom_ids: HashSet<Arc<str>> = custom.iter().map(|rule| rule.id.clone()).collect();
+        let rules = parse_rules(rules_loc, &custom_ids, &mut errors);
 
         let max_heading_depth = lint_partial
             .max_heading_depth
@@ -725,10 +1352,28 @@ impl PartialConfig {
             ));
         }
 
-        let (severity, severity_wildcard) = parse_severity_map(lint_partial.severity, &mut errors);
+        let orphan_roots = compile_patterns(
+            lint_partial.orphan_roots.unwrap_or_default(),
+            "lint.orphan_roots",
+            &mut errors,
+        );
+        let orphan_allow = compile_patterns(
+            lint_partial.orphan_allow.unwrap_or_default(),
+            "lint.orphan_allow",
+            &mut errors,
+        );
+
+        let style = finalize_style(lint_partial.style, &mut errors);
+        let spelling = finalize_spelling(lint_partial.spelling, &mut errors);
+        let duplicates = finalize_duplicates(lint_partial.duplicates, &mut errors);
+        let (toc_format, toc_skip_missing, toc_insert_at) =
+            finalize_toc(lint_partial.toc, &mut errors);
+
+        let (severity, severity_wildcard) =
+            parse_severity_map(lint_partial.severity, &custom_ids, &mut errors);
         let severity_overrides =
-            parse_severity_overrides(lint_partial.severity_overrides, &mut errors);
-        let ignore = parse_ignore_list(lint_partial.ignore, &mut errors);
+            parse_severity_overrides(lint_partial.severity_overrides, &custom_ids, &mut errors);
+        let ignore = parse_ignore_list(lint_partial.ignore, &custom_ids, &mut errors);
 
         let schemas_partial = self.schemas.unwrap_or_default();
         let schemas = finalize_schemas(schemas_partial, &mut errors);
@@ -740,6 +1385,9 @@ impl PartialConfig {
         let toc_settings = TocSettings {
             start_marker: toc_start_marker.value,
             end_marker: toc_end_marker.value,
+            format: toc_format,
+            skip_missing: toc_skip_missing,
+            insert_at: toc_insert_at,
         };
 
         Ok(ResolvedConfig {
@@ -747,11 +1395,16 @@ impl PartialConfig {
                 name: project_partial.name.map(|name| name.value),
                 root: project_root,
                 exclude: PatternList::new(exclude_patterns),
+                anchor_dialect,
             },
             catalog: CatalogSettings {
                 output: catalog_output,
                 include: PatternList::new(catalog_include),
                 exclude: PatternList::new(catalog_exclude),
+                fields: catalog_fields,
+                timestamp: catalog_timestamp,
+                template: catalog_template,
+                directory_index: catalog_directory_index,
             },
             lint: LintSettings {
                 rules,
@@ -761,6 +1414,17 @@ impl PartialConfig {
                 max_heading_depth: max_heading_depth.value,
                 ignore,
                 toc: toc_settings,
+                custom,
+                orphans: OrphanSettings {
+                    roots: PatternList::new(orphan_roots),
+                    allow: PatternList::new(orphan_allow),
+                },
+                style,
+                spelling,
+                duplicates,
+            },
+            search: SearchSettings {
+                index: search_index,
             },
             schemas,
         })
@@ -772,6 +1436,7 @@ struct ProjectPartial {
     name: Option<Located<String>>,
     root: Option<Located<PathBuf>>,
     exclude: Option<Located<Vec<String>>>,
+    anchor_dialect: Option<Located<String>>,
 }
 
 impl ProjectPartial {
@@ -785,6 +1450,22 @@ impl ProjectPartial {
         if other.exclude.is_some() {
             self.exclude = other.exclude;
         }
+        if other.anchor_dialect.is_some() {
+            self.anchor_dialect = other.anchor_dialect;
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct SearchPartial {
+    index: Option<Located<PathBuf>>,
+}
+
+impl SearchPartial {
+    fn merge(&mut self, other: SearchPartial) {
+        if other.index.is_some() {
+            self.index = other.index;
+        }
     }
 }
 
@@ -793,6 +1474,10 @@ struct CatalogPartial {
     output: Option<Located<PathBuf>>,
     include_patterns: Option<Located<Vec<String>>>,
     exclude_patterns: Option<Located<Vec<String>>>,
+    fields: Option<Located<Vec<String>>>,
+    timestamp: Option<Located<String>>,
+    template: Option<Located<PathBuf>>,
+    directory_index: Option<Located<String>>,
 }
 
 impl CatalogPartial {
@@ -806,6 +1491,18 @@ impl CatalogPartial {
         if other.exclude_patterns.is_some() {
             self.exclude_patterns = other.exclude_patterns;
         }
+        if other.fields.is_some() {
+            self.fields = other.fields;
+        }
+        if other.timestamp.is_some() {
+            self.timestamp = other.timestamp;
+        }
+        if other.template.is_some() {
+            self.template = other.template;
+        }
+        if other.directory_index.is_some() {
+            self.directory_index = other.directory_index;
+        }
     }
 }
 
@@ -818,6 +1515,13 @@ struct LintPartial {
     severity_overrides: Vec<Located<LintSeverityOverridePartial>>,
     toc_start_marker: Option<Located<String>>,
     toc_end_marker: Option<Located<String>>,
+    custom: Vec<Located<CustomRulePartial>>,
+    orphan_roots: Option<Located<Vec<String>>>,
+    orphan_allow: Option<Located<Vec<String>>>,
+    style: StylePartial,
+    spelling: SpellingPartial,
+    duplicates: DuplicatesPartial,
+    toc: TocPartial,
 }
 
 impl LintPartial {
@@ -834,49 +1538,181 @@ impl LintPartial {
         if other.toc_end_marker.is_some() {
             self.toc_end_marker = other.toc_end_marker;
         }
+        if other.orphan_roots.is_some() {
+            self.orphan_roots = other.orphan_roots;
+        }
+        if other.orphan_allow.is_some() {
+            self.orphan_allow = other.orphan_allow;
+        }
+        self.style.merge(other.style);
+        self.spelling.merge(other.spelling);
+        self.duplicates.merge(other.duplicates);
+        self.toc.merge(other.toc);
         for (key, value) in other.severity {
             self.severity.insert(key, value);
         }
         self.ignore.extend(other.ignore);
         self.severity_overrides.extend(other.severity_overrides);
+        // Higher-precedence layers replace custom rules that share an id.
+        for entry in other.custom {
+            self.custom.retain(|existing| {
+                existing.source == entry.source || existing.value.id != entry.value.id
+            });
+            self.custom.push(entry);
+        }
     }
 }
 
-#[derive(Clone, Debug)]
-struct LintIgnorePartial {
-    path: String,
-    rules: Vec<String>,
+#[derive(Clone, Debug, Default)]
+struct TocPartial {
+    min_depth: Option<Located<u8>>,
+    max_depth: Option<Located<u8>>,
+    numbered: Option<Located<bool>>,
+    bullet: Option<Located<String>>,
+    exclude: Option<Located<Vec<String>>>,
+    after_block: Option<Located<bool>>,
+    skip_missing: Option<Located<bool>>,
+    insert_at: Option<Located<String>>,
 }
 
-#[derive(Clone, Debug)]
-struct LintSeverityOverridePartial {
-    path: String,
-    rules: HashMap<String, String>,
+impl TocPartial {
+    fn merge(&mut self, other: TocPartial) {
+        if other.min_depth.is_some() {
+            self.min_depth = other.min_depth;
+        }
+        if other.max_depth.is_some() {
+            self.max_depth = other.max_depth;
+        }
+        if other.numbered.is_some() {
+            self.numbered = other.numbered;
+        }
+        if other.bullet.is_some() {
+            self.bullet = other.bullet;
+        }
+        if other.exclude.is_some() {
+            self.exclude = other.exclude;
+        }
+        if other.after_block.is_some() {
+            self.after_block = other.after_block;
+        }
+        if other.skip_missing.is_some() {
+            self.skip_missing = other.skip_missing;
+        }
+        if other.insert_at.is_some() {
+            self.insert_at = other.insert_at;
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
-struct SchemasPartial {
-    entries: HashMap<String, SchemaDefinitionPartial>,
+struct DuplicatesPartial {
+    threshold: Option<Located<f64>>,
+    min_words: Option<Located<usize>>,
 }
 
-impl SchemasPartial {
-    fn merge(&mut self, other: SchemasPartial) {
-        for (name, definition) in other.entries {
-            self.entries
-                .entry(name)
-                .and_modify(|existing| existing.merge(definition.clone()))
-                .or_insert(definition);
+impl DuplicatesPartial {
+    fn merge(&mut self, other: DuplicatesPartial) {
+        if other.threshold.is_some() {
+            self.threshold = other.threshold;
+        }
+        if other.min_words.is_some() {
+            self.min_words = other.min_words;
         }
     }
 }
 
+/// Words accumulate across layers; `word_files` is replaced by higher layers.
 #[derive(Clone, Debug, Default)]
-struct SchemaDefinitionPartial {
-    source: Option<ConfigSource>,
-    patterns: Option<Located<Vec<String>>>,
-    required_sections: Option<Located<Vec<String>>>,
-    allow_additional: Option<Located<bool>>,
-    allow_empty: Option<Located<bool>>,
+struct SpellingPartial {
+    words: Vec<Located<String>>,
+    word_files: Option<Located<Vec<PathBuf>>>,
+}
+
+impl SpellingPartial {
+    fn merge(&mut self, other: SpellingPartial) {
+        self.words.extend(other.words);
+        if other.word_files.is_some() {
+            self.word_files = other.word_files;
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct StylePartial {
+    max_line_length: Option<Located<usize>>,
+    allow_hard_breaks: Option<Located<bool>>,
+    heading_punctuation: Option<Located<String>>,
+    list_marker: Option<Located<String>>,
+    emphasis: Option<Located<String>>,
+}
+
+impl StylePartial {
+    fn merge(&mut self, other: StylePartial) {
+        if other.max_line_length.is_some() {
+            self.max_line_length = other.max_line_length;
+        }
+        if other.allow_hard_breaks.is_some() {
+            self.allow_hard_breaks = other.allow_hard_breaks;
+        }
+        if other.heading_punctuation.is_some() {
+            self.heading_punctuation = other.heading_punctuation;
+        }
+        if other.list_marker.is_some() {
+            self.list_marker = other.list_marker;
+        }
+        if other.emphasis.is_some() {
+            self.emphasis = other.emphasis;
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct LintIgnorePartial {
+    path: String,
+    rules: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+struct CustomRulePartial {
+    id: String,
+    target: Option<String>,
+    pattern: Option<String>,
+    require: bool,
+    max_lines: Option<usize>,
+    paths: Vec<String>,
+    message: String,
+    severity: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+struct LintSeverityOverridePartial {
+    path: String,
+    rules: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct SchemasPartial {
+    entries: HashMap<String, SchemaDefinitionPartial>,
+}
+
+impl SchemasPartial {
+    fn merge(&mut self, other: SchemasPartial) {
+        for (name, definition) in other.entries {
+            self.entries
+                .entry(name)
+                .and_modify(|existing| existing.merge(definition.clone()))
+                .or_insert(definition);
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct SchemaDefinitionPartial {
+    source: Option<ConfigSource>,
+    patterns: Option<Located<Vec<String>>>,
+    required_sections: Option<Located<Vec<String>>>,
+    allow_additional: Option<Located<bool>>,
+    allow_empty: Option<Located<bool>>,
     min_sections: Option<Located<u32>>,
     min_heading_level: Option<Located<u8>>,
     max_heading_level: Option<Located<u8>>,
@@ -942,6 +1778,250 @@ fn resolve_path(located: &Located<PathBuf>) -> PathBuf {
     }
 }
 
+fn finalize_style(partial: StylePartial, errors: &mut Vec<ConfigValidationError>) -> StyleSettings {
+    let mut style = StyleSettings::default();
+
+    if let Some(max) = partial.max_line_length {
+        if max.value == 0 {
+            errors.push(
+                ConfigValidationError::new(
+                    Some(max.source),
+                    "max_line_length must be greater than zero".into(),
+                )
+                .with_context("lint.style"),
+            );
+        } else {
+            style.max_line_length = max.value;
+        }
+    }
+    if let Some(allow) = partial.allow_hard_breaks {
+        style.allow_hard_breaks = allow.value;
+    }
+    if let Some(punctuation) = partial.heading_punctuation {
+        style.heading_punctuation = punctuation.value;
+    }
+    if let Some(marker) = partial.list_marker {
+        match marker.value.parse() {
+            Ok(value) => style.list_marker = value,
+            Err(_) => errors.push(
+                ConfigValidationError::new(
+                    Some(marker.source),
+                    format!(
+                        "unknown list_marker '{}' (expected consistent, dash, asterisk, or plus)",
+                        marker.value
+                    ),
+                )
+                .with_context("lint.style"),
+            ),
+        }
+    }
+    if let Some(emphasis) = partial.emphasis {
+        match emphasis.value.parse() {
+            Ok(value) => style.emphasis = value,
+            Err(_) => errors.push(
+                ConfigValidationError::new(
+                    Some(emphasis.source),
+                    format!(
+                        "unknown emphasis '{}' (expected consistent, asterisk, or underscore)",
+                        emphasis.value
+                    ),
+                )
+                .with_context("lint.style"),
+            ),
+        }
+    }
+
+    style
+}
+
+fn finalize_spelling(
+    partial: SpellingPartial,
+    errors: &mut Vec<ConfigValidationError>,
+) -> SpellingSettings {
+    let mut spelling = SpellingSettings::default();
+
+    for word in partial.words {
+        let trimmed = word.value.trim();
+        if trimmed.is_empty() || trimmed.contains(char::is_whitespace) {
+            errors.push(
+                ConfigValidationError::new(
+                    Some(word.source),
+                    format!("invalid word '{}' (expected a single word)", word.value),
+                )
+                .with_context("lint.spelling.words"),
+            );
+        } else {
+            spelling.words.push(trimmed.to_string());
+        }
+    }
+    if let Some(files) = partial.word_files {
+        if files.value.iter().any(|path| path.is_absolute()) {
+            errors.push(
+                ConfigValidationError::new(
+                    Some(files.source),
+                    "word_files must be relative to the project root".into(),
+                )
+                .with_context("lint.spelling"),
+            );
+        } else {
+            spelling.word_files = files.value;
+        }
+    }
+
+    spelling
+}
+
+fn parse_catalog_fields(
+    located: Option<Located<Vec<String>>>,
+    errors: &mut Vec<ConfigValidationError>,
+) -> Vec<CatalogField> {
+    let Some(located) = located else {
+        return Vec::new();
+    };
+    let mut fields = Vec::new();
+    for name in &located.value {
+        match name.parse::<CatalogField>() {
+            Ok(field) if !fields.contains(&field) => fields.push(field),
+            Ok(_) => {}
+            Err(()) => errors.push(
+                ConfigValidationError::new(
+                    Some(located.source.clone()),
+                    format!(
+                        "unknown catalog field '{name}' (expected {})",
+                        CatalogField::ALL
+                            .iter()
+                            .map(CatalogField::as_str)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                )
+                .with_context("catalog.fields"),
+            ),
+        }
+    }
+    fields
+}
+
+fn finalize_toc(
+    partial: TocPartial,
+    errors: &mut Vec<ConfigValidationError>,
+) -> (TocFormat, bool, TocInsertPosition) {
+    let mut format = TocFormat::default();
+    let mut depth = |located: Option<Located<u8>>, field: &str, target: &mut usize| {
+        if let Some(value) = located {
+            if (1..=6).contains(&value.value) {
+                *target = value.value as usize;
+            } else {
+                errors.push(
+                    ConfigValidationError::new(
+                        Some(value.source),
+                        format!("{field} must be between 1 and 6 (received {})", value.value),
+                    )
+                    .with_context("lint.toc"),
+                );
+            }
+        }
+    };
+    let min_source = partial.min_depth.as_ref().map(|value| value.source.clone());
+    depth(partial.min_depth, "min_depth", &mut format.min_depth);
+    depth(partial.max_depth, "max_depth", &mut format.max_depth);
+    if format.min_depth > format.max_depth {
+        errors.push(
+            ConfigValidationError::new(
+                min_source,
+                format!(
+                    "min_depth ({}) cannot exceed max_depth ({})",
+                    format.min_depth, format.max_depth
+                ),
+            )
+            .with_context("lint.toc"),
+        );
+    }
+
+    if let Some(numbered) = partial.numbered {
+        format.numbered = numbered.value;
+    }
+    if let Some(bullet) = partial.bullet {
+        match bullet.value.as_str() {
+            "-" | "*" | "+" => format.bullet = bullet.value.chars().next().unwrap_or('-'),
+            other => errors.push(
+                ConfigValidationError::new(
+                    Some(bullet.source),
+                    format!("bullet must be one of '-', '*', or '+' (received '{other}')"),
+                )
+                .with_context("lint.toc"),
+            ),
+        }
+    }
+    if let Some(exclude) = partial.exclude {
+        for pattern in &exclude.value {
+            match Regex::new(pattern) {
+                Ok(regex) => format.exclude.push(regex),
+                Err(err) => errors.push(
+                    ConfigValidationError::new(
+                        Some(exclude.source.clone()),
+                        format!("invalid exclude pattern '{pattern}': {err}"),
+                    )
+                    .with_context("lint.toc"),
+                ),
+            }
+        }
+    }
+    if let Some(after_block) = partial.after_block {
+        format.after_block = after_block.value;
+    }
+
+    let skip_missing = partial.skip_missing.is_some_and(|value| value.value);
+
+    let mut insert_at = TocInsertPosition::default();
+    if let Some(position) = partial.insert_at {
+        match position.value.parse() {
+            Ok(value) => insert_at = value,
+            Err(()) => errors.push(
+                ConfigValidationError::new(
+                    Some(position.source),
+                    format!(
+                        "unknown insert_at '{}' (expected after-intro, before-first-section, or contents-heading)",
+                        position.value
+                    ),
+                )
+                .with_context("lint.toc"),
+            ),
+        }
+    }
+
+    (format, skip_missing, insert_at)
+}
+
+fn finalize_duplicates(
+    partial: DuplicatesPartial,
+    errors: &mut Vec<ConfigValidationError>,
+) -> DuplicateSettings {
+    let mut duplicates = DuplicateSettings::default();
+
+    if let Some(threshold) = partial.threshold {
+        if threshold.value > 0.0 && threshold.value <= 1.0 {
+            duplicates.threshold = threshold.value;
+        } else {
+            errors.push(
+                ConfigValidationError::new(
+                    Some(threshold.source),
+                    format!(
+                        "threshold must be greater than 0 and at most 1 (received {})",
+                        threshold.value
+                    ),
+                )
+                .with_context("lint.duplicates"),
+            );
+        }
+    }
+    if let Some(min_words) = partial.min_words {
+        duplicates.min_words = min_words.value;
+    }
+
+    duplicates
+}
+
 fn compile_patterns(
     located: Located<Vec<String>>,
     context: &str,
@@ -1138,16 +2218,37 @@ fn pattern_specificity(pattern: &Pattern) -> PatternSpecificity {
     }
 }
 
+fn resolve_rule(name: &str, custom_ids: &HashSet<Arc<str>>) -> Option<LintRule> {
+    name.parse::<LintRule>()
+        .ok()
+        .or_else(|| custom_ids.get(name).cloned().map(LintRule::Custom))
+}
+
 fn parse_rules(
     located: Located<Vec<String>>,
+    custom_ids: &HashSet<Arc<str>>,
     errors: &mut Vec<ConfigValidationError>,
 ) -> Vec<LintRule> {
     let mut result = Vec::new();
     let mut seen = HashSet::new();
     for rule_name in located.value {
-        match rule_name.parse::<LintRule>() {
-            Ok(rule) => {
-                if !seen.insert(rule) {
+        // Custom rules run whenever they are declared; `lint.rules` selects built-ins only.
+        if custom_ids.contains(rule_name.as_str()) {
+            errors.push(
+                ConfigValidationError::new(
+                    Some(located.source.clone()),
+                    format!(
+                        "custom rule '{rule_name}' cannot be listed in lint.rules; \
+                         set lint.severity.{rule_name} = \"ignore\" to turn it off"
+                    ),
+                )
+                .with_context("lint.rules"),
+            );
+            continue;
+        }
+        match rule_name.parse::<LintRule>().ok() {
+            Some(rule) => {
+                if !seen.insert(rule.clone()) {
                     errors.push(
                         ConfigValidationError::new(
                             Some(located.source.clone()),
@@ -1159,7 +2260,7 @@ fn parse_rules(
                     result.push(rule);
                 }
             }
-            Err(_) => errors.push(
+            None => errors.push(
                 ConfigValidationError::new(
                     Some(located.source.clone()),
                     format!("unknown lint rule '{rule_name}'"),
@@ -1173,6 +2274,7 @@ fn parse_rules(
 
 fn parse_severity_map(
     raw: HashMap<String, Located<String>>,
+    custom_ids: &HashSet<Arc<str>>,
     errors: &mut Vec<ConfigValidationError>,
 ) -> (HashMap<LintRule, SeverityLevel>, Option<SeverityLevel>) {
     let mut result = HashMap::new();
@@ -1207,8 +2309,8 @@ fn parse_severity_map(
             continue;
         }
 
-        match rule_name.parse::<LintRule>() {
-            Ok(rule) => match located_value.value.parse::<SeverityLevel>() {
+        match resolve_rule(&rule_name, custom_ids) {
+            Some(rule) => match located_value.value.parse::<SeverityLevel>() {
                 Ok(level) => {
                     result.insert(rule, level);
                 }
@@ -1223,7 +2325,7 @@ fn parse_severity_map(
                     .with_context("lint.severity"),
                 ),
             },
-            Err(_) => errors.push(
+            None => errors.push(
                 ConfigValidationError::new(
                     Some(located_value.source.clone()),
                     format!("unknown lint rule '{}' in lint.severity", rule_name),
@@ -1237,6 +2339,7 @@ fn parse_severity_map(
 
 fn parse_severity_overrides(
     entries: Vec<Located<LintSeverityOverridePartial>>,
+    custom_ids: &HashSet<Arc<str>>,
     errors: &mut Vec<ConfigValidationError>,
 ) -> Vec<LintSeverityOverride> {
     let mut overrides = Vec::new();
@@ -1302,8 +2405,8 @@ fn parse_severity_overrides(
                 continue;
             }
 
-            match rule_name.parse::<LintRule>() {
-                Ok(rule) => match severity_value.parse::<SeverityLevel>() {
+            match resolve_rule(&rule_name, custom_ids) {
+                Some(rule) => match severity_value.parse::<SeverityLevel>() {
                     Ok(level) => {
                         rules.insert(rule, level);
                     }
@@ -1320,7 +2423,7 @@ fn parse_severity_overrides(
                         .with_context("lint.severity_overrides"),
                     ),
                 },
-                Err(_) => errors.push(
+                None => errors.push(
                     ConfigValidationError::new(
                         Some(source.clone()),
                         format!(
@@ -1361,6 +2464,7 @@ fn parse_severity_overrides(
 
 fn parse_ignore_list(
     entries: Vec<Located<LintIgnorePartial>>,
+    custom_ids: &HashSet<Arc<str>>,
     errors: &mut Vec<ConfigValidationError>,
 ) -> Vec<LintIgnore> {
     let mut result = Vec::new();
@@ -1396,9 +2500,9 @@ fn parse_ignore_list(
                 all_rules = true;
                 continue;
             }
-            match rule_name.parse::<LintRule>() {
-                Ok(rule) => rules.push(rule),
-                Err(_) => errors.push(
+            match resolve_rule(&rule_name, custom_ids) {
+                Some(rule) => rules.push(rule),
+                None => errors.push(
                     ConfigValidationError::new(
                         Some(source.clone()),
                         format!(
@@ -1442,11 +2546,198 @@ fn parse_ignore_list(
     result
 }
 
+fn parse_custom_rules(
+    entries: Vec<Located<CustomRulePartial>>,
+    errors: &mut Vec<ConfigValidationError>,
+) -> Vec<CustomLintRule> {
+    let mut result: Vec<CustomLintRule> = Vec::new();
+    for entry in entries {
+        let Located { value, source } = entry;
+        let id = value.id.trim().to_string();
+        let context = format!("lint.custom.{id}");
+        let error_count = errors.len();
+
+        if id.is_empty() || id.contains(char::is_whitespace) || id == "*" {
+            errors.push(
+                ConfigValidationError::new(
+                    Some(source.clone()),
+                    format!("invalid custom lint rule id '{}'", value.id),
+                )
+                .with_context("lint.custom"),
+            );
+            continue;
+        }
+        if id.parse::<LintRule>().is_ok() {
+            errors.push(
+                ConfigValidationError::new(
+                    Some(source.clone()),
+                    format!("custom lint rule '{id}' shadows a built-in rule"),
+                )
+                .with_context("lint.custom"),
+            );
+            continue;
+        }
+        if result.iter().any(|existing| *existing.id == *id) {
+            errors.push(
+                ConfigValidationError::new(
+                    Some(source.clone()),
+                    format!("duplicate custom lint rule '{id}'"),
+                )
+                .with_context("lint.custom"),
+            );
+            continue;
+        }
+
+        let target = match value.target.as_deref() {
+            None => CustomRuleTarget::Line,
+            Some(raw) => match raw.parse::<CustomRuleTarget>() {
+                Ok(target) => target,
+                Err(_) => {
+                    errors.push(
+                        ConfigValidationError::new(
+                            Some(source.clone()),
+                            format!(
+                                "unknown target '{raw}' (expected line, heading, link, code-block, or section)"
+                            ),
+                        )
+                        .with_context(context.clone()),
+                    );
+                    CustomRuleTarget::Line
+                }
+            },
+        };
+
+        let pattern = value
+            .pattern
+            .as_deref()
+            .and_then(|raw| match Regex::new(raw) {
+                Ok(regex) => Some(regex),
+                Err(err) => {
+                    errors.push(
+                        ConfigValidationError::new(
+                            Some(source.clone()),
+                            format!("invalid pattern '{raw}': {err}"),
+                        )
+                        .with_context(context.clone()),
+                    );
+                    None
+                }
+            });
+
+        match target {
+            CustomRuleTarget::Section => {
+                if value.max_lines.is_none() {
+                    errors.push(
+                        ConfigValidationError::new(
+                            Some(source.clone()),
+                            "section rules must set max_lines".into(),
+                        )
+                        .with_context(context.clone()),
+                    );
+                }
+                if value.require {
+                    errors.push(
+                        ConfigValidationError::new(
+                            Some(source.clone()),
+                            "require is not supported for section rules".into(),
+                        )
+                        .with_context(context.clone()),
+                    );
+                }
+            }
+            _ => {
+                if value.pattern.is_none() {
+                    errors.push(
+                        ConfigValidationError::new(
+                            Some(source.clone()),
+                            format!("{} rules must set a pattern", target.as_str()),
+                        )
+                        .with_context(context.clone()),
+                    );
+                }
+                if value.max_lines.is_some() {
+                    errors.push(
+                        ConfigValidationError::new(
+                            Some(source.clone()),
+                            "max_lines only applies to section rules".into(),
+                        )
+                        .with_context(context.clone()),
+                    );
+                }
+            }
+        }
+
+        if value.max_lines == Some(0) {
+            errors.push(
+                ConfigValidationError::new(
+                    Some(source.clone()),
+                    "max_lines must be greater than 0".into(),
+                )
+                .with_context(context.clone()),
+            );
+        }
+
+        if value.message.trim().is_empty() {
+            errors.push(
+                ConfigValidationError::new(Some(source.clone()), "message cannot be empty".into())
+                    .with_context(context.clone()),
+            );
+        }
+
+        let severity = match value.severity.as_deref() {
+            None => SeverityLevel::Error,
+            Some(raw) => match raw.parse::<SeverityLevel>() {
+                Ok(level) => level,
+                Err(_) => {
+                    errors.push(
+                        ConfigValidationError::new(
+                            Some(source.clone()),
+                            format!("invalid severity '{raw}'"),
+                        )
+                        .with_context(context.clone()),
+                    );
+                    SeverityLevel::Error
+                }
+            },
+        };
+
+        let paths = compile_patterns(
+            Located::new(value.paths, source.clone()),
+            &format!("{context}.paths"),
+            errors,
+        );
+
+        if errors.len() != error_count {
+            continue;
+        }
+
+        let matchers = paths
+            .iter()
+            .map(|pattern| pattern.glob().compile_matcher())
+            .collect();
+
+        result.push(CustomLintRule {
+            id: Arc::from(id),
+            target,
+            pattern,
+            require: value.require,
+            max_lines: value.max_lines,
+            paths: PatternList::new(paths),
+            message: value.message,
+            severity,
+            source,
+            matchers,
+        });
+    }
+    result
+}
+
 #[derive(Clone, Debug)]
 struct ResolvedConfig {
     project: ProjectSettings,
     catalog: CatalogSettings,
     lint: LintSettings,
+    search: SearchSettings,
     schemas: SchemaSettings,
 }
 
@@ -1518,9 +2809,25 @@ struct RawConfig {
     #[serde(default)]
     lint: Option<RawLint>,
     #[serde(default)]
+    search: Option<RawSearch>,
+    #[serde(default)]
     schemas: Option<HashMap<String, RawSchema>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct RawSearch {
+    #[serde(default)]
+    index: Option<PathBuf>,
+}
+
+impl RawSearch {
+    fn into_partial(self, source: ConfigSource) -> SearchPartial {
+        SearchPartial {
+            index: self.index.map(|value| Located::new(value, source)),
+        }
+    }
+}
+
 impl RawConfig {
     fn into_partial(self, source: ConfigSource) -> PartialConfig {
         PartialConfig {
@@ -1531,6 +2838,9 @@ impl RawConfig {
                 .catalog
                 .map(|catalog| catalog.into_partial(source.clone())),
             lint: self.lint.map(|lint| lint.into_partial(source.clone())),
+            search: self
+                .search
+                .map(|search| search.into_partial(source.clone())),
             schemas: self.schemas.map(|schemas| {
                 let mut resolved = SchemasPartial::default();
                 for (name, schema) in schemas {
@@ -1555,6 +2865,8 @@ struct RawProject {
     root: Option<PathBuf>,
     #[serde(default)]
     exclude: Option<Vec<String>>,
+    #[serde(default)]
+    anchor_dialect: Option<String>,
 }
 
 impl RawProject {
@@ -1565,6 +2877,9 @@ impl RawProject {
             exclude: self
                 .exclude
                 .map(|value| Located::new(value, source.clone())),
+            anchor_dialect: self
+                .anchor_dialect
+                .map(|value| Located::new(value, source.clone())),
         }
     }
 }
@@ -1577,6 +2892,14 @@ struct RawCatalog {
     include_patterns: Option<Vec<String>>,
     #[serde(default)]
     exclude_patterns: Option<Vec<String>>,
+    #[serde(default)]
+    fields: Option<Vec<String>>,
+    #[serde(default)]
+    timestamp: Option<String>,
+    #[serde(default)]
+    template: Option<PathBuf>,
+    #[serde(default)]
+    directory_index: Option<String>,
 }
 
 impl RawCatalog {
@@ -1588,6 +2911,16 @@ impl RawCatalog {
                 .map(|value| Located::new(value, source.clone())),
             exclude_patterns: self
                 .exclude_patterns
+                .map(|value| Located::new(value, source.clone())),
+            fields: self.fields.map(|value| Located::new(value, source.clone())),
+            timestamp: self
+                .timestamp
+                .map(|value| Located::new(value, source.clone())),
+            template: self
+                .template
+                .map(|value| Located::new(value, source.clone())),
+            directory_index: self
+                .directory_index
                 .map(|value| Located::new(value, source)),
         }
     }
@@ -1609,6 +2942,20 @@ struct RawLint {
     ignore: Vec<RawLintIgnore>,
     #[serde(default)]
     severity_overrides: Vec<RawLintSeverityOverride>,
+    #[serde(default)]
+    custom: Vec<RawCustomRule>,
+    #[serde(default)]
+    orphan_roots: Option<Vec<String>>,
+    #[serde(default)]
+    orphan_allow: Option<Vec<String>>,
+    #[serde(default)]
+    style: Option<RawStyle>,
+    #[serde(default)]
+    spelling: Option<RawSpelling>,
+    #[serde(default)]
+    duplicates: Option<RawDuplicates>,
+    #[serde(default)]
+    toc: Option<RawToc>,
 }
 
 impl RawLint {
@@ -1647,6 +2994,26 @@ impl RawLint {
             })
             .collect();
 
+        let custom = self
+            .custom
+            .into_iter()
+            .map(|entry| {
+                Located::new(
+                    CustomRulePartial {
+                        id: entry.id,
+                        target: entry.target,
+                        pattern: entry.pattern,
+                        require: entry.require.unwrap_or(false),
+                        max_lines: entry.max_lines,
+                        paths: entry.paths.unwrap_or_default(),
+                        message: entry.message,
+                        severity: entry.severity,
+                    },
+                    source.clone(),
+                )
+            })
+            .collect();
+
         LintPartial {
             rules: self.rules.map(|value| Located::new(value, source.clone())),
             max_heading_depth: self
@@ -1658,9 +3025,32 @@ impl RawLint {
             toc_end_marker: self
                 .toc_end_marker
                 .map(|value| Located::new(value, source.clone())),
+            orphan_roots: self
+                .orphan_roots
+                .map(|value| Located::new(value, source.clone())),
+            orphan_allow: self
+                .orphan_allow
+                .map(|value| Located::new(value, source.clone())),
+            style: self
+                .style
+                .map(|style| style.into_partial(&source))
+                .unwrap_or_default(),
+            spelling: self
+                .spelling
+                .map(|spelling| spelling.into_partial(&source))
+                .unwrap_or_default(),
+            duplicates: self
+                .duplicates
+                .map(|duplicates| duplicates.into_partial(&source))
+                .unwrap_or_default(),
+            toc: self
+                .toc
+                .map(|toc| toc.into_partial(&source))
+                .unwrap_or_default(),
             severity,
             ignore,
             severity_overrides,
+            custom,
         }
     }
 }
@@ -1720,6 +3110,135 @@ impl RawSchema {
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct RawStyle {
+    #[serde(default)]
+    max_line_length: Option<usize>,
+    #[serde(default)]
+    allow_hard_breaks: Option<bool>,
+    #[serde(default)]
+    heading_punctuation: Option<String>,
+    #[serde(default)]
+    list_marker: Option<String>,
+    #[serde(default)]
+    emphasis: Option<String>,
+}
+
+impl RawStyle {
+    fn into_partial(self, source: &ConfigSource) -> StylePartial {
+        StylePartial {
+            max_line_length: self
+                .max_line_length
+                .map(|value| Located::new(value, source.clone())),
+            allow_hard_breaks: self
+                .allow_hard_breaks
+                .map(|value| Located::new(value, source.clone())),
+            heading_punctuation: self
+                .heading_punctuation
+                .map(|value| Located::new(value, source.clone())),
+            list_marker: self
+                .list_marker
+                .map(|value| Located::new(value, source.clone())),
+            emphasis: self
+                .emphasis
+                .map(|value| Located::new(value, source.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawToc {
+    #[serde(default)]
+    min_depth: Option<u8>,
+    #[serde(default)]
+    max_depth: Option<u8>,
+    #[serde(default)]
+    numbered: Option<bool>,
+    #[serde(default)]
+    bullet: Option<String>,
+    #[serde(default)]
+    exclude: Option<Vec<String>>,
+    #[serde(default)]
+    after_block: Option<bool>,
+    #[serde(default)]
+    skip_missing: Option<bool>,
+    #[serde(default)]
+    insert_at: Option<String>,
+}
+
+impl RawToc {
+    fn into_partial(self, source: &ConfigSource) -> TocPartial {
+        TocPartial {
+            min_depth: self
+                .min_depth
+                .map(|value| Located::new(value, source.clone())),
+            max_depth: self
+                .max_depth
+                .map(|value| Located::new(value, source.clone())),
+            numbered: self
+                .numbered
+                .map(|value| Located::new(value, source.clone())),
+            bullet: self.bullet.map(|value| Located::new(value, source.clone())),
+            exclude: self
+                .exclude
+                .map(|value| Located::new(value, source.clone())),
+            after_block: self
+                .after_block
+                .map(|value| Located::new(value, source.clone())),
+            skip_missing: self
+                .skip_missing
+                .map(|value| Located::new(value, source.clone())),
+            insert_at: self
+                .insert_at
+                .map(|value| Located::new(value, source.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawDuplicates {
+    #[serde(default)]
+    threshold: Option<f64>,
+    #[serde(default)]
+    min_words: Option<usize>,
+}
+
+impl RawDuplicates {
+    fn into_partial(self, source: &ConfigSource) -> DuplicatesPartial {
+        DuplicatesPartial {
+            threshold: self
+                .threshold
+                .map(|value| Located::new(value, source.clone())),
+            min_words: self
+                .min_words
+                .map(|value| Located::new(value, source.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSpelling {
+    #[serde(default)]
+    words: Vec<String>,
+    #[serde(default)]
+    word_files: Option<Vec<PathBuf>>,
+}
+
+impl RawSpelling {
+    fn into_partial(self, source: &ConfigSource) -> SpellingPartial {
+        SpellingPartial {
+            words: self
+                .words
+                .into_iter()
+                .map(|word| Located::new(word, source.clone()))
+                .collect(),
+            word_files: self
+                .word_files
+                .map(|value| Located::new(value, source.clone())),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct RawLintIgnore {
     path: String,
@@ -1733,3 +3252,21 @@ struct RawLintSeverityOverride {
     #[serde(default)]
     rules: HashMap<String, String>,
 }
+
+#[derive(Debug, Deserialize)]
+struct RawCustomRule {
+    id: String,
+    #[serde(default)]
+    target: Option<String>,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default)]
+    require: Option<bool>,
+    #[serde(default)]
+    max_lines: Option<usize>,
+    #[serde(default)]
+    paths: Option<Vec<String>>,
+    message: String,
+    #[serde(default)]
+    severity: Option<String>,
+}
@@ -3,8 +3,8 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use markdown_doc_config::{
-    Config, ConfigError, ConfigSourceKind, LintIgnoreRules, LintRule, LoadOptions, Pattern,
-    SeverityLevel,
+    Config, ConfigError, ConfigSourceKind, CustomRuleTarget, LintIgnoreRules, LintRule,
+    LoadOptions, Pattern, SeverityLevel,
 };
 use tempfile::TempDir;
 
@@ -379,3 +379,172 @@ fn severity_overrides_and_wildcard_resolution() {
         "rule remains enabled because at least one path requires it",
     );
 }
+
+#[test]
+fn custom_rules_are_parsed_and_resolvable() {
+    let temp = TempDir::new().expect("tempdir");
+    let working_dir = canonical(temp.path());
+    write_file(
+        working_dir.join(".markdown-doc.toml"),
+        r#"
+        [lint.severity]
+        no-todo = "warning"
+
+        [[lint.ignore]]
+        path = "drafts/**"
+        rules = ["no-todo"]
+
+        [[lint.custom]]
+        id = "no-todo"
+        pattern = "TODO"
+        paths = ["docs/**"]
+        message = "Remove '{match}' before publishing"
+
+        [[lint.custom]]
+        id = "short-sections"
+        target = "section"
+        max_lines = 40
+        message = "Section '{match}' is too long"
+        severity = "warning"
+        "#,
+    );
+
+    let config =
+        Config::load(LoadOptions::default().with_working_dir(&working_dir)).expect("load config");
+
+    assert_eq!(config.lint.custom.len(), 2);
+    let no_todo = &config.lint.custom[0];
+    assert_eq!(&*no_todo.id, "no-todo");
+    assert_eq!(no_todo.target, CustomRuleTarget::Line);
+    assert!(no_todo.applies_to(Path::new("docs/guide.md")));
+    assert!(!no_todo.applies_to(Path::new("README.md")));
+    assert_eq!(
+        no_todo.render_message("TODO"),
+        "Remove 'TODO' before publishing"
+    );
+
+    let rule = no_todo.rule();
+    assert_eq!(
+        config.lint.severity_for(rule.clone()),
+        SeverityLevel::Warning
+    );
+    assert!(matches!(
+        &config.lint.ignore[0].rules,
+        LintIgnoreRules::Specific(rules) if rules == &vec![rule]
+    ));
+
+    let sections = &config.lint.custom[1];
+    assert_eq!(sections.target, CustomRuleTarget::Section);
+    assert_eq!(sections.max_lines, Some(40));
+    assert_eq!(
+        config.lint.severity_for(sections.rule()),
+        SeverityLevel::Warning
+    );
+}
+
+#[test]
+fn invalid_custom_rules_surface_validation_errors() {
+    let temp = TempDir::new().expect("tempdir");
+    let working_dir = canonical(temp.path());
+    write_file(
+        working_dir.join(".markdown-doc.toml"),
+        r#"
+        [lint]
+        rules = ["broken-links", "no-fixme"]
+
+        [[lint.custom]]
+        id = "broken-links"
+        pattern = "x"
+        message = "shadowed"
+
+        [[lint.custom]]
+        id = "no-fixme"
+        pattern = "FIXME"
+        message = "Resolve '{match}'"
+
+        [[lint.custom]]
+        id = "bad-regex"
+        pattern = "(unclosed"
+        message = "never compiles"
+
+        [[lint.custom]]
+        id = "no-pattern"
+        target = "heading"
+        message = "missing pattern"
+        "#,
+    );
+
+    let err = Config::load(LoadOptions::default().with_working_dir(&working_dir))
+        .expect_err("expected validation failure");
+
+    match err {
+        ConfigError::Validation(errors) => {
+            let joined = errors.to_string();
+            assert!(joined.contains("shadows a built-in rule"), "{joined}");
+            assert!(joined.contains("invalid pattern '(unclosed'"), "{joined}");
+            assert!(
+                joined.contains("heading rules must set a pattern"),
+                "{joined}"
+            );
+            assert!(
+                joined.contains("custom rule 'no-fixme' cannot be listed in lint.rules"),
+                "{joined}"
+            );
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[test]
+fn toc_settings_are_parsed_and_validated() {
+    let temp = TempDir::new().expect("tempdir");
+    let working_dir = canonical(temp.path());
+    write_file(
+        working_dir.join(".markdown-doc.toml"),
+        r#"
+        [lint.toc]
+        min_depth = 1
+        max_depth = 3
+        numbered = true
+        exclude = ["^Changelog$"]
+        skip_missing = true
+        "#,
+    );
+
+    let config =
+        Config::load(LoadOptions::default().with_working_dir(&working_dir)).expect("load config");
+    let toc = &config.lint.toc;
+    assert_eq!(toc.start_marker, "<!-- toc -->");
+    assert_eq!((toc.format.min_depth, toc.format.max_depth), (1, 3));
+    assert!(toc.format.numbered);
+    assert_eq!(toc.format.bullet, '-');
+    assert!(toc.format.exclude[0].is_match("Changelog"));
+    assert!(!toc.format.after_block);
+    assert!(toc.skip_missing);
+
+    write_file(
+        working_dir.join(".markdown-doc.toml"),
+        r#"
+        [lint.toc]
+        min_depth = 4
+        max_depth = 2
+        bullet = "1."
+        "#,
+    );
+    let err = Config::load(LoadOptions::default().with_working_dir(&working_dir))
+        .expect_err("expected validation failure");
+    match err {
+        ConfigError::Validation(errors) => {
+            let joined = errors.to_string();
+            assert!(
+                joined.contains("min_depth (4) cannot exceed max_depth (2)"),
+                "unexpected error output: {joined}"
+            );
+            assert!(
+                joined.contains("bullet must be one of"),
+                "unexpected error output: {joined}"
+            );
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+}
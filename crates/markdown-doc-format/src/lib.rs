@@ -1,10 +1,10 @@
 //! Output renderers for markdown-doc commands.
 
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
 
-use chrono::{DateTime, Local, Utc};
-use std::collections::HashSet;
+use chrono::{DateTime, FixedOffset};
+use std::collections::{BTreeMap, HashSet};
 
 use markdown_doc_config::{Config, LintRule, SeverityLevel};
 use serde::Serialize;
@@ -37,6 +37,17 @@ pub struct HeadingSummary {
     pub level: usize,
     pub text: String,
     pub anchor: String,
+    pub metadata: SectionMetadata,
+}
+
+/// Per-section catalog metadata; each field is `None` unless enabled in `catalog.fields`.
+#[derive(Clone, Debug, Default)]
+pub struct SectionMetadata {
+    pub summary: Option<String>,
+    pub words: Option<usize>,
+    pub tokens: Option<usize>,
+    /// First and last line (1-based, inclusive) of the section.
+    pub lines: Option<(usize, usize)>,
 }
 
 /// Catalog entry describing headings for a single document.
@@ -44,15 +55,30 @@ pub struct HeadingSummary {
 pub struct CatalogEntry {
     pub path: PathBuf,
     pub headings: Vec<HeadingSummary>,
+    /// RFC 3339 timestamp of the last change to the document.
+    pub last_modified: Option<String>,
+    /// Scalar front matter fields, when `front-matter` is enabled.
+    pub front_matter: Option<BTreeMap<String, String>>,
 }
 
 /// Aggregate catalog render data.
 #[derive(Clone, Debug)]
 pub struct CatalogRenderData {
-    pub generated_at: SystemTime,
+    /// `Last updated` timestamp; omitted from the output when `None`.
+    pub last_updated: Option<DateTime<FixedOffset>>,
     pub entries: Vec<CatalogEntry>,
+    /// Directory a per-directory catalog describes; entry paths are relative to it.
+    pub directory: Option<PathBuf>,
+    /// Child directories that have their own per-directory catalog.
+    pub subdirectories: Vec<PathBuf>,
 }
 
+/// Built-in catalog template, reproducing the classic `DOC_CATALOG.md` layout.
+pub const DEFAULT_CATALOG_TEMPLATE: &str = include_str!("../templates/catalog.md.j2");
+
+/// Error raised while rendering a catalog template.
+pub use minijinja::Error as TemplateError;
+
 /// Individual lint finding ready for rendering.
 #[derive(Clone, Debug)]
 pub struct LintFinding {
@@ -70,6 +96,30 @@ pub struct LintRenderData {
     pub error_count: usize,
     pub warning_count: usize,
     pub findings: Vec<LintFinding>,
+    pub baseline: Option<LintBaselineSummary>,
+    pub words_added: Option<LintWordsSummary>,
+}
+
+/// Outcome of comparing (or recording) findings against a lint baseline file.
+#[derive(Clone, Debug)]
+pub struct LintBaselineSummary {
+    pub path: PathBuf,
+    /// Findings recorded in the baseline (and therefore suppressed).
+    pub known: usize,
+    /// Findings absent from the baseline.
+    pub new: usize,
+    /// Baseline entries that no longer occur in scanned files.
+    pub fixed: usize,
+    /// True when the baseline was (re)written by this run.
+    pub written: bool,
+}
+
+/// Words recorded into a project word list by `lint --add-words`.
+#[derive(Clone, Debug)]
+pub struct LintWordsSummary {
+    pub path: PathBuf,
+    /// Newly added words, sorted.
+    pub words: Vec<String>,
 }
 
 /// Individual validate finding ready for rendering.
@@ -89,9 +139,48 @@ pub struct ValidateRenderData {
     pub findings: Vec<ValidateFinding>,
 }
 
+/// `Last modified` and `Front matter` lines shown under a catalog file heading.
+fn markdown_entry_details(entry: &CatalogEntry) -> Option<String> {
+    let mut details = Vec::new();
+    if let Some(modified) = &entry.last_modified {
+        details.push(format!("Last modified: {modified}"));
+    }
+    if let Some(front_matter) = entry.front_matter.as_ref().filter(|map| !map.is_empty()) {
+        let fields: Vec<String> = front_matter
+            .iter()
+            .map(|(key, value)| format!("`{key}`: {value}"))
+            .collect();
+        details.push(format!("Front matter: {}", fields.join(", ")));
+    }
+    (!details.is_empty()).then(|| details.join("  \n"))
+}
+
+/// Suffix appended to a catalog heading bullet: ` — summary (lines 3-9, 120 words, ~160 tokens)`.
+fn markdown_section_metadata(metadata: &SectionMetadata) -> String {
+    let mut details = Vec::new();
+    if let Some((start, end)) = metadata.lines {
+        details.push(format!("lines {start}-{end}"));
+    }
+    if let Some(words) = metadata.words {
+        details.push(format!("{words} words"));
+    }
+    if let Some(tokens) = metadata.tokens {
+        details.push(format!("~{tokens} tokens"));
+    }
+
+    let mut suffix = String::new();
+    if let Some(summary) = metadata.summary.as_deref().filter(|text| !text.is_empty()) {
+        suffix.push_str(" — ");
+        suffix.push_str(summary);
+    }
+    if !details.is_empty() {
+        suffix.push_str(&format!(" ({})", details.join(", ")));
+    }
+    suffix
+}
+
 /// Public renderer that transforms operation outputs into user-facing strings.
 pub struct Renderer {
-    #[allow(dead_code)]
     config: Config,
 }
 
@@ -101,48 +190,144 @@ impl Renderer {
         Self { config }
     }
 
-    /// Render catalog in Markdown format.
+    /// Render catalog in Markdown format using the built-in template.
     pub fn render_catalog_markdown(&self, data: &CatalogRenderData) -> String {
-        let timestamp = DateTime::<Utc>::from(data.generated_at)
-            .with_timezone(&Local)
-            .to_rfc3339();
+        self.render_catalog_template(DEFAULT_CATALOG_TEMPLATE, data)
+            .expect("built-in catalog template renders")
+    }
 
-        let mut output = String::new();
-        output.push_str("# Documentation Catalog\n\n");
-        output.push_str(&format!("Last updated: {}\n\n", timestamp));
-        output.push_str("## Catalog\n\n");
+    /// Render catalog with a Jinja-style template (see `DEFAULT_CATALOG_TEMPLATE`).
+    ///
+    /// Block tags swallow their trailing newline and leading indentation, so templates can
+    /// put each `{% ... %}` tag on its own line without emitting blank lines.
+    pub fn render_catalog_template(
+        &self,
+        template: &str,
+        data: &CatalogRenderData,
+    ) -> Result<String, TemplateError> {
+        #[derive(Serialize)]
+        struct TemplateHeading<'a> {
+            level: usize,
+            text: &'a str,
+            anchor: &'a str,
+            /// Two spaces per level below 1, for nested bullet lists.
+            indent: String,
+            summary: Option<&'a str>,
+            words: Option<usize>,
+            tokens: Option<usize>,
+            line_start: Option<usize>,
+            line_end: Option<usize>,
+            /// Pre-rendered ` — summary (lines, words, tokens)` suffix.
+            metadata: String,
+        }
 
-        for entry in &data.entries {
-            let path_str = normalize_path_display(&entry.path);
-            output.push_str(&format!("- [{}]({})\n", path_str, path_str));
+        #[derive(Serialize)]
+        struct TemplateEntry<'a> {
+            path: String,
+            name: String,
+            directory: String,
+            last_modified: Option<&'a str>,
+            front_matter: Option<&'a BTreeMap<String, String>>,
+            /// Pre-rendered `Last modified`/`Front matter` lines, if any.
+            details: Option<String>,
+            headings: Vec<TemplateHeading<'a>>,
         }
 
-        if !data.entries.is_empty() {
-            output.push_str("\n---\n\n");
+        #[derive(Serialize)]
+        struct TemplateDirectory<'a> {
+            path: String,
+            entries: Vec<&'a TemplateEntry<'a>>,
         }
 
-        for (idx, entry) in data.entries.iter().enumerate() {
-            let path_str = normalize_path_display(&entry.path);
-            output.push_str(&format!("## {}\n\n", path_str));
+        #[derive(Serialize)]
+        struct TemplateContext<'a> {
+            last_updated: Option<String>,
+            file_count: usize,
+            directory: Option<String>,
+            subdirectories: Vec<String>,
+            entries: &'a [TemplateEntry<'a>],
+            directories: Vec<TemplateDirectory<'a>>,
+        }
 
-            for heading in &entry.headings {
-                let indent = "  ".repeat(heading.level.saturating_sub(1));
-                output.push_str(&format!(
-                    "{}- [{}](#{})\n",
-                    indent, heading.text, heading.anchor
-                ));
-            }
+        let entries: Vec<TemplateEntry> = data
+            .entries
+            .iter()
+            .map(|entry| {
+                let directory = entry
+                    .path
+                    .parent()
+                    .filter(|parent| !parent.as_os_str().is_empty())
+                    .map_or_else(
+                        || ".".to_string(),
+                        |parent| normalize_path_display(parent).into_owned(),
+                    );
+                TemplateEntry {
+                    path: normalize_path_display(&entry.path).into_owned(),
+                    name: entry
+                        .path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                    directory,
+                    last_modified: entry.last_modified.as_deref(),
+                    front_matter: entry.front_matter.as_ref(),
+                    details: markdown_entry_details(entry),
+                    headings: entry
+                        .headings
+                        .iter()
+                        .map(|heading| TemplateHeading {
+                            level: heading.level,
+                            text: heading.text.as_str(),
+                            anchor: heading.anchor.as_str(),
+                            indent: "  ".repeat(heading.level.saturating_sub(1)),
+                            summary: heading.metadata.summary.as_deref(),
+                            words: heading.metadata.words,
+                            tokens: heading.metadata.tokens,
+                            line_start: heading.metadata.lines.map(|(start, _)| start),
+                            line_end: heading.metadata.lines.map(|(_, end)| end),
+                            metadata: markdown_section_metadata(&heading.metadata),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
 
-            if idx + 1 != data.entries.len() {
-                output.push_str("\n---\n\n");
-            }
+        let mut directories: Vec<TemplateDirectory> = Vec::new();
+        let mut grouped: BTreeMap<&str, Vec<&TemplateEntry>> = BTreeMap::new();
+        for entry in &entries {
+            grouped
+                .entry(entry.directory.as_str())
+                .or_default()
+                .push(entry);
         }
-
-        if !data.entries.is_empty() {
-            output.push('\n');
+        for (path, group) in grouped {
+            directories.push(TemplateDirectory {
+                path: path.to_string(),
+                entries: group,
+            });
         }
 
-        output
+        let context = TemplateContext {
+            last_updated: data.last_updated.map(|timestamp| timestamp.to_rfc3339()),
+            file_count: data.entries.len(),
+            directory: data
+                .directory
+                .as_deref()
+                .map(|directory| normalize_path_display(directory).into_owned()),
+            subdirectories: data
+                .subdirectories
+                .iter()
+                .map(|directory| normalize_path_display(directory).into_owned())
+                .collect(),
+            entries: &entries,
+            directories,
+        };
+
+        let mut env = minijinja::Environment::new();
+        env.set_trim_blocks(true);
+        env.set_lstrip_blocks(true);
+        env.set_keep_trailing_newline(true);
+        env.render_str(template, context)
     }
 
     /// Render catalog in JSON format.
@@ -152,30 +337,43 @@ impl Renderer {
             level: usize,
             text: &'a str,
             anchor: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            summary: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            words: Option<usize>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            tokens: Option<usize>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            line_start: Option<usize>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            line_end: Option<usize>,
         }
 
         #[derive(Serialize)]
         struct JsonFile<'a> {
             path: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            last_modified: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            front_matter: Option<&'a BTreeMap<String, String>>,
             headings: Vec<JsonHeading<'a>>,
         }
 
         #[derive(Serialize)]
         struct CatalogJson<'a> {
-            last_updated: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            last_updated: Option<String>,
             file_count: usize,
             files: Vec<JsonFile<'a>>,
         }
 
-        let timestamp = DateTime::<Utc>::from(data.generated_at)
-            .with_timezone(&Local)
-            .to_rfc3339();
-
         let files = data
             .entries
             .iter()
             .map(|entry| JsonFile {
                 path: normalize_path_display(&entry.path).into_owned(),
+                last_modified: entry.last_modified.as_deref(),
+                front_matter: entry.front_matter.as_ref(),
                 headings: entry
                     .headings
                     .iter()
@@ -183,13 +381,18 @@ impl Renderer {
                         level: heading.level,
                         text: heading.text.as_str(),
                         anchor: heading.anchor.as_str(),
+                        summary: heading.metadata.summary.as_deref(),
+                        words: heading.metadata.words,
+                        tokens: heading.metadata.tokens,
+                        line_start: heading.metadata.lines.map(|(start, _)| start),
+                        line_end: heading.metadata.lines.map(|(_, end)| end),
                     })
                     .collect(),
             })
             .collect();
 
         let json = CatalogJson {
-            last_updated: timestamp,
+            last_updated: data.last_updated.map(|timestamp| timestamp.to_rfc3339()),
             file_count: data.entries.len(),
             files,
         };
@@ -234,16 +437,59 @@ impl Renderer {
             ));
         }
 
+        if let Some(baseline) = &report.baseline {
+            let path = normalize_path_display(&baseline.path);
+            if baseline.written {
+                output.push_str(&format!(
+                    "📝 Baseline written to {} ({} findings)\n",
+                    path, baseline.known
+                ));
+            } else {
+                output.push_str(&format!(
+                    "📋 Baseline {}: {} known, {} new, {} fixed\n",
+                    path, baseline.known, baseline.new, baseline.fixed
+                ));
+            }
+        }
+
+        if let Some(added) = &report.words_added {
+            output.push_str(&format!(
+                "📖 Added {} word{} to {}\n",
+                added.words.len(),
+                if added.words.len() == 1 { "" } else { "s" },
+                normalize_path_display(&added.path)
+            ));
+        }
+
         output
     }
 
     /// Render lint results as JSON.
     pub fn render_lint_json(&self, report: &LintRenderData) -> serde_json::Result<String> {
         #[derive(Serialize)]
-        struct Summary {
+        struct BaselineJson {
+            path: String,
+            known: usize,
+            new: usize,
+            fixed: usize,
+            written: bool,
+        }
+
+        #[derive(Serialize)]
+        struct WordsJson<'a> {
+            path: String,
+            words: &'a [String],
+        }
+
+        #[derive(Serialize)]
+        struct Summary<'a> {
             files_scanned: usize,
             errors: usize,
             warnings: usize,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            baseline: Option<BaselineJson>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            words_added: Option<WordsJson<'a>>,
         }
 
         #[derive(Serialize)]
@@ -257,7 +503,7 @@ impl Renderer {
 
         #[derive(Serialize)]
         struct LintJson<'a> {
-            summary: Summary,
+            summary: Summary<'a>,
             findings: Vec<JsonFinding<'a>>,
         }
 
@@ -278,6 +524,17 @@ impl Renderer {
                 files_scanned: report.files_scanned,
                 errors: report.error_count,
                 warnings: report.warning_count,
+                baseline: report.baseline.as_ref().map(|baseline| BaselineJson {
+                    path: normalize_path_display(&baseline.path).into_owned(),
+                    known: baseline.known,
+                    new: baseline.new,
+                    fixed: baseline.fixed,
+                    written: baseline.written,
+                }),
+                words_added: report.words_added.as_ref().map(|added| WordsJson {
+                    path: normalize_path_display(&added.path).into_owned(),
+                    words: &added.words,
+                }),
             },
             findings,
         };
@@ -289,7 +546,7 @@ impl Renderer {
     pub fn render_lint_sarif(&self, report: &LintRenderData) -> serde_json::Result<String> {
         #[derive(Serialize)]
         struct Message<'a> {
-            text: &'a str,
+            text: Cow<'a, str>,
         }
 
         #[derive(Serialize)]
@@ -363,18 +620,18 @@ impl Renderer {
             runs: Vec<Run<'a>>,
         }
 
-        let mut rule_set: HashSet<LintRule> = HashSet::new();
+        let mut rule_set: HashSet<&LintRule> = HashSet::new();
 
         let results = report
             .findings
             .iter()
             .map(|finding| {
-                rule_set.insert(finding.rule);
+                rule_set.insert(&finding.rule);
                 ResultEntry {
                     rule_id: finding.rule.as_str(),
                     level: sarif_level(finding.severity),
                     message: Message {
-                        text: finding.message.as_str(),
+                        text: finding.message.as_str().into(),
                     },
                     locations: vec![Location {
                         physical_location: PhysicalLocation {
@@ -397,7 +654,7 @@ impl Renderer {
                 id: rule.as_str(),
                 name: rule_display_name(rule),
                 full_description: Message {
-                    text: rule_description(rule),
+                    text: self.rule_description(rule),
                 },
             })
             .collect();
@@ -495,6 +752,55 @@ impl Renderer {
 
         serde_json::to_string_pretty(&json)
     }
+
+    fn rule_description<'a>(&'a self, rule: &'a LintRule) -> Cow<'a, str> {
+        let text = match rule {
+            LintRule::BrokenLinks => "Internal markdown links must reference existing files.",
+            LintRule::BrokenAnchors => "Inline anchors must resolve to existing headings.",
+            LintRule::DuplicateAnchors => "Heading anchor slugs must be unique within a document.",
+            LintRule::HeadingHierarchy => {
+                "Heading levels must not skip levels or exceed configured depth."
+            }
+            LintRule::RequiredSections => {
+                "Documents must include schema-defined required sections."
+            }
+            LintRule::TocSync => {
+                "Declared tables of contents must reflect the current heading structure."
+            }
+            LintRule::UnusedSuppressions => {
+                "Inline suppression comments must suppress at least one finding."
+            }
+            LintRule::Orphans => {
+                "Documents must be linked from another page and reachable from a configured root."
+            }
+            LintRule::LineLength => {
+                "Lines must not exceed `lint.style.max_line_length` outside code blocks and tables."
+            }
+            LintRule::TrailingWhitespace => "Lines must not end with trailing whitespace.",
+            LintRule::HeadingPunctuation => "Headings must not end with punctuation.",
+            LintRule::ListMarkerStyle => "Bullet lists must use a consistent marker.",
+            LintRule::EmphasisStyle => "Emphasis and strong text must use a consistent delimiter.",
+            LintRule::BlanksAroundHeadings => "Headings must be surrounded by blank lines.",
+            LintRule::BlanksAroundFences => "Fenced code blocks must be surrounded by blank lines.",
+            LintRule::FencedCodeLanguage => "Fenced code blocks must declare a language.",
+            LintRule::Spelling => "Prose words must appear in the bundled or project dictionary.",
+            LintRule::DuplicateSections => {
+                "Sections should not duplicate a same-named section in another document."
+            }
+            LintRule::Custom(id) => {
+                let Some(custom) = self.config.lint.custom_rule(rule) else {
+                    return Cow::Borrowed(id);
+                };
+                // The message is a per-finding template; describe the rule by its pattern.
+                let pattern = custom
+                    .pattern
+                    .as_ref()
+                    .map_or("…", |pattern| pattern.as_str());
+                return custom.message.replace("{match}", pattern).into();
+            }
+        };
+        Cow::Borrowed(text)
+    }
 }
 
 fn normalize_path_display(path: &Path) -> std::borrow::Cow<'_, str> {
@@ -521,7 +827,7 @@ fn sarif_level(severity: SeverityLevel) -> &'static str {
     }
 }
 
-fn rule_display_name(rule: LintRule) -> &'static str {
+fn rule_display_name(rule: &LintRule) -> &str {
     match rule {
         LintRule::BrokenLinks => "Broken Links",
         LintRule::BrokenAnchors => "Broken Anchors",
@@ -529,20 +835,18 @@ fn rule_display_name(rule: LintRule) -> &'static str {
         LintRule::HeadingHierarchy => "Heading Hierarchy",
         LintRule::RequiredSections => "Required Sections",
         LintRule::TocSync => "TOC Sync",
-    }
-}
-
-fn rule_description(rule: LintRule) -> &'static str {
-    match rule {
-        LintRule::BrokenLinks => "Internal markdown links must reference existing files.",
-        LintRule::BrokenAnchors => "Inline anchors must resolve to existing headings.",
-        LintRule::DuplicateAnchors => "Heading anchor slugs must be unique within a document.",
-        LintRule::HeadingHierarchy => {
-            "Heading levels must not skip levels or exceed configured depth."
-        }
-        LintRule::RequiredSections => "Documents must include schema-defined required sections.",
-        LintRule::TocSync => {
-            "Declared tables of contents must reflect the current heading structure."
-        }
+        LintRule::UnusedSuppressions => "Unused Suppressions",
+        LintRule::Orphans => "Orphans",
+        LintRule::LineLength => "Line Length",
+        LintRule::TrailingWhitespace => "Trailing Whitespace",
+        LintRule::HeadingPunctuation => "Heading Punctuation",
+        LintRule::ListMarkerStyle => "List Marker Style",
+        LintRule::EmphasisStyle => "Emphasis Style",
+        LintRule::BlanksAroundHeadings => "Blanks Around Headings",
+        LintRule::BlanksAroundFences => "Blanks Around Fences",
+        LintRule::FencedCodeLanguage => "Fenced Code Language",
+        LintRule::Spelling => "Spelling",
+        LintRule::DuplicateSections => "Duplicate Sections",
+        LintRule::Custom(id) => id,
     }
 }
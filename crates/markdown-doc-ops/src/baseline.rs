@@ -0,0 +1,208 @@
+//! Lint baselines: record existing findings so stricter rules can be adopted
+//! incrementally while new violations still fail.
+//!
+//! Findings are fingerprinted by rule, path, and a hash of the offending line's
+//! content so entries survive unrelated edits that shift line numbers. File-level
+//! findings (orphans, custom `require` rules) use rule and path alone. Staged runs
+//! fingerprint the staged contents, matching what lint checked.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use markdown_doc_config::{LintRule, LintSettings};
+use markdown_doc_format::{LintBaselineSummary, LintFinding};
+use markdown_doc_utils::{atomic_write, content_hash};
+use serde::{Deserialize, Serialize};
+
+use crate::git::IndexSnapshot;
+use crate::{path_to_slash, OperationError};
+
+/// Baseline file written when `--write-baseline` is used without `--baseline`.
+pub const DEFAULT_BASELINE_FILE: &str = ".markdown-doc-baseline.json";
+
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BaselineFile {
+    version: u32,
+    findings: Vec<BaselineEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct BaselineEntry {
+    rule: String,
+    path: String,
+    fingerprint: String,
+    /// Informational only; matching relies on the fingerprint.
+    #[serde(default)]
+    message: String,
+}
+
+/// Computes fingerprints, reading each referenced file at most once.
+struct Fingerprinter<'a> {
+    root: &'a Path,
+    settings: &'a LintSettings,
+    index: Option<&'a IndexSnapshot>,
+    lines: HashMap<PathBuf, Vec<String>>,
+}
+
+impl<'a> Fingerprinter<'a> {
+    fn new(root: &'a Path, settings: &'a LintSettings, index: Option<&'a IndexSnapshot>) -> Self {
+        Self {
+            root,
+            settings,
+            index,
+            lines: HashMap::new(),
+        }
+    }
+
+    /// Findings about the document as a whole; their reported line carries no meaning.
+    fn is_file_level(&self, finding: &LintFinding) -> bool {
+        finding.line == 0
+            || finding.rule == LintRule::Orphans
+            || self
+                .settings
+                .custom_rule(&finding.rule)
+                .is_some_and(|custom| custom.require)
+    }
+
+    fn fingerprint(&mut self, finding: &LintFinding) -> String {
+        let content = if self.is_file_level(finding) {
+            ""
+        } else {
+            let (root, index) = (self.root, self.index);
+            let lines = self.lines.entry(finding.path.clone()).or_insert_with(|| {
+                let contents = match index {
+                    Some(index) => index.read(&finding.path),
+                    None => std::fs::read_to_string(root.join(&finding.path)),
+                };
+                contents
+                    .map(|contents| contents.lines().map(str::to_string).collect())
+                    .unwrap_or_default()
+            });
+            finding
+                .line
+                .checked_sub(1)
+                .and_then(|idx| lines.get(idx))
+                .map(|line| line.trim())
+                .unwrap_or("")
+        };
+
+        let key = format!(
+            "{}\0{}\0{}",
+            finding.rule.as_str(),
+            path_to_slash(&finding.path),
+            content
+        );
+        content_hash(key.as_bytes())[..32].to_string()
+    }
+}
+
+/// Record every finding into a baseline file at `path`. With `index`, findings are
+/// fingerprinted against the staged contents.
+pub(crate) fn write(
+    root: &Path,
+    path: &Path,
+    findings: &[LintFinding],
+    settings: &LintSettings,
+    index: Option<&IndexSnapshot>,
+) -> Result<LintBaselineSummary, OperationError> {
+    let mut fingerprinter = Fingerprinter::new(root, settings, index);
+    let mut entries: Vec<BaselineEntry> = findings
+        .iter()
+        .map(|finding| BaselineEntry {
+            rule: finding.rule.as_str().to_string(),
+            path: path_to_slash(&finding.path),
+            fingerprint: fingerprinter.fingerprint(finding),
+            message: finding.message.clone(),
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        (&a.path, &a.rule, &a.fingerprint).cmp(&(&b.path, &b.rule, &b.fingerprint))
+    });
+
+    let file = BaselineFile {
+        version: BASELINE_VERSION,
+        findings: entries,
+    };
+    let mut rendered = serde_json::to_string_pretty(&file)?;
+    rendered.push('\n');
+    atomic_write(&root.join(path), &rendered)?;
+
+    Ok(LintBaselineSummary {
+        path: path.to_path_buf(),
+        known: findings.len(),
+        new: 0,
+        fixed: 0,
+        written: true,
+    })
+}
+
+/// Drop findings recorded in the baseline at `path`, returning the new findings and
+/// a summary. Entries for files outside `scanned` are not counted as fixed.
+pub(crate) fn apply(
+    root: &Path,
+    path: &Path,
+    findings: Vec<LintFinding>,
+    scanned: &[PathBuf],
+    settings: &LintSettings,
+    index: Option<&IndexSnapshot>,
+) -> Result<(Vec<LintFinding>, LintBaselineSummary), OperationError> {
+    let absolute = root.join(path);
+    let contents = std::fs::read_to_string(&absolute).map_err(|source| OperationError::Io {
+        path: absolute.clone(),
+        source,
+    })?;
+    let file: BaselineFile = serde_json::from_str(&contents).map_err(|err| {
+        OperationError::InvalidInput(format!("invalid baseline file '{}': {err}", path.display()))
+    })?;
+    if file.version != BASELINE_VERSION {
+        return Err(OperationError::InvalidInput(format!(
+            "unsupported baseline version {} in '{}'",
+            file.version,
+            path.display()
+        )));
+    }
+
+    // Multiset of fingerprints so repeated identical findings are tracked individually.
+    let mut remaining: HashMap<(String, String, String), usize> = HashMap::new();
+    for entry in file.findings {
+        *remaining
+            .entry((entry.rule, entry.path, entry.fingerprint))
+            .or_default() += 1;
+    }
+
+    let mut fingerprinter = Fingerprinter::new(root, settings, index);
+    let mut known = 0;
+    let mut fresh = Vec::new();
+    for finding in findings {
+        let key = (
+            finding.rule.as_str().to_string(),
+            path_to_slash(&finding.path),
+            fingerprinter.fingerprint(&finding),
+        );
+        match remaining.get_mut(&key) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                known += 1;
+            }
+            _ => fresh.push(finding),
+        }
+    }
+
+    let scanned: HashSet<String> = scanned.iter().map(|path| path_to_slash(path)).collect();
+    let fixed = remaining
+        .iter()
+        .filter(|((_, path, _), _)| scanned.contains(path))
+        .map(|(_, count)| *count)
+        .sum();
+
+    let summary = LintBaselineSummary {
+        path: path.to_path_buf(),
+        known,
+        new: fresh.len(),
+        fixed,
+        written: false,
+    };
+    Ok((fresh, summary))
+}
@@ -0,0 +1,243 @@
+//! Catalog entry construction, including the optional metadata selected by
+//! `catalog.fields`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, FixedOffset};
+
+use markdown_doc_config::CatalogField;
+use markdown_doc_format::{CatalogEntry, HeadingSummary, SectionMetadata};
+use markdown_doc_parser::DocumentSection;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+use crate::front_matter;
+use crate::lines::{byte_to_line, compute_line_offsets};
+
+/// Summaries longer than this many characters are cut at a word boundary.
+const SUMMARY_LIMIT: usize = 200;
+/// Characters per token used for the token estimate.
+const CHARS_PER_TOKEN: usize = 4;
+
+pub(crate) fn build_entry(
+    path: &Path,
+    contents: &str,
+    sections: Vec<DocumentSection>,
+    fields: &[CatalogField],
+    last_modified: Option<String>,
+) -> CatalogEntry {
+    let enabled = |field| fields.contains(&field);
+    let offsets = compute_line_offsets(contents);
+
+    let headings = sections
+        .iter()
+        .enumerate()
+        .map(|(idx, section)| {
+            let body = section_text(contents, section);
+            // The section's own introduction ends where its first nested heading starts.
+            let intro_end = sections
+                .get(idx + 1)
+                .map_or(section.byte_range.end, |next| {
+                    next.heading.byte_range.start.min(section.byte_range.end)
+                });
+            let intro = contents
+                .get(section.heading.byte_range.end.min(intro_end)..intro_end)
+                .unwrap_or("");
+
+            HeadingSummary {
+                level: section.heading.depth,
+                text: section.heading.normalized.clone(),
+                anchor: section.heading.anchor.clone(),
+                metadata: SectionMetadata {
+                    summary: enabled(CatalogField::Summary)
+                        .then(|| summary(intro))
+                        .flatten(),
+                    words: enabled(CatalogField::Words).then(|| word_count(body)),
+                    tokens: enabled(CatalogField::Tokens)
+                        .then(|| body.chars().count().div_ceil(CHARS_PER_TOKEN)),
+                    lines: enabled(CatalogField::Lines).then(|| {
+                        let start = section.byte_range.start;
+                        let end = start + body.len().saturating_sub(1);
+                        (byte_to_line(start, &offsets), byte_to_line(end, &offsets))
+                    }),
+                },
+            }
+        })
+        .collect();
+
+    CatalogEntry {
+        path: normalize_relative_path(path),
+        headings,
+        last_modified: last_modified.filter(|_| enabled(CatalogField::Modified)),
+        front_matter: enabled(CatalogField::FrontMatter).then(|| front_matter(contents)),
+    }
+}
+
+/// Latest commit time among `targets`, used for `catalog.timestamp = "git"`.
+pub(crate) fn latest_commit_time(
+    commit_times: &HashMap<PathBuf, String>,
+    targets: &[PathBuf],
+) -> Option<DateTime<FixedOffset>> {
+    targets
+        .iter()
+        .filter_map(|path| commit_times.get(path))
+        .filter_map(|time| DateTime::parse_from_rfc3339(time).ok())
+        .max()
+}
+
+/// Blank out the value of the catalog's `Last updated` line (markdown) or
+/// `last_updated` key (JSON) so renders can be compared across runs.
+pub(crate) fn strip_timestamp(rendered: &str) -> String {
+    let mut stripped = String::with_capacity(rendered.len());
+    for line in rendered.split_inclusive('\n') {
+        match ["Last updated:", "  \"last_updated\":"]
+            .into_iter()
+            .find(|prefix| line.starts_with(prefix))
+        {
+            Some(prefix) => {
+                stripped.push_str(prefix);
+                stripped.push('\n');
+            }
+            None => stripped.push_str(line),
+        }
+    }
+    stripped
+}
+
+/// One per-directory catalog (`catalog.directory_index`).
+pub(crate) struct DirectoryPage {
+    pub directory: PathBuf,
+    /// The directory's own documents, with paths relative to it.
+    pub entries: Vec<CatalogEntry>,
+    /// Nearest descendant directories with a page of their own, relative to `directory`.
+    pub subdirectories: Vec<PathBuf>,
+}
+
+pub(crate) fn directory_pages(entries: &[CatalogEntry]) -> Vec<DirectoryPage> {
+    let mut grouped: BTreeMap<PathBuf, Vec<CatalogEntry>> = BTreeMap::new();
+    for entry in entries {
+        let directory = entry
+            .path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let mut relative = entry.clone();
+        relative.path = entry
+            .path
+            .strip_prefix(&directory)
+            .unwrap_or(&entry.path)
+            .to_path_buf();
+        grouped.entry(directory).or_default().push(relative);
+    }
+
+    let directories: Vec<PathBuf> = grouped.keys().cloned().collect();
+    grouped
+        .into_iter()
+        .map(|(directory, entries)| {
+            let subdirectories = directories
+                .iter()
+                .filter(|candidate| {
+                    candidate
+                        .ancestors()
+                        .skip(1)
+                        .find(|ancestor| directories.iter().any(|dir| dir == ancestor))
+                        == Some(directory.as_path())
+                })
+                .filter_map(|candidate| candidate.strip_prefix(&directory).ok())
+                .map(Path::to_path_buf)
+                .collect();
+            DirectoryPage {
+                directory,
+                entries,
+                subdirectories,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn normalize_relative_path(path: &Path) -> PathBuf {
+    if path.components().next().is_none() {
+        PathBuf::from(".")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Section source (heading included, nested sections included) without trailing
+/// blank lines.
+fn section_text<'a>(contents: &'a str, section: &DocumentSection) -> &'a str {
+    contents
+        .get(section.byte_range.clone())
+        .unwrap_or("")
+        .trim_end()
+}
+
+pub(crate) fn word_count(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// First sentence of the first paragraph in `intro`, as plain text.
+fn summary(intro: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut in_paragraph = false;
+    for event in Parser::new_ext(intro, Options::all()) {
+        match event {
+            Event::Start(Tag::Paragraph) => in_paragraph = true,
+            Event::End(Tag::Paragraph) if in_paragraph => break,
+            Event::Text(value) | Event::Code(value) if in_paragraph => text.push_str(&value),
+            Event::SoftBreak | Event::HardBreak if in_paragraph => text.push(' '),
+            _ => {}
+        }
+    }
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    let sentence = first_sentence(&text);
+    if sentence.chars().count() <= SUMMARY_LIMIT {
+        return Some(sentence.to_string());
+    }
+    let cut: String = sentence.chars().take(SUMMARY_LIMIT).collect();
+    let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(head, _)| head);
+    Some(format!("{}…", cut.trim_end_matches([',', ';', ':'])))
+}
+
+fn first_sentence(text: &str) -> &str {
+    let mut chars = text.char_indices().peekable();
+    while let Some((idx, ch)) = chars.next() {
+        if matches!(ch, '.' | '!' | '?') && matches!(chars.peek(), None | Some((_, ' '))) {
+            return &text[..idx + ch.len_utf8()];
+        }
+    }
+    text
+}
+
+/// Top-level `key: value` pairs of a leading YAML front matter block. Nested mappings
+/// and block lists are skipped; flow values are kept verbatim.
+fn front_matter(contents: &str) -> BTreeMap<String, String> {
+    let Some(front_matter) = front_matter::parse(contents) else {
+        return BTreeMap::new();
+    };
+    front_matter
+        .body
+        .lines()
+        .filter_map(front_matter::field)
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| (key.to_string(), unquote(value).to_string()))
+        .collect()
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
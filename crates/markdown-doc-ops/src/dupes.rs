@@ -0,0 +1,247 @@
+//! Cross-file near-duplicate section detection.
+//!
+//! Sections are grouped by normalized heading, their bodies are split into word
+//! shingles, and MinHash signatures estimate the Jaccard similarity between bodies
+//! in different documents. Pairs above the configured threshold are merged into
+//! clusters.
+
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use markdown_doc_config::DuplicateSettings;
+use markdown_doc_parser::DocumentSection;
+
+use crate::lines::{byte_to_line, compute_line_offsets};
+
+/// Words per shingle.
+const SHINGLE_SIZE: usize = 3;
+/// Hash functions per MinHash signature.
+const SIGNATURE_SIZE: usize = 128;
+
+/// Clusters of similar sections found across the scanned documents.
+#[derive(Clone, Debug, Default)]
+pub struct DuplicateReport {
+    pub clusters: Vec<DuplicateCluster>,
+}
+
+impl DuplicateReport {
+    /// True when no duplicate clusters were found.
+    pub fn is_empty(&self) -> bool {
+        self.clusters.is_empty()
+    }
+
+    /// Describe the other members of the cluster containing the section at `line`.
+    pub(crate) fn reason_for(&self, path: &Path, line: usize) -> Option<String> {
+        let cluster = self.clusters.iter().find(|cluster| {
+            cluster
+                .sections
+                .iter()
+                .any(|section| section.path == path && section.line == line)
+        })?;
+        let others = cluster
+            .sections
+            .iter()
+            .filter(|section| !(section.path == path && section.line == line))
+            .map(|section| format!("{}:{}", section.path.display(), section.line))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(format!(
+            "Section '{}' is {:.0}% similar to {}",
+            cluster.heading,
+            cluster.similarity * 100.0,
+            others
+        ))
+    }
+}
+
+/// Sections sharing a heading whose bodies are near-identical.
+#[derive(Clone, Debug)]
+pub struct DuplicateCluster {
+    /// Normalized heading shared by every member.
+    pub heading: String,
+    /// Lowest estimated similarity among the pairs that formed the cluster.
+    pub similarity: f64,
+    pub sections: Vec<DuplicateSection>,
+}
+
+/// A member of a duplicate cluster.
+#[derive(Clone, Debug)]
+pub struct DuplicateSection {
+    pub path: PathBuf,
+    pub anchor: String,
+    /// 1-based line of the heading.
+    pub line: usize,
+    /// Byte range of the whole section (`DocumentSection::byte_range`).
+    pub byte_range: Range<usize>,
+}
+
+/// Parsed document handed to [`analyze`].
+pub(crate) struct ParsedDocument {
+    pub path: PathBuf,
+    pub contents: String,
+    pub sections: Vec<DocumentSection>,
+}
+
+struct Candidate {
+    section: DuplicateSection,
+    signature: Vec<u64>,
+}
+
+/// Find clusters of same-named sections with similar bodies in different documents.
+pub(crate) fn analyze(
+    documents: &[ParsedDocument],
+    settings: &DuplicateSettings,
+) -> DuplicateReport {
+    let mut groups: BTreeMap<String, Vec<Candidate>> = BTreeMap::new();
+    for document in documents {
+        let offsets = compute_line_offsets(&document.contents);
+        for section in &document.sections {
+            let start = section.heading.byte_range.end.min(section.byte_range.end);
+            let body = document
+                .contents
+                .get(start..section.byte_range.end)
+                .unwrap_or("");
+            let words = words(body);
+            if words.len() < settings.min_words.max(1) {
+                continue;
+            }
+            groups
+                .entry(section.heading.normalized.trim().to_lowercase())
+                .or_default()
+                .push(Candidate {
+                    section: DuplicateSection {
+                        path: document.path.clone(),
+                        anchor: section.heading.anchor.clone(),
+                        line: byte_to_line(section.heading.byte_range.start, &offsets),
+                        byte_range: section.byte_range.clone(),
+                    },
+                    signature: signature(&words),
+                });
+        }
+    }
+
+    let mut clusters = Vec::new();
+    for (heading, candidates) in groups {
+        if candidates.len() < 2 {
+            continue;
+        }
+        let mut sets = DisjointSet::new(candidates.len());
+        let mut edges = Vec::new();
+        for i in 0..candidates.len() {
+            for j in (i + 1)..candidates.len() {
+                if candidates[i].section.path == candidates[j].section.path {
+                    continue;
+                }
+                let similarity = estimate(&candidates[i].signature, &candidates[j].signature);
+                if similarity >= settings.threshold {
+                    sets.union(i, j);
+                    edges.push((i, similarity));
+                }
+            }
+        }
+
+        let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for index in 0..candidates.len() {
+            members.entry(sets.find(index)).or_default().push(index);
+        }
+        for (root, indices) in members {
+            if indices.len() < 2 {
+                continue;
+            }
+            let similarity = edges
+                .iter()
+                .filter(|(index, _)| sets.find(*index) == root)
+                .map(|(_, similarity)| *similarity)
+                .fold(1.0, f64::min);
+            let mut sections: Vec<DuplicateSection> = indices
+                .iter()
+                .map(|index| candidates[*index].section.clone())
+                .collect();
+            sections.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+            clusters.push(DuplicateCluster {
+                heading: heading.clone(),
+                similarity,
+                sections,
+            });
+        }
+    }
+
+    clusters.sort_by(|a, b| {
+        (&a.sections[0].path, a.sections[0].line).cmp(&(&b.sections[0].path, b.sections[0].line))
+    });
+    DuplicateReport { clusters }
+}
+
+fn words(body: &str) -> Vec<String> {
+    body.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// MinHash signature over the body's word shingles.
+fn signature(words: &[String]) -> Vec<u64> {
+    let width = SHINGLE_SIZE.min(words.len());
+    let shingles: HashSet<u64> = words
+        .windows(width)
+        .map(|window| fnv1a(window.join(" ").as_bytes()))
+        .collect();
+
+    (0..SIGNATURE_SIZE as u64)
+        .map(|seed| {
+            shingles
+                .iter()
+                .map(|shingle| mix(shingle ^ mix(seed)))
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+/// Fraction of matching signature slots, an estimate of Jaccard similarity.
+fn estimate(a: &[u64], b: &[u64]) -> f64 {
+    let matching = a.iter().zip(b).filter(|(x, y)| x == y).count();
+    matching as f64 / SIGNATURE_SIZE as f64
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// SplitMix64 finalizer, used to derive independent hash functions from one seed.
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+struct DisjointSet {
+    parent: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(size: usize) -> Self {
+        DisjointSet {
+            parent: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, index: usize) -> usize {
+        let parent = self.parent[index];
+        if parent == index {
+            return index;
+        }
+        let root = self.find(parent);
+        self.parent[index] = root;
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        self.parent[root_b] = root_a;
+    }
+}
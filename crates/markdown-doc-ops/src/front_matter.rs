@@ -0,0 +1,57 @@
+//! Detection of a leading YAML front matter block, shared by every command that skips it.
+
+/// A YAML front matter block at the very start of a document.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct FrontMatter<'a> {
+    /// Text between the opening and closing delimiter lines.
+    pub body: &'a str,
+    /// Byte offset just past the closing delimiter line.
+    pub end: usize,
+    /// 1-based line number of the closing delimiter.
+    pub end_line: usize,
+}
+
+/// Locate front matter: an opening `---` line, a closing `---` or `...` line, and at
+/// least one top-level `key:` line in between. Anything else, such as a document that
+/// opens with a thematic break, is ordinary Markdown.
+pub(crate) fn parse(contents: &str) -> Option<FrontMatter<'_>> {
+    let mut lines = contents.split_inclusive('\n');
+    if lines.next().map(str::trim_end) != Some("---") {
+        return None;
+    }
+    let body_start = contents.find('\n')? + 1;
+    let mut offset = body_start;
+    let mut has_field = false;
+    for (idx, line) in lines.enumerate() {
+        let next = offset + line.len();
+        if matches!(line.trim_end(), "---" | "...") {
+            return has_field.then(|| FrontMatter {
+                body: &contents[body_start..offset],
+                end: next,
+                end_line: idx + 2,
+            });
+        }
+        has_field |= field(line).is_some();
+        offset = next;
+    }
+    None
+}
+
+/// Offset just past a leading YAML front matter block, or 0.
+pub(crate) fn front_matter_end(contents: &str) -> usize {
+    parse(contents).map_or(0, |front_matter| front_matter.end)
+}
+
+/// Split a top-level `key: value` line. Indented lines, block list items, and comments
+/// are not fields; the value may be empty (a nested mapping or list follows).
+pub(crate) fn field(line: &str) -> Option<(&str, &str)> {
+    if line.starts_with(char::is_whitespace) || line.starts_with(['-', '#']) {
+        return None;
+    }
+    let (key, value) = line.split_once(':')?;
+    let key = key.trim();
+    if key.is_empty() || !(value.is_empty() || value.starts_with(char::is_whitespace)) {
+        return None;
+    }
+    Some((key, value.trim()))
+}
@@ -0,0 +1,327 @@
+//! Git plumbing behind `--staged`, `--since`, `--changed-from`, and `mv --git`.
+//!
+//! Paths are exchanged relative to the project root (`git -C <root> ... --relative`),
+//! which need not be the repository top level.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use crate::paths::is_markdown_path;
+use crate::OperationError;
+
+/// Markdown files whose staged version differs from `HEAD`. Deleted files are skipped
+/// and renamed files are reported under their new name.
+pub(crate) fn staged_files(root: &Path) -> Result<Vec<PathBuf>, OperationError> {
+    diff_names(root, &["--cached"])
+}
+
+/// Markdown files that differ between `rev` and the working tree, or, with `merge_base`,
+/// files changed on the current branch since it diverged from `rev` (`rev...HEAD`).
+pub(crate) fn changed_files(
+    root: &Path,
+    rev: &str,
+    merge_base: bool,
+) -> Result<Vec<PathBuf>, OperationError> {
+    // git would parse these as options (`--output=...`), not revisions.
+    if rev.starts_with('-') {
+        return Err(OperationError::InvalidInput(format!(
+            "invalid revision '{rev}': revisions cannot start with '-'"
+        )));
+    }
+    let range = if merge_base {
+        format!("{rev}...HEAD")
+    } else {
+        rev.to_string()
+    };
+    diff_names(root, &[range.as_str(), "--"])
+}
+
+/// Commit time (RFC 3339) of the last commit touching each Markdown file under `root`,
+/// from a single `git log` walk. Renames are not followed.
+pub(crate) fn last_commit_times(root: &Path) -> Result<HashMap<PathBuf, String>, OperationError> {
+    let output = run(
+        root,
+        &[
+            "-c",
+            "core.quotePath=false",
+            "log",
+            "--format=%x01%cI",
+            "--name-only",
+            "--relative",
+        ],
+        None,
+    )?;
+    let mut times = HashMap::new();
+    let mut current = "";
+    let text = String::from_utf8_lossy(&output);
+    for line in text.lines() {
+        if let Some(time) = line.strip_prefix('\u{1}') {
+            current = time;
+        } else if !line.is_empty() && is_markdown_path(line) {
+            times
+                .entry(PathBuf::from(line))
+                .or_insert_with(|| current.to_string());
+        }
+    }
+    Ok(times)
+}
+
+/// Whether `root` lies inside a git work tree (false when git is unavailable).
+pub(crate) fn is_work_tree(root: &Path) -> bool {
+    run(root, &["rev-parse", "--is-inside-work-tree"], None)
+        .is_ok_and(|output| output.starts_with(b"true"))
+}
+
+/// Those of `paths` with uncommitted changes: modified or staged relative to `HEAD`,
+/// or untracked.
+pub(crate) fn dirty_files(root: &Path, paths: &[PathBuf]) -> Result<Vec<PathBuf>, OperationError> {
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut dirty = Vec::new();
+    for args in [
+        &["diff", "--name-only", "-z", "--relative"][..],
+        &["diff", "--cached", "--name-only", "-z", "--relative"][..],
+        &["ls-files", "-z", "--others", "--exclude-standard"][..],
+    ] {
+        dirty.extend(path_list(root, args, paths)?);
+    }
+    dirty.sort();
+    dirty.dedup();
+    Ok(dirty)
+}
+
+/// Those of `paths` tracked in the git index.
+pub(crate) fn tracked_files(
+    root: &Path,
+    paths: &[PathBuf],
+) -> Result<Vec<PathBuf>, OperationError> {
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+    path_list(root, &["ls-files", "-z"], paths)
+}
+
+/// `git mv`, so the index records the rename and history follows the file.
+pub(crate) fn move_file(root: &Path, from: &Path, to: &Path) -> Result<(), OperationError> {
+    let (from, to) = (slash_path(from), slash_path(to));
+    run(root, &["mv", "-f", "--", &from, &to], None).map(|_| ())
+}
+
+/// Restore `path` in both the index and the working tree from `HEAD`.
+pub(crate) fn restore_from_head(root: &Path, path: &Path) -> Result<(), OperationError> {
+    let path = slash_path(path);
+    run(
+        root,
+        &["--literal-pathspecs", "checkout", "HEAD", "--", &path],
+        None,
+    )
+    .map(|_| ())
+}
+
+/// Stage the current contents of `paths`.
+pub(crate) fn stage(root: &Path, paths: &[PathBuf]) -> Result<(), OperationError> {
+    if paths.is_empty() {
+        return Ok(());
+    }
+    let mut args = vec!["add".to_string(), "--".to_string()];
+    args.extend(paths.iter().map(|path| slash_path(path)));
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    run(root, &args, None).map(|_| ())
+}
+
+/// Run a NUL-separated listing command restricted to `paths` (taken literally).
+fn path_list(
+    root: &Path,
+    command: &[&str],
+    paths: &[PathBuf],
+) -> Result<Vec<PathBuf>, OperationError> {
+    let pathspecs: Vec<String> = paths.iter().map(|path| slash_path(path)).collect();
+    let mut args = vec!["--literal-pathspecs"];
+    args.extend_from_slice(command);
+    args.push("--");
+    args.extend(pathspecs.iter().map(String::as_str));
+    let output = run(root, &args, None)?;
+    Ok(output
+        .split(|byte| *byte == 0)
+        .filter_map(|name| std::str::from_utf8(name).ok())
+        .filter(|name| !name.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+fn diff_names(root: &Path, extra: &[&str]) -> Result<Vec<PathBuf>, OperationError> {
+    let mut args = vec![
+        "diff",
+        "--name-only",
+        "-z",
+        "--relative",
+        "--find-renames",
+        "--diff-filter=d",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(root, &args, None)?;
+    Ok(output
+        .split(|byte| *byte == 0)
+        .filter_map(|name| std::str::from_utf8(name).ok())
+        .filter(|name| !name.is_empty() && is_markdown_path(name))
+        .map(PathBuf::from)
+        .collect())
+}
+
+fn run(root: &Path, args: &[&str], stdin: Option<&[u8]>) -> Result<Vec<u8>, OperationError> {
+    let description = format!("git {}", args.join(" "));
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| OperationError::Git {
+            source: err,
+            message: "failed to invoke git".into(),
+        })?;
+
+    // Feed stdin from a separate thread so a large request cannot deadlock against a
+    // full stdout pipe.
+    let writer = match (stdin, child.stdin.take()) {
+        (Some(input), Some(mut pipe)) => {
+            let input = input.to_vec();
+            Some(std::thread::spawn(move || pipe.write_all(&input)))
+        }
+        _ => None,
+    };
+    let output = child.wait_with_output();
+    let written = writer.map_or(Ok(()), |handle| {
+        handle
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("stdin writer panicked")))
+    });
+    let output = output
+        .and_then(|output| written.map(|_| output))
+        .map_err(|err| OperationError::Git {
+            source: err,
+            message: format!("{description} failed"),
+        })?;
+    if !output.status.success() {
+        return Err(OperationError::Git {
+            source: io::Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+            message: format!("{description} failed"),
+        });
+    }
+    Ok(output.stdout)
+}
+
+/// File contents as recorded in the git index, fetched with `git cat-file --batch`.
+///
+/// Paths absent from the index read as `NotFound`, mirroring a missing file on disk.
+pub(crate) struct IndexSnapshot {
+    root: PathBuf,
+    blobs: Mutex<HashMap<PathBuf, Option<String>>>,
+}
+
+impl IndexSnapshot {
+    /// Prefetch the staged contents of `paths` in a single git invocation.
+    pub(crate) fn load(root: &Path, paths: &[PathBuf]) -> Result<Self, OperationError> {
+        let snapshot = IndexSnapshot {
+            root: root.to_path_buf(),
+            blobs: Mutex::default(),
+        };
+        let blobs = snapshot.fetch(paths)?;
+        *snapshot.blobs.lock().unwrap() = blobs;
+        Ok(snapshot)
+    }
+
+    pub(crate) fn read(&self, relative: &Path) -> io::Result<String> {
+        let cached = self.blobs.lock().unwrap().get(relative).cloned();
+        let blob = match cached {
+            Some(blob) => blob,
+            None => {
+                let fetched = self
+                    .fetch(&[relative.to_path_buf()])
+                    .map_err(|err| io::Error::other(err.to_string()))?;
+                let blob = fetched.get(relative).cloned().flatten();
+                self.blobs
+                    .lock()
+                    .unwrap()
+                    .insert(relative.to_path_buf(), blob.clone());
+                blob
+            }
+        };
+        blob.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not in the git index", relative.display()),
+            )
+        })
+    }
+
+    pub(crate) fn exists(&self, relative: &Path) -> bool {
+        self.read(relative).is_ok()
+    }
+
+    fn fetch(&self, paths: &[PathBuf]) -> Result<HashMap<PathBuf, Option<String>>, OperationError> {
+        let mut blobs = HashMap::new();
+        if paths.is_empty() {
+            return Ok(blobs);
+        }
+
+        // `:./path` resolves against the working directory (`-C root`), not the top level.
+        let mut request = Vec::new();
+        for path in paths {
+            request.extend_from_slice(format!(":./{}\n", slash_path(path)).as_bytes());
+        }
+        let output = run(&self.root, &["cat-file", "--batch"], Some(&request))?;
+
+        let mut cursor = 0;
+        for path in paths {
+            let header_end = output[cursor..]
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .map(|offset| cursor + offset)
+                .ok_or_else(truncated_batch)?;
+            let header = String::from_utf8_lossy(&output[cursor..header_end]).to_string();
+            cursor = header_end + 1;
+
+            let mut fields = header.split(' ');
+            let (kind, size) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(_), Some(kind), Some(size)) => (kind, size.parse::<usize>().ok()),
+                _ => ("missing", None),
+            };
+            let Some(size) = size else {
+                blobs.insert(path.clone(), None);
+                continue;
+            };
+            let contents = output
+                .get(cursor..cursor + size)
+                .ok_or_else(truncated_batch)?;
+            let blob = (kind == "blob").then(|| String::from_utf8_lossy(contents).into_owned());
+            blobs.insert(path.clone(), blob);
+            cursor += size + 1;
+        }
+        Ok(blobs)
+    }
+}
+
+fn truncated_batch() -> OperationError {
+    OperationError::Git {
+        source: io::Error::other("unexpected end of output"),
+        message: "git cat-file --batch failed".into(),
+    }
+}
+
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
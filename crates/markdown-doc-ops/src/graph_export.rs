@@ -0,0 +1,470 @@
+//! Link graph export for `markdown-doc graph --format dot|mermaid|graphml|json`.
+//!
+//! Nodes are documents (plus, optionally, their anchors and any non-document link
+//! targets); edges aggregate links (inline and reference-style), images, and unused
+//! reference definitions between them.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::anchors::normalize_anchor_fragment;
+use crate::refactor::graph::{LinkGraph, LinkKind, LinkTarget};
+use crate::{OperationError, ScanOptions};
+
+/// Options for exporting the link graph.
+#[derive(Default)]
+pub struct GraphExportOptions {
+    pub scan: ScanOptions,
+    /// Add a node per anchor; links whose fragment names a known anchor point at it.
+    pub anchors: bool,
+    /// Keep only documents matching one of these globs (all documents when empty).
+    pub include: Vec<String>,
+    /// Keep only documents reachable from this one by following outbound links.
+    pub root: Option<PathBuf>,
+    /// Maximum number of link hops from `root` (unlimited when `None`).
+    pub depth: Option<usize>,
+}
+
+/// Exported nodes and edges, sorted by id.
+#[derive(Clone, Debug, Default)]
+pub struct GraphExport {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GraphNode {
+    /// `path` for files, `path#anchor` for anchors.
+    pub id: String,
+    pub kind: GraphNodeKind,
+    pub path: PathBuf,
+    pub anchor: Option<String>,
+    /// Heading text for anchors, the path otherwise.
+    pub label: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GraphNodeKind {
+    /// A scanned Markdown document.
+    Document,
+    /// A heading or HTML anchor inside a document.
+    Anchor,
+    /// A link target outside the scanned documents (asset, ignored or missing file).
+    File,
+}
+
+impl GraphNodeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GraphNodeKind::Document => "document",
+            GraphNodeKind::Anchor => "anchor",
+            GraphNodeKind::File => "file",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub kind: GraphEdgeKind,
+    /// Number of links folded into this edge.
+    pub count: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GraphEdgeKind {
+    /// Inline or reference-style link.
+    Link,
+    Image,
+    /// Reference definition that no link in its document uses.
+    Definition,
+    /// Document to one of its anchors.
+    Contains,
+}
+
+impl GraphEdgeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GraphEdgeKind::Link => "link",
+            GraphEdgeKind::Image => "image",
+            GraphEdgeKind::Definition => "definition",
+            GraphEdgeKind::Contains => "contains",
+        }
+    }
+}
+
+/// Build the export from `graph`, applying the include globs and root/depth filter.
+pub(crate) fn export(
+    graph: &LinkGraph,
+    options: &GraphExportOptions,
+    root: Option<&Path>,
+) -> Result<GraphExport, OperationError> {
+    let include = build_include_set(&options.include)?;
+    let mut documents: BTreeSet<PathBuf> = graph
+        .files()
+        .map(|entry| entry.path().to_path_buf())
+        .filter(|path| match &include {
+            Some(set) => set.is_match(path),
+            None => true,
+        })
+        .collect();
+
+    if let Some(root) = root {
+        if !documents.contains(root) {
+            return Err(OperationError::InvalidInput(format!(
+                "graph root '{}' is not among the exported documents",
+                root.display()
+            )));
+        }
+        documents = reachable(graph, &documents, root, options.depth);
+    }
+
+    let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+    let mut edges: BTreeMap<(String, String, GraphEdgeKind), usize> = BTreeMap::new();
+    for path in &documents {
+        let id = path_id(path);
+        nodes.insert(
+            id.clone(),
+            GraphNode {
+                id: id.clone(),
+                kind: GraphNodeKind::Document,
+                path: path.clone(),
+                anchor: None,
+                label: id.clone(),
+            },
+        );
+        if options.anchors {
+            for anchor in graph.anchors_in(path) {
+                let node = anchor_node(path, &anchor.slug, &anchor.normalized);
+                edges.insert((id.clone(), node.id.clone(), GraphEdgeKind::Contains), 1);
+                nodes.insert(node.id.clone(), node);
+            }
+        }
+    }
+
+    for path in &documents {
+        let Some(entry) = graph.file(path) else {
+            continue;
+        };
+        let links = entry.links().iter().filter_map(|link| {
+            let kind = match link.kind {
+                LinkKind::Inline | LinkKind::Reference => GraphEdgeKind::Link,
+                LinkKind::Image => GraphEdgeKind::Image,
+            };
+            Some((kind, link.target.as_ref()?))
+        });
+        // Definitions already reached through a reference-style link would count twice.
+        let used: HashSet<&LinkTarget> = entry
+            .links()
+            .iter()
+            .filter(|link| matches!(link.kind, LinkKind::Reference))
+            .filter_map(|link| link.target.as_ref())
+            .collect();
+        let definitions = entry
+            .definitions()
+            .iter()
+            .filter_map(|definition| definition.target.as_ref())
+            .filter(|target| !used.contains(target))
+            .map(|target| (GraphEdgeKind::Definition, target));
+        for (kind, target) in links.chain(definitions) {
+            let Some(target_id) = target_node(graph, &documents, &mut nodes, target, options)
+            else {
+                continue;
+            };
+            let source_id = path_id(path);
+            if source_id == target_id {
+                continue;
+            }
+            *edges.entry((source_id, target_id, kind)).or_default() += 1;
+        }
+    }
+
+    Ok(GraphExport {
+        nodes: nodes.into_values().collect(),
+        edges: edges
+            .into_iter()
+            .map(|((source, target, kind), count)| GraphEdge {
+                source,
+                target,
+                kind,
+                count,
+            })
+            .collect(),
+    })
+}
+
+/// Node id for `target`, adding a `File` node for targets outside the scanned
+/// documents. Documents filtered out of the export yield `None`.
+fn target_node(
+    graph: &LinkGraph,
+    documents: &BTreeSet<PathBuf>,
+    nodes: &mut BTreeMap<String, GraphNode>,
+    target: &LinkTarget,
+    options: &GraphExportOptions,
+) -> Option<String> {
+    let path = target.path.as_ref()?;
+    if graph.file(path).is_none() {
+        let id = path_id(path);
+        nodes.entry(id.clone()).or_insert_with(|| GraphNode {
+            id: id.clone(),
+            kind: GraphNodeKind::File,
+            path: path.clone(),
+            anchor: None,
+            label: id.clone(),
+        });
+        return Some(id);
+    }
+    if !documents.contains(path) {
+        return None;
+    }
+    if options.anchors {
+        if let Some(anchor) = target.anchor.as_deref().filter(|anchor| !anchor.is_empty()) {
+            let known = graph
+                .anchors_in(path)
+                .into_iter()
+                .find(|record| normalize_anchor_fragment(&record.slug) == anchor);
+            if let Some(record) = known {
+                return Some(anchor_node(path, &record.slug, &record.normalized).id);
+            }
+        }
+    }
+    Some(path_id(path))
+}
+
+/// Documents within `depth` outbound hops of `root`, restricted to `documents`.
+fn reachable(
+    graph: &LinkGraph,
+    documents: &BTreeSet<PathBuf>,
+    root: &Path,
+    depth: Option<usize>,
+) -> BTreeSet<PathBuf> {
+    let mut seen: HashSet<PathBuf> = HashSet::from([root.to_path_buf()]);
+    let mut queue = VecDeque::from([(root.to_path_buf(), 0usize)]);
+    while let Some((path, distance)) = queue.pop_front() {
+        if depth.is_some_and(|limit| distance >= limit) {
+            continue;
+        }
+        let Some(entry) = graph.file(&path) else {
+            continue;
+        };
+        let targets = entry
+            .links()
+            .iter()
+            .filter_map(|link| link.target.as_ref())
+            .chain(
+                entry
+                    .definitions()
+                    .iter()
+                    .filter_map(|definition| definition.target.as_ref()),
+            )
+            .filter_map(|target| target.path.clone());
+        for target in targets {
+            if documents.contains(&target) && seen.insert(target.clone()) {
+                queue.push_back((target, distance + 1));
+            }
+        }
+    }
+    documents
+        .iter()
+        .filter(|path| seen.contains(*path))
+        .cloned()
+        .collect()
+}
+
+fn build_include_set(patterns: &[String]) -> Result<Option<GlobSet>, OperationError> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|err| {
+            OperationError::InvalidInput(format!("invalid glob '{pattern}': {err}"))
+        })?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|err| OperationError::InvalidInput(err.to_string()))
+}
+
+fn anchor_node(path: &Path, slug: &str, heading: &str) -> GraphNode {
+    GraphNode {
+        id: format!("{}#{slug}", path_id(path)),
+        kind: GraphNodeKind::Anchor,
+        path: path.to_path_buf(),
+        anchor: Some(slug.to_string()),
+        label: if heading.is_empty() {
+            format!("#{slug}")
+        } else {
+            heading.to_string()
+        },
+    }
+}
+
+fn path_id(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+impl GraphExport {
+    /// Graphviz DOT: documents are boxes, anchors ellipses, other files notes.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph docs {\n  rankdir=LR;\n  node [shape=box];\n");
+        for node in &self.nodes {
+            let shape = match node.kind {
+                GraphNodeKind::Document => "",
+                GraphNodeKind::Anchor => ", shape=ellipse",
+                GraphNodeKind::File => ", shape=note, style=dashed",
+            };
+            let _ = writeln!(
+                out,
+                "  \"{}\" [label=\"{}\"{shape}];",
+                dot_escape(&node.id),
+                dot_escape(&node.label)
+            );
+        }
+        for edge in &self.edges {
+            let mut attributes = Vec::new();
+            match edge.kind {
+                GraphEdgeKind::Link => {}
+                GraphEdgeKind::Image => attributes.push("style=dashed".to_string()),
+                GraphEdgeKind::Definition => attributes.push("style=dotted".to_string()),
+                GraphEdgeKind::Contains => attributes.push("arrowhead=none".to_string()),
+            }
+            if edge.count > 1 {
+                attributes.push(format!("weight={0}, label=\"{0}\"", edge.count));
+            }
+            let attributes = if attributes.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attributes.join(", "))
+            };
+            let _ = writeln!(
+                out,
+                "  \"{}\" -> \"{}\"{attributes};",
+                dot_escape(&edge.source),
+                dot_escape(&edge.target)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Mermaid flowchart; node ids are positional (`n0`, `n1`, ...) since paths are
+    /// not valid Mermaid identifiers.
+    pub fn to_mermaid(&self) -> String {
+        let ids: BTreeMap<&str, String> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| (node.id.as_str(), format!("n{idx}")))
+            .collect();
+        let mut out = String::from("flowchart LR\n");
+        for node in &self.nodes {
+            let label = mermaid_escape(&node.label);
+            let shape = match node.kind {
+                GraphNodeKind::Document => format!("[\"{label}\"]"),
+                GraphNodeKind::Anchor => format!("([\"{label}\"])"),
+                GraphNodeKind::File => format!("[/\"{label}\"/]"),
+            };
+            let _ = writeln!(out, "  {}{shape}", ids[node.id.as_str()]);
+        }
+        for edge in &self.edges {
+            let (Some(source), Some(target)) =
+                (ids.get(edge.source.as_str()), ids.get(edge.target.as_str()))
+            else {
+                continue;
+            };
+            let arrow = match edge.kind {
+                GraphEdgeKind::Link => "-->",
+                GraphEdgeKind::Image => "-.->",
+                GraphEdgeKind::Definition => "-->|ref|",
+                GraphEdgeKind::Contains => "---",
+            };
+            let _ = writeln!(out, "  {source} {arrow} {target}");
+        }
+        out
+    }
+
+    /// GraphML with `kind`, `path`, `anchor`, and `label` node data and `kind` and
+    /// `count` edge data.
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"path\" for=\"node\" attr.name=\"path\" attr.type=\"string\"/>\n",
+            "  <key id=\"anchor\" for=\"node\" attr.name=\"anchor\" attr.type=\"string\"/>\n",
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"edge_kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"count\" for=\"edge\" attr.name=\"count\" attr.type=\"int\"/>\n",
+            "  <graph id=\"docs\" edgedefault=\"directed\">\n",
+        ));
+        for node in &self.nodes {
+            let _ = writeln!(out, "    <node id=\"{}\">", xml_escape(&node.id));
+            let _ = writeln!(
+                out,
+                "      <data key=\"kind\">{}</data>",
+                node.kind.as_str()
+            );
+            let _ = writeln!(
+                out,
+                "      <data key=\"path\">{}</data>",
+                xml_escape(&path_id(&node.path))
+            );
+            if let Some(anchor) = &node.anchor {
+                let _ = writeln!(
+                    out,
+                    "      <data key=\"anchor\">{}</data>",
+                    xml_escape(anchor)
+                );
+            }
+            let _ = writeln!(
+                out,
+                "      <data key=\"label\">{}</data>",
+                xml_escape(&node.label)
+            );
+            out.push_str("    </node>\n");
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "    <edge source=\"{}\" target=\"{}\">",
+                xml_escape(&edge.source),
+                xml_escape(&edge.target)
+            );
+            let _ = writeln!(
+                out,
+                "      <data key=\"edge_kind\">{}</data>",
+                edge.kind.as_str()
+            );
+            let _ = writeln!(out, "      <data key=\"count\">{}</data>", edge.count);
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+}
+
+fn dot_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
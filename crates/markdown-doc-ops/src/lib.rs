@@ -1,39 +1,73 @@
 //! High-level operations shared by markdown-doc commands.
 
 mod anchors;
+mod baseline;
+mod catalog;
+mod dupes;
+mod front_matter;
+mod git;
+mod graph_export;
 mod lines;
 mod lint;
+mod move_map;
+mod orphans;
+mod outbound;
 mod paths;
 pub mod refactor;
+mod rename;
 mod schema;
+mod search;
+mod split;
+mod stats;
 mod toc;
+mod watch;
 
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use ignore::gitignore::Gitignore;
-use markdown_doc_config::Config;
+use markdown_doc_config::{
+    CatalogField, CatalogTimestamp, Config, LintRule, SeverityLevel, TocInsertPosition,
+};
 use markdown_doc_format::{
-    CatalogEntry, CatalogFormat, CatalogRenderData, HeadingSummary, LintFormat, LintRenderData,
-    Renderer, ValidateFinding, ValidateFormat, ValidateRenderData,
+    CatalogEntry, CatalogFormat, CatalogRenderData, LintFinding, LintFormat, LintRenderData,
+    Renderer, TemplateError, ValidateFinding, ValidateFormat, ValidateRenderData,
 };
-use markdown_doc_parser::{DocumentSection, ParserContext};
+use markdown_doc_parser::ParserContext;
 use markdown_doc_utils::atomic_write;
 use rayon::prelude::*;
 use similar::TextDiff;
 use thiserror::Error;
 use walkdir::WalkDir;
 
+pub use crate::baseline::DEFAULT_BASELINE_FILE;
+pub use crate::dupes::{DuplicateCluster, DuplicateReport, DuplicateSection};
+pub use crate::graph_export::{
+    GraphEdge, GraphEdgeKind, GraphExport, GraphExportOptions, GraphNode, GraphNodeKind,
+};
+pub use crate::lint::{CACHE_DIR, DEFAULT_WORDS_FILE};
+pub use crate::move_map::load_move_map;
+pub use crate::orphans::OrphanReport;
+pub use crate::outbound::{
+    OutboundGroup, OutboundLink, OutboundLinkKind, OutboundOptions, OutboundReport, OutboundStatus,
+};
+pub use crate::rename::RenameHeadingOptions;
+pub use crate::search::{SearchIndexStats, SearchResult, SnippetLine};
+pub use crate::split::{MergeOptions, SplitOptions};
+pub use crate::stats::{FileStats, LinkStats, SectionStats, StatsReport, StatsSummary};
+pub use crate::watch::{WatchOptions, WatchReport, WatchSession};
+
 use crate::anchors::normalize_anchor_fragment;
 use crate::paths::normalize_path;
 use crate::refactor::graph::LinkGraph;
 use crate::refactor::rewrite::{plan_file_moves, FileMove, RewriteError};
 use crate::schema::SchemaEngine;
+use crate::search::SearchIndex;
 
 /// Primary entry point for catalog and lint operations.
 pub struct Operations {
@@ -62,47 +96,158 @@ impl Operations {
 
     /// Generate documentation catalog data and optionally write the Markdown output to disk.
     pub fn catalog(&self, options: CatalogOptions) -> Result<CatalogOutcome, OperationError> {
-        let targets = self.collect_targets(&options.scan)?;
+        let root = &self.config.project.root;
+        // Per-directory catalogs only apply to markdown output.
+        let directory_index = self
+            .config
+            .catalog
+            .directory_index
+            .as_deref()
+            .filter(|_| options.format == CatalogFormat::Markdown);
+        let absolute = self.resolve_output_path(
+            options
+                .output_path
+                .as_deref()
+                .unwrap_or(&self.config.catalog.output),
+        );
+        let display_path = absolute
+            .strip_prefix(root)
+            .unwrap_or(&absolute)
+            .to_path_buf();
+        // A catalog never lists itself, otherwise every run would change it.
+        let targets: Vec<PathBuf> = self
+            .collect_targets(&options.scan)?
+            .into_iter()
+            .filter(|path| match directory_index {
+                Some(name) => path.file_name() != Some(OsStr::new(name)),
+                None => *path != display_path,
+            })
+            .collect();
+        let fields = &self.config.catalog.fields;
+        let timestamp = options.timestamp.unwrap_or(self.config.catalog.timestamp);
+        // Outside a git repository every document falls back to its mtime.
+        let commit_times =
+            if fields.contains(&CatalogField::Modified) || timestamp == CatalogTimestamp::Git {
+                git::last_commit_times(root).unwrap_or_default()
+            } else {
+                HashMap::new()
+            };
 
         let entries: Vec<CatalogEntry> = targets
             .par_iter()
             .map(|path| {
-                let sections =
-                    self.parser
-                        .sections_for_path(path)
-                        .map_err(|source| OperationError::Io {
-                            path: path.clone(),
-                            source,
-                        })?;
-                Ok(build_catalog_entry(path, sections))
+                let absolute = root.join(path);
+                let contents =
+                    std::fs::read_to_string(&absolute).map_err(|source| OperationError::Io {
+                        path: path.clone(),
+                        source,
+                    })?;
+                let sections = self.parser.sections_from_str(path, &contents);
+                let last_modified = commit_times
+                    .get(path)
+                    .cloned()
+                    .or_else(|| modified_time(&absolute));
+                Ok(catalog::build_entry(
+                    path,
+                    &contents,
+                    sections,
+                    fields,
+                    last_modified,
+                ))
             })
             .collect::<Result<Vec<_>, OperationError>>()?;
 
         let mut sorted_entries = entries;
         sorted_entries.sort_by(|a, b| a.path.cmp(&b.path));
 
-        let data = CatalogRenderData {
-            generated_at: SystemTime::now(),
-            entries: sorted_entries.clone(),
+        let last_updated = match timestamp {
+            CatalogTimestamp::Now => Some(chrono::Local::now().fixed_offset()),
+            CatalogTimestamp::Git => catalog::latest_commit_time(&commit_times, &targets),
+            CatalogTimestamp::Omit => None,
+        };
+        let template = match &self.config.catalog.template {
+            Some(path) if options.format == CatalogFormat::Markdown => Some(
+                std::fs::read_to_string(path).map_err(|source| OperationError::Io {
+                    path: path.clone(),
+                    source,
+                })?,
+            ),
+            _ => None,
+        };
+        let render = |data: &CatalogRenderData| -> Result<String, OperationError> {
+            Ok(match (options.format, &template) {
+                (CatalogFormat::Markdown, Some(template)) => {
+                    self.renderer.render_catalog_template(template, data)?
+                }
+                (CatalogFormat::Markdown, None) => self.renderer.render_catalog_markdown(data),
+                (CatalogFormat::Json, _) => self.renderer.render_catalog_json(data)?,
+            })
         };
 
-        let rendered = match options.format {
-            CatalogFormat::Markdown => self.renderer.render_catalog_markdown(&data),
-            CatalogFormat::Json => self.renderer.render_catalog_json(&data)?,
+        let pages = match directory_index {
+            None => vec![(
+                display_path,
+                render(&CatalogRenderData {
+                    last_updated,
+                    entries: sorted_entries.clone(),
+                    directory: None,
+                    subdirectories: Vec::new(),
+                })?,
+            )],
+            Some(name) => catalog::directory_pages(&sorted_entries)
+                .into_iter()
+                .map(|page| {
+                    let rendered = render(&CatalogRenderData {
+                        last_updated,
+                        entries: page.entries,
+                        directory: Some(catalog::normalize_relative_path(&page.directory)),
+                        subdirectories: page.subdirectories,
+                    })?;
+                    Ok((page.directory.join(name), rendered))
+                })
+                .collect::<Result<Vec<_>, OperationError>>()?,
         };
 
-        if options.write_to_disk {
-            let output_path = options
-                .output_path
-                .unwrap_or_else(|| self.config.catalog.output.clone());
-            let absolute = self.resolve_output_path(&output_path);
-            atomic_write(&absolute, &rendered)?;
+        let mut status = CatalogStatus::Rendered;
+        let mut reports = Vec::new();
+        for (path, rendered) in pages {
+            let (page_status, report) = self.sync_catalog_page(&options, &path, rendered)?;
+            status = status.max(page_status);
+            reports.push((path, page_status, report));
         }
 
+        let rendered = if directory_index.is_some()
+            && options.mode == CatalogMode::Update
+            && options.write_to_disk
+        {
+            let written: Vec<String> = reports
+                .iter()
+                .filter(|(_, status, _)| *status == CatalogStatus::Written)
+                .map(|(path, _, _)| format!("✏️  wrote {}", path.display()))
+                .collect();
+            if written.is_empty() {
+                format!("✅ {} directory catalogs up to date", reports.len())
+            } else {
+                written.join("\n")
+            }
+        } else {
+            reports
+                .into_iter()
+                .map(|(_, _, report)| report)
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let exit_code = i32::from(matches!(
+            status,
+            CatalogStatus::Stale | CatalogStatus::Missing
+        ));
+
         Ok(CatalogOutcome {
             rendered,
             format: options.format,
             entries: sorted_entries,
+            status,
+            exit_code,
         })
     }
 
@@ -110,28 +255,106 @@ impl Operations {
     pub fn lint(&self, options: LintOptions) -> Result<LintOutcome, OperationError> {
         let targets = self.collect_targets(&options.scan)?;
 
+        // Orphans and duplicates compare against every document in the project, not just
+        // the lint targets.
+        let lint = &self.config.lint;
+        let wants = |rule| lint.rules.contains(&rule) && lint.is_rule_enabled(rule);
+        let check_orphans = wants(LintRule::Orphans);
+        let check_duplicates = wants(LintRule::DuplicateSections);
+        let project = if check_orphans || check_duplicates {
+            self.collect_targets(&ScanOptions {
+                respect_ignore: options.scan.respect_ignore,
+                ..ScanOptions::default()
+            })?
+        } else {
+            Vec::new()
+        };
+
+        // Staged runs check what is about to be committed, not the working tree.
+        let index = if options.scan.staged {
+            let mut paths = [targets.as_slice(), project.as_slice()].concat();
+            paths.sort();
+            paths.dedup();
+            Some(git::IndexSnapshot::load(&self.config.project.root, &paths)?)
+        } else {
+            None
+        };
+
+        let documents = self.read_documents(&project, index.as_ref())?;
+        let orphan_report = check_orphans.then(|| self.orphan_report(&documents));
+        let duplicate_report = check_duplicates.then(|| self.duplicate_report(&documents));
+
         let result = lint::run(lint::LintRunInput {
             config: &self.config,
             parser: &self.parser,
             targets: &targets,
             root: &self.config.project.root,
             schema_engine: &self.schema_engine,
+            orphans: orphan_report.as_ref(),
+            duplicates: duplicate_report.as_ref(),
+            cache: options.cache,
+            index: index.as_ref(),
         })?;
 
+        let root = &self.config.project.root;
+        let mut findings = result.findings;
+        let mut words_added = None;
+        if options.add_words {
+            let (remaining, summary) =
+                lint::add_unknown_words(root, &self.config.lint.spelling, findings)?;
+            findings = remaining;
+            words_added = Some(summary);
+        }
+
+        let mut baseline_summary = None;
+        if options.write_baseline {
+            let path = options
+                .baseline
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(baseline::DEFAULT_BASELINE_FILE));
+            baseline_summary = Some(baseline::write(
+                root,
+                &path,
+                &findings,
+                &self.config.lint,
+                index.as_ref(),
+            )?);
+        } else if let Some(path) = &options.baseline {
+            let (fresh, summary) = baseline::apply(
+                root,
+                path,
+                findings,
+                &targets,
+                &self.config.lint,
+                index.as_ref(),
+            )?;
+            findings = fresh;
+            baseline_summary = Some(summary);
+        }
+
+        let (error_count, warning_count) = count_severities(&findings);
         let report = LintRenderData {
             files_scanned: result.files_scanned,
-            error_count: result.error_count,
-            warning_count: result.warning_count,
-            findings: result.findings.clone(),
+            error_count,
+            warning_count,
+            findings,
+            baseline: baseline_summary,
+            words_added,
         };
 
         let rendered = self.render_lint(&report, options.format)?;
-        let exit_code = if result.error_count > 0 { 1 } else { 0 };
+        // Recording a baseline accepts every current finding.
+        let exit_code = if error_count > 0 && !options.write_baseline {
+            1
+        } else {
+            0
+        };
 
         Ok(LintOutcome {
             rendered,
             report,
             exit_code,
+            files_cached: result.files_cached,
         })
     }
 
@@ -228,30 +451,75 @@ impl Operations {
                 })?;
 
             let sections = self.parser.sections_from_str(&path, &contents);
-            let block = match toc::locate_block(&contents, &self.config.lint.toc) {
-                Some(block) => block,
-                None => {
-                    encountered_error = true;
+            let line_sep = if contents.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            };
+            let blocks = toc::locate_blocks(&contents, &self.config.lint.toc);
+            if blocks.is_empty() {
+                if options.mode == TocMode::Insert {
+                    let position = options.insert_at.unwrap_or(self.config.lint.toc.insert_at);
+                    let inserted = toc::insert_block(
+                        &contents,
+                        &sections,
+                        &self.config.lint.toc,
+                        position,
+                        line_sep,
+                    );
+                    let sections = self.parser.sections_from_str(&path, &inserted);
+                    let blocks = toc::locate_blocks(&inserted, &self.config.lint.toc);
+                    let updated = toc::render_blocks(&inserted, &sections, &blocks, line_sep);
+                    atomic_write(&absolute, &updated)?;
                     changes.push(TocChange {
                         path: path.clone(),
-                        status: TocStatus::MissingMarkers,
+                        status: TocStatus::Inserted,
                         diff: None,
                     });
-                    messages.push(format!("❌ {} missing TOC markers", path.display()));
+                    messages.push(format!("✏️  inserted TOC into {}", path.display()));
                     continue;
                 }
-            };
+                if self.config.lint.toc.skip_missing {
+                    changes.push(TocChange {
+                        path: path.clone(),
+                        status: TocStatus::Skipped,
+                        diff: None,
+                    });
+                    continue;
+                }
+                encountered_error = true;
+                changes.push(TocChange {
+                    path: path.clone(),
+                    status: TocStatus::MissingMarkers,
+                    diff: None,
+                });
+                messages.push(format!("❌ {} missing TOC markers", path.display()));
+                continue;
+            }
 
-            let generated_items = toc::generate_items(&sections);
-            let existing_body = contents[block.start_offset..block.end_offset].to_string();
-            let line_sep = if existing_body.contains("\r\n") || contents.contains("\r\n") {
-                "\r\n"
-            } else {
-                "\n"
-            };
-            let rendered_body = toc::render_items_with_separator(&generated_items, line_sep);
+            let invalid: Vec<String> = blocks
+                .iter()
+                .flat_map(|block| {
+                    block
+                        .errors
+                        .iter()
+                        .map(|error| format!("❌ {}:{}: {error}", path.display(), block.start_line))
+                })
+                .collect();
+            if !invalid.is_empty() {
+                encountered_error = true;
+                changes.push(TocChange {
+                    path: path.clone(),
+                    status: TocStatus::InvalidOptions,
+                    diff: None,
+                });
+                messages.extend(invalid);
+                continue;
+            }
 
-            if existing_body == rendered_body {
+            let updated = toc::render_blocks(&contents, &sections, &blocks, line_sep);
+
+            if contents == updated {
                 changes.push(TocChange {
                     path: path.clone(),
                     status: TocStatus::UpToDate,
@@ -272,7 +540,7 @@ impl Operations {
                 }
                 TocMode::Diff => {
                     requires_update = true;
-                    let diff = build_diff(&path, &existing_body, &rendered_body);
+                    let diff = build_diff(&path, &contents, &updated);
                     changes.push(TocChange {
                         path: path.clone(),
                         status: TocStatus::NeedsUpdate,
@@ -280,11 +548,7 @@ impl Operations {
                     });
                     messages.push(diff);
                 }
-                TocMode::Update => {
-                    let mut updated = String::new();
-                    updated.push_str(&contents[..block.start_offset]);
-                    updated.push_str(&rendered_body);
-                    updated.push_str(&contents[block.end_offset..]);
+                TocMode::Update | TocMode::Insert => {
                     atomic_write(&absolute, &updated)?;
                     changes.push(TocChange {
                         path: path.clone(),
@@ -297,7 +561,7 @@ impl Operations {
         }
 
         let exit_code = match options.mode {
-            TocMode::Update => {
+            TocMode::Update | TocMode::Insert => {
                 if encountered_error {
                     1
                 } else {
@@ -332,6 +596,213 @@ impl Operations {
         LinkGraph::build(&self.parser, &self.config.project.root, &targets)
     }
 
+    /// Report documents without inbound links or unreachable from the configured roots.
+    pub fn orphans(&self, options: ScanOptions) -> Result<OrphanReport, OperationError> {
+        let targets = self.collect_targets(&options)?;
+        Ok(self.orphan_report(&self.read_documents(&targets, None)?))
+    }
+
+    fn orphan_report(&self, documents: &[(PathBuf, String)]) -> OrphanReport {
+        let root = &self.config.project.root;
+        let graph = LinkGraph::from_documents(&self.parser, root, documents.to_vec());
+        orphans::analyze(&graph, &self.config.lint.orphans, root)
+    }
+
+    /// Export the link graph as nodes and edges, filtered by glob and by distance
+    /// from a root document.
+    pub fn export_graph(&self, options: GraphExportOptions) -> Result<GraphExport, OperationError> {
+        let root = &self.config.project.root;
+        let start = match &options.root {
+            Some(path) => Some(resolve_input_path(root, path, true, "graph root")?.0),
+            None if options.depth.is_some() => {
+                return Err(OperationError::InvalidInput(
+                    "a depth limit requires a root document".into(),
+                ));
+            }
+            None => None,
+        };
+        let mut targets = self.collect_targets(&options.scan)?;
+        if let Some(start) = &start {
+            targets.push(start.clone());
+            targets.sort();
+            targets.dedup();
+        }
+        let graph = LinkGraph::build(&self.parser, root, &targets)?;
+        graph_export::export(&graph, &options, start.as_deref())
+    }
+
+    /// Find clusters of same-named sections with near-identical bodies across documents.
+    pub fn duplicates(&self, options: ScanOptions) -> Result<DuplicateReport, OperationError> {
+        let targets = self.collect_targets(&options)?;
+        Ok(self.duplicate_report(&self.read_documents(&targets, None)?))
+    }
+
+    fn duplicate_report(&self, documents: &[(PathBuf, String)]) -> DuplicateReport {
+        let documents = documents
+            .par_iter()
+            .map(|(path, contents)| dupes::ParsedDocument {
+                path: path.clone(),
+                contents: contents.clone(),
+                sections: self.parser.sections_from_str(path, contents),
+            })
+            .collect::<Vec<_>>();
+        dupes::analyze(&documents, &self.config.lint.duplicates)
+    }
+
+    /// Read project-relative documents from the working tree, or from the git index when
+    /// `index` is set (documents that are not in the index are skipped).
+    fn read_documents(
+        &self,
+        paths: &[PathBuf],
+        index: Option<&git::IndexSnapshot>,
+    ) -> Result<Vec<(PathBuf, String)>, OperationError> {
+        let root = &self.config.project.root;
+        paths
+            .par_iter()
+            .filter_map(|path| {
+                let contents = match index {
+                    Some(index) => index.read(path),
+                    None => fs::read_to_string(root.join(path)),
+                };
+                match contents {
+                    Ok(contents) => Some(Ok((path.clone(), contents))),
+                    Err(err) if index.is_some() && err.kind() == io::ErrorKind::NotFound => None,
+                    Err(source) => Some(Err(OperationError::Io {
+                        path: root.join(path),
+                        source,
+                    })),
+                }
+            })
+            .collect()
+    }
+
+    /// Collect per-document and aggregate documentation health metrics.
+    pub fn stats(&self, options: StatsOptions) -> Result<StatsReport, OperationError> {
+        let root = &self.config.project.root;
+        let targets = self.collect_targets(&options.scan)?;
+        // Inbound links and orphans consider every document in the project.
+        let documents = self.collect_targets(&ScanOptions {
+            respect_ignore: options.scan.respect_ignore,
+            ..ScanOptions::default()
+        })?;
+        let graph = LinkGraph::build(&self.parser, root, &documents)?;
+        let orphan_report = orphans::analyze(&graph, &self.config.lint.orphans, root);
+
+        let lint = self.lint(LintOptions {
+            scan: ScanOptions {
+                paths: targets.clone(),
+                respect_ignore: options.scan.respect_ignore,
+                ..ScanOptions::default()
+            },
+            format: LintFormat::Json,
+            baseline: None,
+            write_baseline: false,
+            add_words: false,
+            cache: true,
+        })?;
+        let mut findings: HashMap<&Path, usize> = HashMap::new();
+        for finding in &lint.report.findings {
+            *findings.entry(finding.path.as_path()).or_default() += 1;
+        }
+        let commit_times = git::last_commit_times(root).unwrap_or_default();
+
+        let per_file = targets
+            .par_iter()
+            .map(|path| {
+                let absolute = root.join(path);
+                let contents =
+                    std::fs::read_to_string(&absolute).map_err(|source| OperationError::Io {
+                        path: path.clone(),
+                        source,
+                    })?;
+                let sections = self.parser.sections_from_str(path, &contents);
+                let (mut file, sized) =
+                    stats::document_stats(&graph, root, path, &contents, &sections);
+
+                let schema = self.schema_engine.schema_for_path(path);
+                let offsets = lines::compute_line_offsets(&contents);
+                file.schema = schema.name().to_string();
+                file.schema_conforms = self
+                    .schema_engine
+                    .check(schema, &sections, &offsets)
+                    .violations
+                    .is_empty();
+                file.orphan = orphan_report.orphans.contains(path);
+                file.lint_findings = findings.get(path.as_path()).copied().unwrap_or(0);
+                file.last_modified = commit_times
+                    .get(path)
+                    .cloned()
+                    .or_else(|| modified_time(&absolute));
+                Ok((file, sized))
+            })
+            .collect::<Result<Vec<_>, OperationError>>()?;
+
+        let (files, sections): (Vec<_>, Vec<_>) = per_file.into_iter().unzip();
+        Ok(stats::summarize(
+            files,
+            sections.into_iter().flatten().collect(),
+            options.top,
+        ))
+    }
+
+    /// Search document sections, refreshing the persistent index first unless disabled.
+    pub fn search(&self, options: SearchOptions) -> Result<SearchOutcome, OperationError> {
+        let started = Instant::now();
+        let root = &self.config.project.root;
+        let index_path = self.resolve_output_path(
+            options
+                .index_path
+                .as_deref()
+                .unwrap_or(&self.config.search.index),
+        );
+        let mut index = SearchIndex::load(&index_path, &search::index_fingerprint(&self.parser));
+
+        // The index always covers the whole project; `scan` only narrows the results.
+        let mut refreshed = 0;
+        let mut bytes = None;
+        if options.refresh || index.file_count() == 0 {
+            let documents = self.collect_targets(&ScanOptions {
+                respect_ignore: options.scan.respect_ignore,
+                ..ScanOptions::default()
+            })?;
+            let summary = index.refresh(root, &self.parser, &documents)?;
+            refreshed = summary.refreshed;
+            if summary.changed {
+                // Like the lint cache, a failure to persist the index never fails a search.
+                bytes = index.save(&index_path).ok();
+            }
+        }
+
+        let scoped = !options.scan.paths.is_empty()
+            || options.scan.staged
+            || options.scan.changed_since.is_some();
+        let allowed: Option<HashSet<PathBuf>> = if scoped {
+            Some(self.collect_targets(&options.scan)?.into_iter().collect())
+        } else {
+            None
+        };
+        let results = search::rank(&index, &options.query, allowed.as_ref(), options.limit);
+
+        let stats = SearchIndexStats {
+            files: index.file_count(),
+            sections: index.section_count(),
+            refreshed,
+            bytes: bytes
+                .or_else(|| fs::metadata(&index_path).ok().map(|meta| meta.len()))
+                .unwrap_or(0),
+            path: index_path,
+        };
+        let exit_code = if results.is_empty() { 1 } else { 0 };
+
+        Ok(SearchOutcome {
+            query: options.query,
+            results,
+            index: stats,
+            duration: started.elapsed(),
+            exit_code,
+        })
+    }
+
     /// Locate references to a given Markdown path or anchor.
     pub fn refs(&self, options: RefsOptions) -> Result<RefsOutcome, OperationError> {
         let root = &self.config.project.root;
@@ -385,68 +856,76 @@ impl Operations {
         })
     }
 
-    /// Move/rename a Markdown file while updating inbound/outbound references.
-    pub fn mv(&self, options: MvOptions) -> Result<MvOutcome, OperationError> {
+    /// List the links made by a document, or by every document under a directory,
+    /// grouped into resolved, unresolved, and external targets.
+    pub fn outbound(&self, options: OutboundOptions) -> Result<OutboundReport, OperationError> {
         let root = &self.config.project.root;
-        let (source_rel, source_abs) = resolve_input_path(root, &options.source, true, "source")?;
-        if !is_markdown_path(&source_rel.to_string_lossy()) {
+        let (from, absolute) = resolve_input_path(root, &options.from, true, "path")?;
+        let mut targets = self.collect_targets(&options.scan)?;
+        let sources: Vec<PathBuf> = if absolute.is_dir() {
+            targets
+                .iter()
+                .filter(|path| path.starts_with(&from))
+                .cloned()
+                .collect()
+        } else if is_markdown_path(&from.to_string_lossy()) {
+            targets.push(from.clone());
+            targets.sort();
+            targets.dedup();
+            vec![from.clone()]
+        } else {
             return Err(OperationError::InvalidInput(format!(
-                "source '{}' is not a Markdown file",
-                source_rel.display()
+                "'{}' is not a Markdown file or directory",
+                from.display()
             )));
-        }
-        let source_meta = fs::metadata(&source_abs).map_err(|source| OperationError::Io {
-            path: source_abs.clone(),
-            source,
-        })?;
-        if !source_meta.is_file() {
+        };
+        if sources.is_empty() {
             return Err(OperationError::InvalidInput(format!(
-                "source '{}' must be a file",
-                source_rel.display()
+                "no Markdown documents under '{}'",
+                from.display()
             )));
         }
 
-        let (dest_rel, dest_abs) =
-            resolve_input_path(root, &options.destination, false, "destination")?;
-        if !is_markdown_path(&dest_rel.to_string_lossy()) {
-            return Err(OperationError::InvalidInput(format!(
-                "destination '{}' must end with .md or .markdown",
-                dest_rel.display()
-            )));
-        }
-        if source_rel == dest_rel {
-            return Err(OperationError::InvalidInput(
-                "source and destination refer to the same path".into(),
-            ));
-        }
-        if dest_abs.exists() && !options.force {
-            return Err(OperationError::InvalidInput(format!(
-                "destination '{}' already exists (use --force to overwrite)",
-                dest_rel.display()
-            )));
-        }
+        let graph = LinkGraph::build(&self.parser, root, &targets)?;
+        let from = if from.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            from
+        };
+        Ok(outbound::collect(
+            &graph,
+            root,
+            &from,
+            &sources,
+            options.aggregate,
+        ))
+    }
+
+    /// Move/rename Markdown files, directories, or assets while updating
+    /// inbound/outbound references. All moves are applied as one transaction.
+    pub fn mv(&self, options: MvOptions) -> Result<MvOutcome, OperationError> {
+        let root = &self.config.project.root;
+        let moves = self.expand_moves(&options.moves, options.force)?;
 
         let mut targets = self.collect_targets(&options.scan)?;
-        if !targets.contains(&source_rel) {
-            targets.push(source_rel.clone());
-            targets.sort();
-            targets.dedup();
-        }
+        // Moved documents need their outbound links rewritten even when filtered out.
+        targets.extend(
+            moves
+                .iter()
+                .filter(|mv| is_markdown_path(&mv.from.to_string_lossy()))
+                .map(|mv| mv.from.clone()),
+        );
+        targets.sort();
+        targets.dedup();
         let graph = LinkGraph::build(&self.parser, root, &targets)?;
 
-        let plan = plan_file_moves(
-            &graph,
-            root,
-            &[FileMove {
-                from: source_rel.clone(),
-                to: dest_rel.clone(),
-            }],
-        )?;
+        let plan = plan_file_moves(&graph, root, &moves)?;
 
         #[derive(Clone)]
         struct ProcessedEdit {
             edit: crate::refactor::rewrite::FileEdit,
-            original_contents: String,
+            /// `None` for assets, which are relocated without being read.
+            original_contents: Option<String>,
             diff: Option<String>,
             status: MvFileStatus,
         }
@@ -477,11 +956,25 @@ impl Operations {
             };
             processed.push(ProcessedEdit {
                 edit: edit.clone(),
-                original_contents,
+                original_contents: Some(original_contents),
                 diff,
                 status,
             });
         }
+        for mv in moves.iter().filter(|mv| graph.file(&mv.from).is_none()) {
+            processed.push(ProcessedEdit {
+                edit: crate::refactor::rewrite::FileEdit {
+                    original_path: mv.from.clone(),
+                    output_path: mv.to.clone(),
+                    edits: Vec::new(),
+                    updated_contents: String::new(),
+                },
+                original_contents: None,
+                diff: None,
+                status: MvFileStatus::Relocated,
+            });
+        }
+        processed.sort_by(|a, b| a.edit.original_path.cmp(&b.edit.original_path));
 
         let changes: Vec<MvFileChange> = processed
             .iter()
@@ -497,20 +990,63 @@ impl Operations {
             })
             .collect();
 
+        let use_git = match options.git {
+            Some(true) if !git::is_work_tree(root) => {
+                return Err(OperationError::InvalidInput(format!(
+                    "--git requires '{}' to be inside a git work tree",
+                    root.display()
+                )));
+            }
+            Some(enabled) => enabled,
+            None => git::is_work_tree(root),
+        };
+        let mut tracked = HashSet::new();
+        let mut dirty = Vec::new();
+        if use_git {
+            let touched: Vec<PathBuf> = processed
+                .iter()
+                .filter(|edit| edit.status != MvFileStatus::Unchanged)
+                .map(|edit| edit.edit.original_path.clone())
+                .collect();
+            if !options.allow_dirty {
+                dirty = git::dirty_files(root, &touched)?;
+            }
+            // Dry runs list them instead, so the preview still shows every change.
+            if !dirty.is_empty() && !options.dry_run {
+                return Err(OperationError::InvalidInput(format!(
+                    "uncommitted changes in {}; commit or stash them, or pass --allow-dirty",
+                    dirty
+                        .iter()
+                        .map(|path| format!("'{}'", path.display()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+            tracked.extend(git::tracked_files(root, &touched)?);
+        }
+        // Git is the safety net in git mode, so no backups are written.
+        let create_backup = options.create_backup && !use_git;
+
         if options.dry_run {
             return Ok(MvOutcome {
                 changes,
                 exit_code: 0,
                 dry_run: true,
+                dirty,
             });
         }
 
         struct AppliedOperation {
             original_path: PathBuf,
             output_path: PathBuf,
-            original_contents: String,
-            dest_original_contents: Option<String>,
+            original_contents: Option<String>,
+            dest_original_contents: Option<Vec<u8>>,
+            /// The overwritten destination was tracked, so `git mv -f` dropped it
+            /// from the index.
+            dest_tracked: bool,
             rename: bool,
+            /// Moved with `git mv`, so rolled back the same way.
+            git_rename: bool,
         }
 
         let mut applied: Vec<AppliedOperation> = Vec::new();
@@ -536,6 +1072,7 @@ impl Operations {
                 })?;
 
                 let mut dest_original_contents = None;
+                let mut dest_tracked = false;
                 if dest_abs.exists() {
                     if !options.force {
                         return Err(OperationError::InvalidInput(format!(
@@ -544,13 +1081,18 @@ impl Operations {
                         )));
                     }
                     dest_original_contents =
-                        Some(fs::read_to_string(&dest_abs).map_err(|source| {
-                            OperationError::Io {
-                                path: dest_abs.clone(),
-                                source,
-                            }
+                        Some(fs::read(&dest_abs).map_err(|source| OperationError::Io {
+                            path: dest_abs.clone(),
+                            source,
                         })?);
-                    if options.create_backup {
+                    if use_git {
+                        dest_tracked = !git::tracked_files(
+                            root,
+                            std::slice::from_ref(&edit.edit.output_path),
+                        )?
+                        .is_empty();
+                    }
+                    if create_backup {
                         maybe_create_backup(&dest_abs).map_err(|source| OperationError::Io {
                             path: dest_abs.clone(),
                             source,
@@ -562,24 +1104,20 @@ impl Operations {
                     })?;
                 }
 
-                if options.create_backup && source_abs.exists() {
+                if create_backup && source_abs.exists() {
                     maybe_create_backup(&source_abs).map_err(|source| OperationError::Io {
                         path: source_abs.clone(),
                         source,
                     })?;
                 }
 
-                fs::rename(&source_abs, &dest_abs).map_err(|source| OperationError::Io {
-                    path: source_abs.clone(),
-                    source,
-                })?;
-
-                if edit.status != MvFileStatus::Unchanged {
-                    atomic_write(&dest_abs, &edit.edit.updated_contents).map_err(|source| {
-                        OperationError::Io {
-                            path: dest_abs.clone(),
-                            source,
-                        }
+                let git_rename = tracked.contains(&edit.edit.original_path);
+                if git_rename {
+                    git::move_file(root, &edit.edit.original_path, &edit.edit.output_path)?;
+                } else {
+                    fs::rename(&source_abs, &dest_abs).map_err(|source| OperationError::Io {
+                        path: source_abs.clone(),
+                        source,
                     })?;
                 }
 
@@ -588,8 +1126,19 @@ impl Operations {
                     output_path: edit.edit.output_path.clone(),
                     original_contents: edit.original_contents.clone(),
                     dest_original_contents,
+                    dest_tracked,
                     rename: true,
+                    git_rename,
                 });
+
+                if edit.original_contents.is_some() && edit.diff.is_some() {
+                    atomic_write(&dest_abs, &edit.edit.updated_contents).map_err(|source| {
+                        OperationError::Io {
+                            path: dest_abs.clone(),
+                            source,
+                        }
+                    })?;
+                }
             }
 
             // Update files that remain in place.
@@ -602,7 +1151,7 @@ impl Operations {
                 }
 
                 let path_abs = root.join(&edit.edit.original_path);
-                if options.create_backup && path_abs.exists() {
+                if create_backup && path_abs.exists() {
                     maybe_create_backup(&path_abs).map_err(|source| OperationError::Io {
                         path: path_abs.clone(),
                         source,
@@ -621,50 +1170,180 @@ impl Operations {
                     output_path: edit.edit.output_path.clone(),
                     original_contents: edit.original_contents.clone(),
                     dest_original_contents: None,
+                    dest_tracked: false,
                     rename: false,
+                    git_rename: false,
                 });
             }
 
+            if use_git {
+                let staged: Vec<PathBuf> = processed
+                    .iter()
+                    .filter(|edit| {
+                        edit.status != MvFileStatus::Unchanged
+                            && tracked.contains(&edit.edit.original_path)
+                    })
+                    .map(|edit| edit.edit.output_path.clone())
+                    .collect();
+                git::stage(root, &staged)?;
+            }
+
             Ok(())
         })();
 
         if let Err(err) = apply_result {
             // Attempt rollback best-effort.
             for op in applied.iter().rev() {
+                let original_abs = root.join(&op.original_path);
                 if op.rename {
                     let dest_abs = root.join(&op.output_path);
-                    let original_abs = root.join(&op.original_path);
-                    if dest_abs.exists() {
-                        if let Err(rename_err) = fs::rename(&dest_abs, &original_abs) {
-                            let _ = atomic_write(&original_abs, &op.original_contents);
-                            if let Some(dest_contents) = &op.dest_original_contents {
-                                let _ = atomic_write(&dest_abs, dest_contents);
-                            }
-                            let _ = rename_err;
-                        } else if let Some(dest_contents) = &op.dest_original_contents {
-                            let _ = atomic_write(&dest_abs, dest_contents);
-                        }
-                    } else {
-                        let _ = atomic_write(&original_abs, &op.original_contents);
-                        if let Some(dest_contents) = &op.dest_original_contents {
-                            let _ = atomic_write(&dest_abs, dest_contents);
-                        }
+                    if op.git_rename {
+                        let _ = git::move_file(root, &op.output_path, &op.original_path);
+                    } else if dest_abs.exists() {
+                        let _ = fs::rename(&dest_abs, &original_abs);
+                    }
+                    if op.dest_tracked {
+                        let _ = git::restore_from_head(root, &op.output_path);
+                    }
+                    if let Some(dest_contents) = &op.dest_original_contents {
+                        let _ = fs::write(&dest_abs, dest_contents);
                     }
-                } else {
-                    let path_abs = root.join(&op.original_path);
-                    let _ = atomic_write(&path_abs, &op.original_contents);
+                }
+                // Relocated documents may already carry rewritten links.
+                if let Some(contents) = &op.original_contents {
+                    let _ = atomic_write(&original_abs, contents);
                 }
             }
             return Err(err);
         }
 
+        // Directory moves leave their (now empty) source directories behind.
+        for directory in moved_directories(&options.moves, root) {
+            remove_empty_dirs(&directory);
+        }
+
         Ok(MvOutcome {
             changes,
             exit_code: 0,
             dry_run: false,
+            dirty: Vec::new(),
         })
     }
 
+    /// Resolve requested moves into project-relative file moves, expanding
+    /// directories into one move per contained file.
+    fn expand_moves(
+        &self,
+        requests: &[FileMove],
+        force: bool,
+    ) -> Result<Vec<FileMove>, OperationError> {
+        let root = &self.config.project.root;
+        if requests.is_empty() {
+            return Err(OperationError::InvalidInput("no moves requested".into()));
+        }
+
+        let mut moves = Vec::new();
+        for request in requests {
+            let (source_rel, source_abs) = resolve_input_path(root, &request.from, true, "source")?;
+            let (dest_rel, dest_abs) = resolve_input_path(root, &request.to, false, "destination")?;
+            if source_rel == dest_rel {
+                return Err(OperationError::InvalidInput(format!(
+                    "source and destination refer to the same path ('{}')",
+                    source_rel.display()
+                )));
+            }
+
+            if source_abs.is_dir() {
+                if dest_rel.starts_with(&source_rel) {
+                    return Err(OperationError::InvalidInput(format!(
+                        "cannot move directory '{}' into itself",
+                        source_rel.display()
+                    )));
+                }
+                if dest_abs.is_file() {
+                    return Err(OperationError::InvalidInput(format!(
+                        "destination '{}' is a file; directories must move to a directory",
+                        dest_rel.display()
+                    )));
+                }
+                let before = moves.len();
+                for entry in WalkDir::new(&source_abs)
+                    .sort_by_file_name()
+                    .into_iter()
+                    .filter_map(Result::ok)
+                    .filter(|entry| entry.file_type().is_file())
+                {
+                    let Ok(inner) = entry.path().strip_prefix(&source_abs) else {
+                        continue;
+                    };
+                    moves.push(FileMove {
+                        from: source_rel.join(inner),
+                        to: dest_rel.join(inner),
+                    });
+                }
+                if moves.len() == before {
+                    return Err(OperationError::InvalidInput(format!(
+                        "source directory '{}' contains no files",
+                        source_rel.display()
+                    )));
+                }
+                continue;
+            }
+
+            if !source_abs.is_file() {
+                return Err(OperationError::InvalidInput(format!(
+                    "source '{}' must be a file or directory",
+                    source_rel.display()
+                )));
+            }
+            if is_markdown_path(&source_rel.to_string_lossy())
+                && !is_markdown_path(&dest_rel.to_string_lossy())
+            {
+                return Err(OperationError::InvalidInput(format!(
+                    "destination '{}' must end with .md or .markdown",
+                    dest_rel.display()
+                )));
+            }
+            moves.push(FileMove {
+                from: source_rel,
+                to: dest_rel,
+            });
+        }
+
+        let mut sources = HashSet::new();
+        let mut destinations = HashSet::new();
+        for mv in &moves {
+            if !sources.insert(&mv.from) {
+                return Err(OperationError::InvalidInput(format!(
+                    "'{}' is moved more than once",
+                    mv.from.display()
+                )));
+            }
+            if !destinations.insert(&mv.to) {
+                return Err(OperationError::InvalidInput(format!(
+                    "multiple moves target '{}'",
+                    mv.to.display()
+                )));
+            }
+        }
+        for mv in &moves {
+            if sources.contains(&mv.to) {
+                return Err(OperationError::InvalidInput(format!(
+                    "destination '{}' is also moved by this batch; chained moves are not supported",
+                    mv.to.display()
+                )));
+            }
+            if !force && root.join(&mv.to).exists() {
+                return Err(OperationError::InvalidInput(format!(
+                    "destination '{}' already exists (use --force to overwrite)",
+                    mv.to.display()
+                )));
+            }
+        }
+
+        Ok(moves)
+    }
+
     fn render_lint(
         &self,
         report: &LintRenderData,
@@ -680,12 +1359,27 @@ impl Operations {
 
     fn collect_targets(&self, options: &ScanOptions) -> Result<Vec<PathBuf>, OperationError> {
         let root = self.config.project.root.clone();
-        let mut candidates = if options.staged {
-            let staged = self.git_staged_files()?;
+        let changed = match (options.staged, &options.changed_since) {
+            (true, Some(_)) => {
+                return Err(OperationError::InvalidInput(
+                    "--staged cannot be combined with --since or --changed-from".into(),
+                ))
+            }
+            (true, None) => Some(git::staged_files(&root)?),
+            (false, Some(ChangedSince::Revision(rev))) => {
+                Some(git::changed_files(&root, rev, false)?)
+            }
+            (false, Some(ChangedSince::MergeBase(base))) => {
+                Some(git::changed_files(&root, base, true)?)
+            }
+            (false, None) => None,
+        };
+
+        let mut candidates = if let Some(changed) = changed {
             if options.paths.is_empty() {
-                staged
+                changed
             } else {
-                filter_paths(staged, &options.paths, &root)
+                filter_paths(changed, &options.paths, &root)
             }
         } else if options.paths.is_empty() {
             walk_markdown_files(&root, self.ignore_filter.as_ref())?
@@ -711,35 +1405,67 @@ impl Operations {
         Ok(candidates)
     }
 
-    fn git_staged_files(&self) -> Result<Vec<PathBuf>, OperationError> {
-        let output = Command::new("git")
-            .arg("-C")
-            .arg(&self.config.project.root)
-            .arg("diff")
-            .arg("--name-only")
-            .arg("--cached")
-            .output()
-            .map_err(|err| OperationError::Git {
-                source: err,
-                message: "failed to invoke git".into(),
-            })?;
-
-        if !output.status.success() {
-            return Err(OperationError::Git {
-                source: io::Error::other(String::from_utf8_lossy(&output.stderr).to_string()),
-                message: "git diff --name-only --cached failed".into(),
-            });
+    /// Write or check one catalog file. Returns its status and either the catalog text
+    /// (`Update`) or the check report (`Check`/`Diff`).
+    fn sync_catalog_page(
+        &self,
+        options: &CatalogOptions,
+        display_path: &Path,
+        rendered: String,
+    ) -> Result<(CatalogStatus, String), OperationError> {
+        if options.mode == CatalogMode::Update && !options.write_to_disk {
+            return Ok((CatalogStatus::Rendered, rendered));
         }
 
-        let mut files = Vec::new();
-        for line in String::from_utf8_lossy(&output.stdout).lines() {
-            let path = PathBuf::from(line);
-            if is_markdown_path(line) {
-                files.push(path);
+        let absolute = self.config.project.root.join(display_path);
+        let existing = match std::fs::read_to_string(&absolute) {
+            Ok(contents) => Some(contents),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(source) => {
+                return Err(OperationError::Io {
+                    path: absolute,
+                    source,
+                })
             }
+        };
+        // Only the timestamp differs: the catalog on disk is still current.
+        let current = existing.as_deref().filter(|existing| {
+            catalog::strip_timestamp(existing) == catalog::strip_timestamp(&rendered)
+        });
+
+        if options.mode == CatalogMode::Update {
+            return match current {
+                Some(existing) if !options.regen => {
+                    Ok((CatalogStatus::UpToDate, existing.to_string()))
+                }
+                _ => {
+                    atomic_write(&absolute, &rendered)?;
+                    Ok((CatalogStatus::Written, rendered))
+                }
+            };
         }
 
-        Ok(files)
+        let status = if current.is_some() {
+            CatalogStatus::UpToDate
+        } else if existing.is_some() {
+            CatalogStatus::Stale
+        } else {
+            CatalogStatus::Missing
+        };
+        let display = display_path.display();
+        let report = match status {
+            CatalogStatus::UpToDate => format!("✅ {display} is up to date"),
+            _ if options.mode == CatalogMode::Diff => build_diff(
+                display_path,
+                &catalog::strip_timestamp(existing.as_deref().unwrap_or("")),
+                &catalog::strip_timestamp(&rendered),
+            ),
+            CatalogStatus::Missing => {
+                format!("❌ {display} is missing; run `markdown-doc catalog`")
+            }
+            _ => format!("❌ {display} is out of date; run `markdown-doc catalog`"),
+        };
+        Ok((status, report))
     }
 
     fn resolve_output_path(&self, path: &Path) -> PathBuf {
@@ -952,6 +1678,72 @@ fn resolve_input_path(
     Ok((relative, normalized))
 }
 
+/// Requested sources that are (still) directories, i.e. directory moves.
+fn moved_directories(requests: &[FileMove], root: &Path) -> Vec<PathBuf> {
+    requests
+        .iter()
+        .map(|request| normalize_path(root.join(&request.from)))
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// Remove `dir` and its subdirectories, bottom-up, as long as they are empty.
+fn remove_empty_dirs(dir: &Path) {
+    for entry in WalkDir::new(dir)
+        .contents_first(true)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_dir())
+    {
+        let _ = fs::remove_dir(entry.path());
+    }
+}
+
+/// Whole-file write planned by a refactoring command.
+pub(crate) struct PlannedWrite {
+    pub path: PathBuf,
+    /// Current contents; `None` when the file is created.
+    pub original: Option<String>,
+    /// New contents; `None` removes the file.
+    pub updated: Option<String>,
+}
+
+/// Apply `writes` (paths relative to `root`) as one transaction: on the first failure,
+/// files already written are restored and created files removed, best-effort.
+pub(crate) fn apply_planned_writes(
+    root: &Path,
+    writes: &[PlannedWrite],
+    create_backup: bool,
+) -> Result<(), OperationError> {
+    let mut applied: Vec<&PlannedWrite> = Vec::new();
+    let result = writes.iter().try_for_each(|write| {
+        let path = root.join(&write.path);
+        let io_error = |source| OperationError::Io {
+            path: path.clone(),
+            source,
+        };
+        if create_backup && write.original.is_some() {
+            maybe_create_backup(&path).map_err(io_error)?;
+        }
+        applied.push(write);
+        match &write.updated {
+            Some(contents) => atomic_write(&path, contents).map_err(io_error),
+            None => fs::remove_file(&path).map_err(io_error),
+        }
+    });
+
+    if result.is_err() {
+        for write in applied.iter().rev() {
+            let path = root.join(&write.path);
+            let _ = match &write.original {
+                Some(contents) => atomic_write(&path, contents),
+                None => fs::remove_file(&path),
+            };
+        }
+    }
+    result
+}
+
 fn maybe_create_backup(path: &Path) -> io::Result<()> {
     if !path.exists() {
         return Ok(());
@@ -1098,6 +1890,16 @@ impl PathMatcher {
     }
 }
 
+fn count_severities(findings: &[LintFinding]) -> (usize, usize) {
+    findings.iter().fold((0, 0), |(errors, warnings), finding| {
+        match finding.severity {
+            SeverityLevel::Error => (errors + 1, warnings),
+            SeverityLevel::Warning => (errors, warnings + 1),
+            SeverityLevel::Ignore => (errors, warnings),
+        }
+    })
+}
+
 fn contains_glob_characters(value: &str) -> bool {
     value.contains('*') || value.contains('?') || value.contains('[')
 }
@@ -1109,28 +1911,13 @@ fn path_to_slash(path: &Path) -> String {
         .join("/")
 }
 
-fn build_catalog_entry(path: &Path, sections: Vec<DocumentSection>) -> CatalogEntry {
-    let headings = sections
-        .into_iter()
-        .map(|section| HeadingSummary {
-            level: section.heading.depth,
-            text: section.heading.normalized.clone(),
-            anchor: section.heading.anchor.clone(),
-        })
-        .collect();
-
-    CatalogEntry {
-        path: normalize_relative_path(path),
-        headings,
-    }
-}
-
-fn normalize_relative_path(path: &Path) -> PathBuf {
-    if path.components().next().is_none() {
-        PathBuf::from(".")
-    } else {
-        path.to_path_buf()
-    }
+/// File modification time as an RFC 3339 UTC timestamp.
+fn modified_time(path: &Path) -> Option<String> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(
+        chrono::DateTime::<chrono::Utc>::from(modified)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+    )
 }
 
 fn is_markdown_path(path: &str) -> bool {
@@ -1144,12 +1931,49 @@ pub struct CatalogOptions {
     pub format: CatalogFormat,
     pub output_path: Option<PathBuf>,
     pub write_to_disk: bool,
+    pub mode: CatalogMode,
+    /// Overrides `catalog.timestamp` when set.
+    pub timestamp: Option<CatalogTimestamp>,
+    /// Rewrite the catalog even when only its timestamp would change.
+    pub regen: bool,
+}
+
+/// Mode selection for catalog execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CatalogMode {
+    /// Render the catalog and write it when `write_to_disk` is set.
+    Update,
+    /// Compare against the catalog on disk without writing.
+    Check,
+    /// Like `Check`, but render a unified diff of the changes.
+    Diff,
+}
+
+/// Status classification for catalog outcomes, ordered by severity; with
+/// `catalog.directory_index` the outcome reports the most severe page status.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CatalogStatus {
+    /// Rendered without touching disk.
+    Rendered,
+    /// The catalog on disk matches apart from its timestamp.
+    UpToDate,
+    Written,
+    Stale,
+    Missing,
 }
 
 /// Lint execution options.
 pub struct LintOptions {
     pub scan: ScanOptions,
     pub format: LintFormat,
+    /// Baseline file used to suppress known findings (or written to with `write_baseline`).
+    pub baseline: Option<PathBuf>,
+    /// Record current findings into the baseline instead of failing on them.
+    pub write_baseline: bool,
+    /// Append words reported by the `spelling` rule to the project word list.
+    pub add_words: bool,
+    /// Reuse per-file results from the cache under `.markdown-doc/cache` and update it.
+    pub cache: bool,
 }
 
 /// Validate execution options.
@@ -1163,7 +1987,10 @@ pub struct ValidateOptions {
 /// File scanning configuration shared by catalog and lint.
 pub struct ScanOptions {
     pub paths: Vec<PathBuf>,
+    /// Only scan files staged in the git index; lint reads their staged contents.
     pub staged: bool,
+    /// Only scan files changed relative to a git revision.
+    pub changed_since: Option<ChangedSince>,
     pub respect_ignore: bool,
 }
 
@@ -1172,16 +1999,29 @@ impl Default for ScanOptions {
         ScanOptions {
             paths: Vec::new(),
             staged: false,
+            changed_since: None,
             respect_ignore: true,
         }
     }
 }
 
+/// Git revision a scan is restricted against.
+#[derive(Clone, Debug)]
+pub enum ChangedSince {
+    /// Files that differ between the revision and the working tree (`--since`).
+    Revision(String),
+    /// Files changed on the current branch since it forked from the base (`--changed-from`).
+    MergeBase(String),
+}
+
 /// Catalog execution result.
 pub struct CatalogOutcome {
+    /// The catalog itself in `Update` mode, otherwise the check report.
     pub rendered: String,
     pub format: CatalogFormat,
     pub entries: Vec<CatalogEntry>,
+    pub status: CatalogStatus,
+    pub exit_code: i32,
 }
 
 /// Lint execution result containing rendered output and exit code.
@@ -1189,6 +2029,8 @@ pub struct LintOutcome {
     pub rendered: String,
     pub report: LintRenderData,
     pub exit_code: i32,
+    /// Files whose findings were reused from the lint cache.
+    pub files_cached: usize,
 }
 
 /// Validate execution result containing rendered output and exit code.
@@ -1205,6 +2047,34 @@ pub struct RefsOptions {
     pub anchor_only: bool,
 }
 
+/// Options for the stats command.
+pub struct StatsOptions {
+    pub scan: ScanOptions,
+    /// Number of largest sections and oldest documents to report.
+    pub top: usize,
+}
+
+/// Search execution options.
+pub struct SearchOptions {
+    pub scan: ScanOptions,
+    pub query: String,
+    pub limit: usize,
+    /// Overrides `search.index` when set.
+    pub index_path: Option<PathBuf>,
+    /// Re-parse changed documents before searching; an existing index is used as-is
+    /// otherwise (a missing index is always built).
+    pub refresh: bool,
+}
+
+/// Ranked search results plus index statistics.
+pub struct SearchOutcome {
+    pub query: String,
+    pub results: Vec<SearchResult>,
+    pub index: SearchIndexStats,
+    pub duration: Duration,
+    pub exit_code: i32,
+}
+
 /// Reference lookup result set.
 pub struct RefsOutcome {
     pub query: String,
@@ -1224,11 +2094,17 @@ pub struct RefsMatch {
 /// Options for the mv command.
 pub struct MvOptions {
     pub scan: ScanOptions,
-    pub source: PathBuf,
-    pub destination: PathBuf,
+    /// Requested moves, relative to the project root. Sources may be Markdown files,
+    /// other files (assets), or directories, which move with everything inside them.
+    pub moves: Vec<FileMove>,
     pub dry_run: bool,
     pub force: bool,
     pub create_backup: bool,
+    /// Move with `git mv` and stage rewritten files instead of writing backups;
+    /// `None` enables this when the project root is inside a git work tree.
+    pub git: Option<bool>,
+    /// In git mode, allow touching files that have uncommitted changes.
+    pub allow_dirty: bool,
     pub quiet: bool,
     pub json: bool,
 }
@@ -1238,6 +2114,9 @@ pub struct MvOutcome {
     pub changes: Vec<MvFileChange>,
     pub exit_code: i32,
     pub dry_run: bool,
+    /// Files with uncommitted changes that a real `mv --git` run would refuse to
+    /// touch; only reported by dry runs.
+    pub dirty: Vec<PathBuf>,
 }
 
 /// Individual file update surfaced by the mv command.
@@ -1254,6 +2133,10 @@ pub enum MvFileStatus {
     Updated,
     Relocated,
     Unchanged,
+    /// New document written by `split`.
+    Created,
+    /// Document folded into another by `merge`.
+    Removed,
 }
 
 /// Options for the TOC command.
@@ -1261,6 +2144,8 @@ pub struct TocOptions {
     pub scan: ScanOptions,
     pub mode: TocMode,
     pub quiet: bool,
+    /// Placement for `TocMode::Insert`; `None` uses `lint.toc.insert_at`.
+    pub insert_at: Option<TocInsertPosition>,
 }
 
 /// Execution result for TOC synchronisation.
@@ -1283,6 +2168,8 @@ pub enum TocMode {
     Check,
     Update,
     Diff,
+    /// Update existing blocks and add a block to files without markers.
+    Insert,
 }
 
 /// Status classification for TOC outcomes.
@@ -1291,7 +2178,13 @@ pub enum TocStatus {
     UpToDate,
     NeedsUpdate,
     Updated,
+    /// A new block was added by `TocMode::Insert`.
+    Inserted,
     MissingMarkers,
+    /// No TOC markers and `lint.toc.skip_missing` is set.
+    Skipped,
+    /// A start marker carries options that could not be parsed.
+    InvalidOptions,
 }
 
 /// Errors surfaced while running operations.
@@ -1315,6 +2208,8 @@ pub enum OperationError {
     InvalidInput(String),
     #[error("rewrite failure: {0}")]
     Rewrite(#[from] RewriteError),
+    #[error("catalog template error: {0:#}")]
+    Template(#[from] TemplateError),
     #[error("{0}")]
     Other(String),
 }
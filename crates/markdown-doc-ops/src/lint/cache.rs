@@ -0,0 +1,351 @@
+//! Persistent lint cache stored under `.markdown-doc/cache`.
+//!
+//! Each entry records the content hash a file was checked against, the anchors it
+//! exposes, the anchor fingerprint of every document it links to, and the findings
+//! produced. An entry is reused only while the file, the anchors of its link targets,
+//! and its orphan/duplicate context are unchanged. The whole cache is discarded when
+//! the tool version or the effective lint configuration changes.
+//!
+//! The cache is best-effort: unreadable or corrupt cache files are treated as empty,
+//! and failures to persist it never fail a lint run.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use markdown_doc_config::{Config, LintRule, SeverityLevel};
+use markdown_doc_format::LintFinding;
+use markdown_doc_utils::{atomic_write, content_hash};
+use serde::{Deserialize, Serialize};
+
+use super::{document_anchors, spelling, FileSnapshot, LintEnvironment};
+use crate::dupes::DuplicateReport;
+use crate::orphans::OrphanReport;
+use crate::paths::{is_external, is_markdown_path, resolve_relative_path, split_link_target};
+
+/// Directory, relative to the project root, holding persistent caches.
+pub const CACHE_DIR: &str = ".markdown-doc/cache";
+const CACHE_FILE: &str = "lint.json";
+/// Bumped whenever the on-disk layout or the meaning of an entry changes.
+const FORMAT_VERSION: u32 = 2;
+/// Anchor fingerprint recorded for link targets that do not exist.
+const MISSING: &str = "missing";
+
+#[derive(Default, Deserialize, Serialize)]
+struct CacheFile {
+    version: u32,
+    tool_version: String,
+    config_hash: String,
+    files: BTreeMap<String, CachedFile>,
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+struct CachedFile {
+    content_hash: String,
+    /// Hash of the project-wide orphan and duplicate verdicts for this file.
+    context_hash: String,
+    anchors: Vec<String>,
+    /// Linked documents mapped to their anchor fingerprint when the file was checked.
+    dependencies: BTreeMap<String, String>,
+    findings: Vec<CachedFinding>,
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+struct CachedFinding {
+    rule: String,
+    line: usize,
+    message: String,
+    severity: String,
+}
+
+/// Hashes identifying the inputs a file was checked against.
+pub(super) struct CacheKey {
+    content_hash: String,
+    context_hash: String,
+}
+
+pub(super) struct LintCache {
+    path: PathBuf,
+    config_hash: String,
+    previous: BTreeMap<String, CachedFile>,
+    current: Mutex<BTreeMap<String, CachedFile>>,
+    /// Anchor fingerprints computed during this run.
+    fingerprints: Mutex<HashMap<PathBuf, String>>,
+    hits: AtomicUsize,
+}
+
+impl LintCache {
+    pub(super) fn load(root: &Path, config: &Config) -> Self {
+        let path = root.join(CACHE_DIR).join(CACHE_FILE);
+        let config_hash = config_hash(root, config);
+        let previous = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<CacheFile>(&contents).ok())
+            .filter(|file| {
+                file.version == FORMAT_VERSION
+                    && file.tool_version == env!("CARGO_PKG_VERSION")
+                    && file.config_hash == config_hash
+            })
+            .map(|file| file.files)
+            .unwrap_or_default();
+
+        LintCache {
+            path,
+            config_hash,
+            previous,
+            current: Mutex::default(),
+            fingerprints: Mutex::default(),
+            hits: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of files answered from the cache so far.
+    pub(super) fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn key(relative: &Path, contents: &str, env: &LintEnvironment) -> CacheKey {
+        let context = verdict_context(relative, env.orphans, env.duplicates);
+        CacheKey {
+            content_hash: content_hash(contents.as_bytes()),
+            context_hash: content_hash(context.as_bytes()),
+        }
+    }
+
+    /// Return the cached findings for `relative` if none of its inputs changed.
+    pub(super) fn lookup(
+        &self,
+        relative: &Path,
+        key: &CacheKey,
+        env: &LintEnvironment,
+    ) -> Option<Vec<LintFinding>> {
+        let name = cache_name(relative);
+        let entry = self.previous.get(&name)?;
+        if entry.content_hash != key.content_hash || entry.context_hash != key.context_hash {
+            return None;
+        }
+        let dependencies_unchanged = entry.dependencies.iter().all(|(dependency, fingerprint)| {
+            self.anchor_fingerprint(Path::new(dependency), env) == *fingerprint
+        });
+        if !dependencies_unchanged {
+            return None;
+        }
+        let findings = entry
+            .findings
+            .iter()
+            .map(|finding| finding.restore(relative, env.config))
+            .collect::<Option<Vec<_>>>()?;
+
+        self.current.lock().unwrap().insert(name, entry.clone());
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(findings)
+    }
+
+    /// Record the findings produced for a freshly checked snapshot.
+    pub(super) fn store(
+        &self,
+        snapshot: &FileSnapshot,
+        key: CacheKey,
+        findings: &[LintFinding],
+        env: &LintEnvironment,
+    ) {
+        let mut dependencies = BTreeMap::new();
+        for link in &snapshot.links {
+            if link.target.is_empty() || is_external(&link.target) {
+                continue;
+            }
+            let (path_part, _) = split_link_target(&link.target);
+            if path_part.is_empty() || !is_markdown_path(path_part) {
+                continue;
+            }
+            let resolved = resolve_relative_path(&snapshot.relative_path, path_part, env.root);
+            if resolved.relative == snapshot.relative_path {
+                continue;
+            }
+            let fingerprint = self.anchor_fingerprint(&resolved.relative, env);
+            dependencies.insert(cache_name(&resolved.relative), fingerprint);
+        }
+
+        let entry = CachedFile {
+            content_hash: key.content_hash,
+            context_hash: key.context_hash,
+            anchors: snapshot.anchor_names().map(str::to_string).collect(),
+            dependencies,
+            findings: findings.iter().map(CachedFinding::from).collect(),
+        };
+        self.current
+            .lock()
+            .unwrap()
+            .insert(cache_name(&snapshot.relative_path), entry);
+    }
+
+    /// Persist this run's entries, keeping entries for files outside the run that
+    /// still exist.
+    pub(super) fn save(self, root: &Path) {
+        let current = self.current.into_inner().unwrap();
+        let mut files = self.previous.clone();
+        files.extend(current);
+        files.retain(|name, _| root.join(name).is_file());
+        if files == self.previous && self.path.is_file() {
+            return;
+        }
+
+        let file = CacheFile {
+            version: FORMAT_VERSION,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            config_hash: self.config_hash,
+            files,
+        };
+        if let Ok(json) = serde_json::to_string(&file) {
+            let _ = atomic_write(&self.path, &json);
+        }
+    }
+
+    /// Hash of the anchors `relative` currently exposes, reusing cached anchors when the
+    /// file is unchanged since it was last checked.
+    fn anchor_fingerprint(&self, relative: &Path, env: &LintEnvironment) -> String {
+        if let Some(fingerprint) = self.fingerprints.lock().unwrap().get(relative) {
+            return fingerprint.clone();
+        }
+
+        let fingerprint = match env.read_document(relative) {
+            Ok(contents) => {
+                let hash = content_hash(contents.as_bytes());
+                let anchors = match self.previous.get(&cache_name(relative)) {
+                    Some(entry) if entry.content_hash == hash => entry.anchors.clone(),
+                    _ => document_anchors(env.parser, relative, &contents),
+                };
+                content_hash(anchors.join("\n").as_bytes())
+            }
+            Err(_) => MISSING.to_string(),
+        };
+
+        self.fingerprints
+            .lock()
+            .unwrap()
+            .insert(relative.to_path_buf(), fingerprint.clone());
+        fingerprint
+    }
+}
+
+impl From<&LintFinding> for CachedFinding {
+    fn from(finding: &LintFinding) -> Self {
+        CachedFinding {
+            rule: finding.rule.as_str().to_string(),
+            line: finding.line,
+            message: finding.message.clone(),
+            severity: finding.severity.to_string(),
+        }
+    }
+}
+
+impl CachedFinding {
+    fn restore(&self, relative: &Path, config: &Config) -> Option<LintFinding> {
+        let rule = self.rule.parse::<LintRule>().ok().or_else(|| {
+            config
+                .lint
+                .custom
+                .iter()
+                .find(|custom| *custom.id == *self.rule)
+                .map(|custom| custom.rule())
+        })?;
+        Some(LintFinding {
+            rule,
+            path: relative.to_path_buf(),
+            line: self.line,
+            message: self.message.clone(),
+            severity: self.severity.parse::<SeverityLevel>().ok()?,
+        })
+    }
+}
+
+/// The project-wide orphan and duplicate verdicts that affect `relative`'s findings;
+/// empty when it is neither orphaned nor part of a duplicate cluster.
+pub(crate) fn verdict_context(
+    relative: &Path,
+    orphans: Option<&OrphanReport>,
+    duplicates: Option<&DuplicateReport>,
+) -> String {
+    let mut context = String::new();
+    if let Some(reason) = orphans.and_then(|orphans| orphans.reason_for(relative)) {
+        context.push_str(&reason);
+    }
+    for cluster in duplicates.into_iter().flat_map(|duplicates| {
+        duplicates.clusters.iter().filter(|cluster| {
+            cluster
+                .sections
+                .iter()
+                .any(|section| section.path == relative)
+        })
+    }) {
+        let _ = write!(context, "{cluster:?}");
+    }
+    context
+}
+
+fn cache_name(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Deterministic fingerprint of every setting that can change lint findings, plus the
+/// contents of the spelling word lists.
+fn config_hash(root: &Path, config: &Config) -> String {
+    let lint = &config.lint;
+
+    let mut severity: Vec<String> = lint
+        .severity
+        .iter()
+        .map(|(rule, level)| format!("{}={level}", rule.as_str()))
+        .collect();
+    severity.sort();
+
+    let overrides: Vec<String> = lint
+        .severity_overrides
+        .iter()
+        .map(|entry| {
+            let mut rules: Vec<String> = entry
+                .rules
+                .iter()
+                .map(|(rule, level)| format!("{}={level}", rule.as_str()))
+                .collect();
+            rules.sort();
+            format!("{}:{rules:?}:{:?}", entry.path.original(), entry.wildcard)
+        })
+        .collect();
+
+    let mut schemas: Vec<String> = config
+        .schemas
+        .schemas
+        .values()
+        .map(|schema| format!("{schema:?}"))
+        .collect();
+    schemas.sort();
+
+    let mut fingerprint = format!(
+        "{:?}|{:?}|{severity:?}|{:?}|{overrides:?}|{}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{schemas:?}|{}|{:?}",
+        config.project.anchor_dialect,
+        lint.rules,
+        lint.severity_wildcard,
+        lint.max_heading_depth,
+        lint.ignore,
+        lint.toc,
+        lint.custom,
+        lint.orphans,
+        lint.style,
+        lint.spelling,
+        lint.duplicates,
+        config.schemas.default_schema,
+        config.schemas.patterns,
+    );
+    for file in spelling::word_files(&lint.spelling).iter() {
+        let contents = std::fs::read(root.join(file)).unwrap_or_default();
+        fingerprint.push('|');
+        fingerprint.push_str(&content_hash(&contents));
+    }
+    content_hash(fingerprint.as_bytes())
+}
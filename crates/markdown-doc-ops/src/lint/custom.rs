@@ -0,0 +1,119 @@
+//! Evaluation of project-specific `[[lint.custom]]` rules.
+
+use markdown_doc_config::{CustomLintRule, CustomRuleTarget};
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
+
+use super::{FileSnapshot, RuleFinding};
+use crate::lines::byte_to_line;
+
+/// Evaluate a custom rule against a parsed file snapshot.
+pub(super) fn evaluate(rule: &CustomLintRule, snapshot: &FileSnapshot) -> Vec<RuleFinding> {
+    if !rule.applies_to(&snapshot.relative_path) {
+        return Vec::new();
+    }
+
+    if rule.target == CustomRuleTarget::Section {
+        return evaluate_section_length(rule, snapshot);
+    }
+
+    let Some(pattern) = rule.pattern.as_ref() else {
+        return Vec::new();
+    };
+
+    let mut findings = Vec::new();
+    for (line, text) in candidates(rule.target, snapshot) {
+        if let Some(found) = pattern.find(&text) {
+            if rule.require {
+                return Vec::new();
+            }
+            findings.push(RuleFinding {
+                line,
+                message: rule.render_message(found.as_str()),
+            });
+        }
+    }
+
+    if rule.require {
+        // No candidate matched; report the absence once at the top of the file.
+        return vec![RuleFinding {
+            line: 1,
+            message: rule.render_message(""),
+        }];
+    }
+
+    findings
+}
+
+/// Collect `(line, text)` pairs for the element kind a rule targets.
+fn candidates(target: CustomRuleTarget, snapshot: &FileSnapshot) -> Vec<(usize, String)> {
+    match target {
+        CustomRuleTarget::Line => snapshot
+            .contents
+            .lines()
+            .enumerate()
+            .map(|(idx, text)| (idx + 1, text.to_string()))
+            .collect(),
+        CustomRuleTarget::Heading => snapshot
+            .anchors
+            .iter()
+            .map(|anchor| (anchor.line, anchor.normalized.clone()))
+            .collect(),
+        CustomRuleTarget::Link => snapshot
+            .links
+            .iter()
+            .map(|link| (link.line, link.target.clone()))
+            .collect(),
+        CustomRuleTarget::CodeBlock => code_block_info(snapshot),
+        CustomRuleTarget::Section => Vec::new(),
+    }
+}
+
+fn code_block_info(snapshot: &FileSnapshot) -> Vec<(usize, String)> {
+    let parser = Parser::new_ext(&snapshot.contents, Options::all());
+    parser
+        .into_offset_iter()
+        .filter_map(|(event, range)| match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => Some((
+                byte_to_line(range.start, &snapshot.line_offsets),
+                info.trim().to_string(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Flag sections whose own body (up to the next heading of any depth) exceeds `max_lines`.
+fn evaluate_section_length(rule: &CustomLintRule, snapshot: &FileSnapshot) -> Vec<RuleFinding> {
+    let Some(max_lines) = rule.max_lines else {
+        return Vec::new();
+    };
+
+    let total_lines = snapshot.line_offsets.len();
+    let mut findings = Vec::new();
+    for (idx, anchor) in snapshot.anchors.iter().enumerate() {
+        if let Some(pattern) = rule.pattern.as_ref() {
+            if !pattern.is_match(&anchor.normalized) {
+                continue;
+            }
+        }
+
+        let next_line = snapshot
+            .anchors
+            .get(idx + 1)
+            .map(|next| next.line)
+            .unwrap_or(total_lines + 1);
+        let length = next_line.saturating_sub(anchor.line);
+        if length > max_lines {
+            findings.push(RuleFinding {
+                line: anchor.line,
+                message: format!(
+                    "{} ({} lines, max {})",
+                    rule.render_message(&anchor.normalized),
+                    length,
+                    max_lines
+                ),
+            });
+        }
+    }
+    findings
+}
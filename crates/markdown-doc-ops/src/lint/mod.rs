@@ -1,33 +1,52 @@
+mod cache;
+mod custom;
+mod spelling;
+mod style;
+mod suppress;
+
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use globset::GlobMatcher;
 use markdown_doc_config::{
-    Config, LintIgnore, LintIgnoreRules, LintRule, SeverityLevel, TocSettings,
+    Config, CustomLintRule, LintIgnore, LintIgnoreRules, LintRule, SeverityLevel, TocSettings,
 };
 use markdown_doc_format::LintFinding;
-use markdown_doc_parser::{DocumentSection, ParserContext};
+use markdown_doc_parser::{html_anchors, slugify, DocumentSection, ParserContext};
 use pulldown_cmark::{Event, Options, Parser, Tag};
 use rayon::prelude::*;
 use strsim::normalized_levenshtein;
 
 use crate::{
     anchors::normalize_anchor_fragment,
+    git::IndexSnapshot,
     lines::{byte_to_line, compute_line_offsets},
-    paths::{is_external, is_markdown_path, resolve_relative_path, split_link_target},
+    paths::{
+        is_external, is_markdown_path, resolve_relative_path, split_link_target, ResolvedPath,
+    },
     schema::SchemaEngine,
     toc,
-    toc::{TocBlock, TocEntry},
-    OperationError,
+    toc::{GeneratedItem, TocBlock, TocEntry},
+    DuplicateReport, OperationError, OrphanReport,
 };
 
+pub(crate) use self::cache::verdict_context;
+pub use self::cache::CACHE_DIR;
+pub(crate) use self::spelling::add_unknown_words;
+pub use self::spelling::DEFAULT_WORDS_FILE;
+
+use self::cache::LintCache;
+use self::spelling::SpellChecker;
+use self::style::StyleScan;
+use self::suppress::Suppressions;
+
 /// Result produced by the lint engine prior to rendering.
 pub(crate) struct LintResult {
     pub findings: Vec<LintFinding>,
     pub files_scanned: usize,
-    pub error_count: usize,
-    pub warning_count: usize,
+    /// Files whose findings were reused from the persistent cache.
+    pub files_cached: usize,
 }
 
 /// Input bundle for executing lint rules.
@@ -37,6 +56,12 @@ pub(crate) struct LintRunInput<'a> {
     pub targets: &'a [PathBuf],
     pub root: &'a Path,
     pub schema_engine: &'a SchemaEngine,
+    pub orphans: Option<&'a OrphanReport>,
+    pub duplicates: Option<&'a DuplicateReport>,
+    /// Reuse and update the persistent cache under [`CACHE_DIR`].
+    pub cache: bool,
+    /// Read documents from the git index instead of the working tree.
+    pub index: Option<&'a IndexSnapshot>,
 }
 
 /// Execute lint rules across the provided targets.
@@ -46,18 +71,32 @@ pub(crate) fn run(input: LintRunInput<'_>) -> Result<LintResult, OperationError>
         return Ok(LintResult {
             findings: Vec::new(),
             files_scanned: input.targets.len(),
-            error_count: 0,
-            warning_count: 0,
+            files_cached: 0,
         });
     }
 
     let ignore_map = build_ignore_map(&input.config.lint.ignore, &active_rules);
+    let spelling = if active_rules
+        .iter()
+        .any(|active| active.rule == LintRule::Spelling)
+    {
+        Some(SpellChecker::load(&input.config.lint.spelling, input.root)?)
+    } else {
+        None
+    };
     let environment = LintEnvironment {
         config: input.config,
         parser: input.parser,
         root: input.root,
         anchor_cache: Arc::new(AnchorCache::default()),
         schema_engine: input.schema_engine,
+        orphans: input.orphans,
+        duplicates: input.duplicates,
+        spelling,
+        cache: input
+            .cache
+            .then(|| LintCache::load(input.root, input.config)),
+        index: input.index,
     };
 
     let findings = input
@@ -69,6 +108,15 @@ pub(crate) fn run(input: LintRunInput<'_>) -> Result<LintResult, OperationError>
             Ok(acc)
         })?;
 
+    let files_cached = match environment.cache {
+        Some(cache) => {
+            let hits = cache.hits();
+            cache.save(input.root);
+            hits
+        }
+        None => 0,
+    };
+
     let mut sorted = findings;
     sorted.sort_by(|a, b| match a.path.cmp(&b.path) {
         std::cmp::Ordering::Equal => match a.line.cmp(&b.line) {
@@ -78,20 +126,10 @@ pub(crate) fn run(input: LintRunInput<'_>) -> Result<LintResult, OperationError>
         other => other,
     });
 
-    let (errors, warnings) = sorted.iter().fold((0usize, 0usize), |mut acc, finding| {
-        match finding.severity {
-            SeverityLevel::Error => acc.0 += 1,
-            SeverityLevel::Warning => acc.1 += 1,
-            SeverityLevel::Ignore => {}
-        }
-        acc
-    });
-
     Ok(LintResult {
         findings: sorted,
         files_scanned: input.targets.len(),
-        error_count: errors,
-        warning_count: warnings,
+        files_cached,
     })
 }
 
@@ -101,7 +139,25 @@ struct ActiveRule {
     executor: RuleExecutor,
 }
 
-type RuleExecutor = fn(&FileSnapshot, &LintEnvironment) -> Vec<RuleFinding>;
+type BuiltinExecutor = fn(&FileSnapshot, &LintEnvironment) -> Vec<RuleFinding>;
+
+#[derive(Clone)]
+enum RuleExecutor {
+    Builtin(BuiltinExecutor),
+    Custom(Arc<CustomLintRule>),
+    /// Evaluated after every other rule, once suppression usage is known.
+    UnusedSuppressions,
+}
+
+impl RuleExecutor {
+    fn execute(&self, snapshot: &FileSnapshot, env: &LintEnvironment) -> Vec<RuleFinding> {
+        match self {
+            RuleExecutor::Builtin(executor) => executor(snapshot, env),
+            RuleExecutor::Custom(rule) => custom::evaluate(rule, snapshot),
+            RuleExecutor::UnusedSuppressions => Vec::new(),
+        }
+    }
+}
 
 #[derive(Clone)]
 struct RuleFinding {
@@ -115,6 +171,28 @@ struct LintEnvironment<'a> {
     root: &'a Path,
     anchor_cache: Arc<AnchorCache>,
     schema_engine: &'a SchemaEngine,
+    orphans: Option<&'a OrphanReport>,
+    duplicates: Option<&'a DuplicateReport>,
+    spelling: Option<SpellChecker>,
+    cache: Option<LintCache>,
+    index: Option<&'a IndexSnapshot>,
+}
+
+impl LintEnvironment<'_> {
+    /// Read a project-relative document from the index (staged runs) or the working tree.
+    fn read_document(&self, relative: &Path) -> std::io::Result<String> {
+        match self.index {
+            Some(index) => index.read(relative),
+            None => std::fs::read_to_string(self.root.join(relative)),
+        }
+    }
+
+    fn document_exists(&self, resolved: &ResolvedPath) -> bool {
+        match self.index {
+            Some(index) => index.exists(&resolved.relative),
+            None => resolved.absolute.exists(),
+        }
+    }
 }
 
 #[derive(Default)]
@@ -132,24 +210,19 @@ impl AnchorCache {
             return Ok(Some(cached.clone()));
         }
 
-        let sections = match ctx.parser.sections_for_path(relative_path) {
-            Ok(sections) => sections,
+        let contents = match ctx.read_document(relative_path) {
+            Ok(contents) => contents,
             Err(err) => {
-                let absolute = ctx.root.join(relative_path);
                 if err.kind() == std::io::ErrorKind::NotFound {
                     return Ok(None);
                 }
                 return Err(OperationError::Io {
-                    path: absolute,
+                    path: ctx.root.join(relative_path),
                     source: err,
                 });
             }
         };
-
-        let anchors: Vec<String> = sections
-            .into_iter()
-            .map(|section| section.heading.anchor)
-            .collect();
+        let anchors = document_anchors(ctx.parser, relative_path, &contents);
 
         let mut guard = self.inner.lock().unwrap();
         guard.insert(relative_path.to_path_buf(), anchors.clone());
@@ -157,31 +230,74 @@ impl AnchorCache {
     }
 }
 
+/// Every anchor a document exposes: heading anchors (slugged or explicit `{#id}`)
+/// followed by HTML `<a id>` / `<a name>` targets.
+fn document_anchors(parser: &ParserContext, relative: &Path, contents: &str) -> Vec<String> {
+    parser
+        .sections_from_str(relative, contents)
+        .into_iter()
+        .map(|section| section.heading.anchor)
+        .chain(html_anchors(contents).into_iter().map(|anchor| anchor.id))
+        .collect()
+}
+
 fn build_active_rules(config: &Config) -> Vec<ActiveRule> {
-    config
+    let mut active: Vec<ActiveRule> = config
         .lint
         .rules
         .iter()
         .filter_map(|rule| {
-            if !config.lint.is_rule_enabled(*rule) {
+            if !config.lint.is_rule_enabled(rule.clone()) {
                 return None;
             }
 
-            let executor = match rule {
-                LintRule::BrokenLinks => evaluate_broken_links as RuleExecutor,
-                LintRule::BrokenAnchors => evaluate_broken_anchors as RuleExecutor,
-                LintRule::DuplicateAnchors => evaluate_duplicate_anchors as RuleExecutor,
-                LintRule::HeadingHierarchy => evaluate_heading_hierarchy as RuleExecutor,
-                LintRule::RequiredSections => evaluate_required_sections as RuleExecutor,
-                LintRule::TocSync => evaluate_toc_sync as RuleExecutor,
+            let executor: BuiltinExecutor = match rule {
+                LintRule::BrokenLinks => evaluate_broken_links,
+                LintRule::BrokenAnchors => evaluate_broken_anchors,
+                LintRule::DuplicateAnchors => evaluate_duplicate_anchors,
+                LintRule::HeadingHierarchy => evaluate_heading_hierarchy,
+                LintRule::RequiredSections => evaluate_required_sections,
+                LintRule::TocSync => evaluate_toc_sync,
+                LintRule::Orphans => evaluate_orphans,
+                LintRule::LineLength => style::evaluate_line_length,
+                LintRule::TrailingWhitespace => style::evaluate_trailing_whitespace,
+                LintRule::HeadingPunctuation => style::evaluate_heading_punctuation,
+                LintRule::ListMarkerStyle => style::evaluate_list_marker_style,
+                LintRule::EmphasisStyle => style::evaluate_emphasis_style,
+                LintRule::BlanksAroundHeadings => style::evaluate_blanks_around_headings,
+                LintRule::BlanksAroundFences => style::evaluate_blanks_around_fences,
+                LintRule::FencedCodeLanguage => style::evaluate_fenced_code_language,
+                LintRule::Spelling => spelling::evaluate_spelling,
+                LintRule::DuplicateSections => evaluate_duplicate_sections,
+                LintRule::UnusedSuppressions => {
+                    return Some(ActiveRule {
+                        rule: rule.clone(),
+                        executor: RuleExecutor::UnusedSuppressions,
+                    })
+                }
+                LintRule::Custom(_) => return None,
             };
 
             Some(ActiveRule {
-                rule: *rule,
-                executor,
+                rule: rule.clone(),
+                executor: RuleExecutor::Builtin(executor),
             })
         })
-        .collect()
+        .collect();
+
+    // Custom rules are active whenever they are declared; config rejects them in `lint.rules`.
+    for custom in &config.lint.custom {
+        let rule = custom.rule();
+        if !config.lint.is_rule_enabled(rule.clone()) {
+            continue;
+        }
+        active.push(ActiveRule {
+            rule,
+            executor: RuleExecutor::Custom(Arc::new(custom.clone())),
+        });
+    }
+
+    active
 }
 
 fn build_ignore_map(
@@ -201,7 +317,7 @@ fn build_ignore_map(
                 }
             }
         }
-        map.insert(active.rule, matchers);
+        map.insert(active.rule.clone(), matchers);
     }
     map
 }
@@ -212,15 +328,14 @@ fn process_file(
     ignore_map: &HashMap<LintRule, Vec<GlobMatcher>>,
     env: &LintEnvironment,
 ) -> Result<Vec<LintFinding>, OperationError> {
-    let absolute = env.root.join(relative_path);
-    let contents = match std::fs::read_to_string(&absolute) {
+    let contents = match env.read_document(relative_path) {
         Ok(contents) => contents,
         Err(err) => {
             if err.kind() == std::io::ErrorKind::NotFound {
                 return Ok(vec![LintFinding {
                     rule: rules
                         .first()
-                        .map(|rule| rule.rule)
+                        .map(|rule| rule.rule.clone())
                         .unwrap_or(LintRule::BrokenLinks),
                     path: relative_path.to_path_buf(),
                     line: 0,
@@ -229,15 +344,27 @@ fn process_file(
                 }]);
             }
             return Err(OperationError::Io {
-                path: absolute,
+                path: env.root.join(relative_path),
                 source: err,
             });
         }
     };
 
+    let cache_key = env
+        .cache
+        .as_ref()
+        .map(|_| LintCache::key(relative_path, &contents, env));
+    if let (Some(cache), Some(key)) = (&env.cache, &cache_key) {
+        if let Some(findings) = cache.lookup(relative_path, key, env) {
+            return Ok(findings);
+        }
+    }
+
     let snapshot = FileSnapshot::from_contents(relative_path, contents, env.parser)?;
+    let mut suppressions = Suppressions::parse(&snapshot.contents, &snapshot.line_offsets);
 
     let mut results = Vec::new();
+    let mut unused_severity = None;
     for active in rules {
         let ignored = ignore_map
             .get(&active.rule)
@@ -250,16 +377,24 @@ fn process_file(
         let severity = env
             .config
             .lint
-            .severity_for_path(&snapshot.relative_path, active.rule);
+            .severity_for_path(&snapshot.relative_path, active.rule.clone());
 
         if severity == SeverityLevel::Ignore {
             continue;
         }
 
-        let findings = (active.executor)(&snapshot, env);
+        if let RuleExecutor::UnusedSuppressions = active.executor {
+            unused_severity = Some(severity);
+            continue;
+        }
+
+        let findings = active.executor.execute(&snapshot, env);
         for finding in findings {
+            if suppressions.suppresses(active.rule.as_str(), finding.line) {
+                continue;
+            }
             results.push(LintFinding {
-                rule: active.rule,
+                rule: active.rule.clone(),
                 path: snapshot.relative_path.clone(),
                 line: finding.line,
                 message: finding.message,
@@ -268,6 +403,58 @@ fn process_file(
         }
     }
 
+    let is_known = |name: &str| {
+        name.parse::<LintRule>().is_ok()
+            || env
+                .config
+                .lint
+                .custom
+                .iter()
+                .any(|custom| &*custom.id == name)
+    };
+    // A misspelt rule name suppresses nothing, so it is reported even when
+    // `unused-suppressions` is not among the active rules.
+    let unknown_severity = env
+        .config
+        .lint
+        .severity_for_path(&snapshot.relative_path, LintRule::UnusedSuppressions);
+    let unknown_ignored = ignore_map
+        .get(&LintRule::UnusedSuppressions)
+        .is_some_and(|matchers| matches_ignored(matchers, &snapshot.relative_path));
+    if unknown_severity != SeverityLevel::Ignore && !unknown_ignored {
+        for unknown in suppressions.unknown_rules(is_known) {
+            results.push(LintFinding {
+                rule: LintRule::UnusedSuppressions,
+                path: snapshot.relative_path.clone(),
+                line: unknown.line,
+                message: format!(
+                    "Suppression '{}' names unknown rule '{}'",
+                    unknown.directive, unknown.rule
+                ),
+                severity: unknown_severity,
+            });
+        }
+    }
+
+    if let Some(severity) = unused_severity {
+        for unused in suppressions.unused(is_known) {
+            results.push(LintFinding {
+                rule: LintRule::UnusedSuppressions,
+                path: snapshot.relative_path.clone(),
+                line: unused.line,
+                message: format!(
+                    "Suppression '{}' did not match any finding",
+                    unused.directive
+                ),
+                severity,
+            });
+        }
+    }
+
+    if let (Some(cache), Some(key)) = (&env.cache, cache_key) {
+        cache.store(&snapshot, key, &results, env);
+    }
+
     Ok(results)
 }
 
@@ -282,14 +469,17 @@ struct FileSnapshot {
     sections: Vec<DocumentSection>,
     line_offsets: Arc<Vec<usize>>,
     anchors: Vec<AnchorInfo>,
+    /// Ids of HTML `<a id>` / `<a name>` targets.
+    html_anchors: Vec<String>,
     links: Vec<LinkReference>,
+    style: OnceLock<StyleScan>,
 }
 
 #[derive(Clone)]
 struct AnchorInfo {
     anchor: String,
     normalized: String,
-    depth: usize,
+    explicit: bool,
     line: usize,
 }
 
@@ -313,10 +503,14 @@ impl FileSnapshot {
             .map(|section| AnchorInfo {
                 anchor: section.heading.anchor.clone(),
                 normalized: section.heading.normalized.clone(),
-                depth: section.heading.depth,
+                explicit: section.heading.explicit_id.is_some(),
                 line: byte_to_line(section.heading.byte_range.start, &line_offsets),
             })
             .collect();
+        let html_anchors = html_anchors(&contents)
+            .into_iter()
+            .map(|anchor| anchor.id)
+            .collect();
 
         let links = extract_links(&contents, &line_offsets);
 
@@ -326,19 +520,31 @@ impl FileSnapshot {
             sections,
             line_offsets,
             anchors,
+            html_anchors,
             links,
+            style: OnceLock::new(),
         })
     }
 
+    /// Formatting structure shared by the style rules, computed on first use.
+    fn style(&self) -> &StyleScan {
+        self.style.get_or_init(|| StyleScan::build(self))
+    }
+
+    /// Anchors as link fragments are compared: explicit ids may use any case.
     fn normalized_anchor_set(&self) -> HashSet<String> {
+        self.anchor_names().map(normalize_anchor_fragment).collect()
+    }
+
+    fn anchor_names(&self) -> impl Iterator<Item = &str> {
         self.anchors
             .iter()
-            .map(|anchor| anchor.anchor.clone())
-            .collect()
+            .map(|anchor| anchor.anchor.as_str())
+            .chain(self.html_anchors.iter().map(String::as_str))
     }
 
-    fn toc_block(&self, settings: &TocSettings) -> Option<TocBlock> {
-        toc::locate_block(&self.contents, settings)
+    fn toc_blocks(&self, settings: &TocSettings) -> Vec<TocBlock> {
+        toc::locate_blocks(&self.contents, settings)
     }
 }
 
@@ -374,7 +580,7 @@ fn evaluate_broken_links(snapshot: &FileSnapshot, env: &LintEnvironment) -> Vec<
         }
 
         let resolved = resolve_relative_path(&snapshot.relative_path, path_part, env.root);
-        if !resolved.absolute.exists() {
+        if !env.document_exists(&resolved) {
             findings.push(RuleFinding {
                 line: link.line,
                 message: format!("Broken link to '{}'", link.target),
@@ -411,7 +617,7 @@ fn evaluate_broken_anchors(snapshot: &FileSnapshot, env: &LintEnvironment) -> Ve
                     link.line,
                     &normalized_anchor,
                     &link.target,
-                    &snapshot.anchors,
+                    snapshot.anchor_names(),
                 ));
             }
             continue;
@@ -422,7 +628,7 @@ fn evaluate_broken_anchors(snapshot: &FileSnapshot, env: &LintEnvironment) -> Ve
         }
 
         let resolved = resolve_relative_path(&snapshot.relative_path, path_part, env.root);
-        if !resolved.absolute.exists() {
+        if !env.document_exists(&resolved) {
             // Broken links rule will flag missing file.
             continue;
         }
@@ -433,7 +639,10 @@ fn evaluate_broken_anchors(snapshot: &FileSnapshot, env: &LintEnvironment) -> Ve
             Err(_) => continue,
         };
 
-        let anchor_set: HashSet<String> = anchors.iter().cloned().collect();
+        let anchor_set: HashSet<String> = anchors
+            .iter()
+            .map(|anchor| normalize_anchor_fragment(anchor))
+            .collect();
         if !anchor_set.contains(&normalized_anchor) {
             findings.push(RuleFinding {
                 line: link.line,
@@ -448,20 +657,14 @@ fn evaluate_broken_anchors(snapshot: &FileSnapshot, env: &LintEnvironment) -> Ve
     findings
 }
 
-fn anchor_missing_finding(
+fn anchor_missing_finding<'a>(
     line: usize,
     anchor: &str,
     target: &str,
-    anchors: &[AnchorInfo],
+    anchors: impl Iterator<Item = &'a str>,
 ) -> RuleFinding {
     let suggestion = anchors
-        .iter()
-        .map(|candidate| {
-            (
-                candidate.anchor.as_str(),
-                normalized_levenshtein(anchor, candidate.anchor.as_str()),
-            )
-        })
+        .map(|candidate| (candidate, normalized_levenshtein(anchor, candidate)))
         .filter(|(_, score)| *score > 0.6)
         .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
         .map(|(slug, _)| slug.to_string());
@@ -474,11 +677,19 @@ fn anchor_missing_finding(
     RuleFinding { line, message }
 }
 
-fn evaluate_duplicate_anchors(snapshot: &FileSnapshot, _env: &LintEnvironment) -> Vec<RuleFinding> {
+fn evaluate_duplicate_anchors(snapshot: &FileSnapshot, env: &LintEnvironment) -> Vec<RuleFinding> {
+    // Anchors are already de-duplicated with numeric suffixes, so group by the slug
+    // each heading would have received on its own. Explicit ids are taken verbatim.
+    let dialect = env.config.project.anchor_dialect;
     let mut findings = Vec::new();
-    let mut map: HashMap<&str, Vec<&AnchorInfo>> = HashMap::new();
+    let mut map: HashMap<String, Vec<&AnchorInfo>> = HashMap::new();
     for anchor in &snapshot.anchors {
-        map.entry(anchor.anchor.as_str()).or_default().push(anchor);
+        let slug = if anchor.explicit {
+            anchor.anchor.clone()
+        } else {
+            slugify(dialect, &anchor.normalized)
+        };
+        map.entry(slug).or_default().push(anchor);
     }
 
     for (slug, entries) in map {
@@ -488,8 +699,8 @@ fn evaluate_duplicate_anchors(snapshot: &FileSnapshot, _env: &LintEnvironment) -
                 findings.push(RuleFinding {
                     line: duplicate.line,
                     message: format!(
-                        "Duplicate anchor '{}' (first defined at line {})",
-                        slug, first.line
+                        "Duplicate anchor '{}' (first defined at line {}); this heading resolves to '#{}'",
+                        slug, first.line, duplicate.anchor
                     ),
                 });
             }
@@ -553,14 +764,23 @@ fn evaluate_required_sections(snapshot: &FileSnapshot, env: &LintEnvironment) ->
 
 fn evaluate_toc_sync(snapshot: &FileSnapshot, env: &LintEnvironment) -> Vec<RuleFinding> {
     let mut findings = Vec::new();
-    let Some(block) = snapshot.toc_block(&env.config.lint.toc) else {
-        return findings;
-    };
+    for block in snapshot.toc_blocks(&env.config.lint.toc) {
+        findings.extend(block.errors.iter().map(|error| RuleFinding {
+            line: block.start_line,
+            message: format!("Invalid TOC option: {error}."),
+        }));
+        if block.errors.is_empty() {
+            findings.extend(toc_block_findings(snapshot, &block));
+        }
+    }
+    findings
+}
 
-    let document_heads: Vec<&AnchorInfo> = snapshot
-        .anchors
-        .iter()
-        .filter(|anchor| anchor.depth >= 2)
+fn toc_block_findings(snapshot: &FileSnapshot, block: &TocBlock) -> Vec<RuleFinding> {
+    let mut findings = Vec::new();
+    let expected: Vec<(String, GeneratedItem)> = toc::generate_items(&snapshot.sections, block)
+        .into_iter()
+        .map(|item| (normalize_anchor_fragment(&item.anchor), item))
         .collect();
 
     let toc_anchors: Vec<&TocEntry> = block.entries.iter().collect();
@@ -569,18 +789,16 @@ fn evaluate_toc_sync(snapshot: &FileSnapshot, env: &LintEnvironment) -> Vec<Rule
         .iter()
         .map(|entry| entry.anchor.as_str())
         .collect();
-    let document_anchor_set: HashSet<&str> = document_heads
-        .iter()
-        .map(|anchor| anchor.anchor.as_str())
-        .collect();
+    let document_anchor_set: HashSet<&str> =
+        expected.iter().map(|(anchor, _)| anchor.as_str()).collect();
 
-    for heading in &document_heads {
-        if !toc_anchor_set.contains(heading.anchor.as_str()) {
+    for (anchor, item) in &expected {
+        if !toc_anchor_set.contains(anchor.as_str()) {
             findings.push(RuleFinding {
                 line: block.start_line,
                 message: format!(
                     "TOC missing entry for heading '{}' (#{}).",
-                    heading.normalized, heading.anchor
+                    item.text, item.anchor
                 ),
             });
         }
@@ -598,16 +816,15 @@ fn evaluate_toc_sync(snapshot: &FileSnapshot, env: &LintEnvironment) -> Vec<Rule
         }
     }
 
-    let min_len = document_heads.len().min(toc_anchors.len());
+    let min_len = expected.len().min(toc_anchors.len());
     for idx in 0..min_len {
-        let expected = document_heads[idx].anchor.as_str();
-        let actual = toc_anchors[idx].anchor.as_str();
-        if expected != actual {
+        let (expected_anchor, item) = &expected[idx];
+        if *expected_anchor != toc_anchors[idx].anchor {
             findings.push(RuleFinding {
                 line: toc_anchors[idx].line,
                 message: format!(
                     "TOC entry '{}' is out of order (expected '#{}').",
-                    toc_anchors[idx].text, expected
+                    toc_anchors[idx].text, item.anchor
                 ),
             });
             break;
@@ -617,4 +834,29 @@ fn evaluate_toc_sync(snapshot: &FileSnapshot, env: &LintEnvironment) -> Vec<Rule
     findings
 }
 
+fn evaluate_orphans(snapshot: &FileSnapshot, env: &LintEnvironment) -> Vec<RuleFinding> {
+    env.orphans
+        .and_then(|report| report.reason_for(&snapshot.relative_path))
+        .map(|message| vec![RuleFinding { line: 1, message }])
+        .unwrap_or_default()
+}
+
+fn evaluate_duplicate_sections(snapshot: &FileSnapshot, env: &LintEnvironment) -> Vec<RuleFinding> {
+    let Some(report) = env.duplicates else {
+        return Vec::new();
+    };
+    snapshot
+        .anchors
+        .iter()
+        .filter_map(|anchor| {
+            report
+                .reason_for(&snapshot.relative_path, anchor.line)
+                .map(|message| RuleFinding {
+                    line: anchor.line,
+                    message,
+                })
+        })
+        .collect()
+}
+
 // Path helpers now live in crate::paths.
@@ -0,0 +1,355 @@
+//! Offline spell checking of prose against a bundled dictionary plus project word lists.
+//!
+//! Only text events are checked; code blocks, inline code, HTML, autolinks, and
+//! URL- or path-like tokens are skipped. Capitalised words are checked case-insensitively,
+//! while acronyms and mixed-case identifiers (`GitHub`, `README`) are ignored.
+
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use markdown_doc_config::{LintRule, SpellingSettings};
+use markdown_doc_format::{LintFinding, LintWordsSummary};
+use markdown_doc_utils::atomic_write;
+use pulldown_cmark::{Event, LinkType, Options, Parser, Tag};
+use strsim::damerau_levenshtein;
+
+use super::{FileSnapshot, LintEnvironment, RuleFinding};
+use crate::lines::byte_to_line;
+use crate::OperationError;
+
+/// Word list read, and written by `--add-words`, when `lint.spelling.word_files` is empty.
+pub const DEFAULT_WORDS_FILE: &str = ".markdown-doc-words";
+
+/// Word list files in effect: the configured ones, or [`DEFAULT_WORDS_FILE`] when none
+/// are configured so words recorded by `--add-words` are read back on the next run.
+pub(super) fn word_files(settings: &SpellingSettings) -> Cow<'_, [PathBuf]> {
+    if settings.word_files.is_empty() {
+        Cow::Owned(vec![PathBuf::from(DEFAULT_WORDS_FILE)])
+    } else {
+        Cow::Borrowed(&settings.word_files)
+    }
+}
+
+const BUNDLED_DICTIONARY: &str = include_str!("dictionary.txt");
+
+/// Words shorter than this are never reported.
+const MIN_WORD_LENGTH: usize = 3;
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Inflection rules tried when a word is not found verbatim: `(suffix, replacement)`.
+const SUFFIXES: &[(&str, &str)] = &[
+    ("ies", "y"),
+    ("ied", "y"),
+    ("ier", "y"),
+    ("iest", "y"),
+    ("ily", "y"),
+    ("es", ""),
+    ("s", ""),
+    ("ed", ""),
+    ("ed", "e"),
+    ("d", ""),
+    ("ing", ""),
+    ("ing", "e"),
+    ("ly", ""),
+    ("er", ""),
+    ("er", "e"),
+    ("est", ""),
+    ("ness", ""),
+    ("ment", ""),
+    ("able", ""),
+    ("able", "e"),
+];
+
+const PREFIXES: &[&str] = &["un", "re", "pre", "non", "sub", "multi", "over", "under"];
+
+fn bundled() -> &'static HashSet<&'static str> {
+    static WORDS: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| parse_word_list(BUNDLED_DICTIONARY).collect())
+}
+
+fn parse_word_list(contents: &str) -> impl Iterator<Item = &str> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Bundled dictionary merged with the project's configured words and word files.
+pub(super) struct SpellChecker {
+    project: HashSet<String>,
+    /// Suggestions are costly to compute and typos tend to repeat across files.
+    suggestions: Mutex<HashMap<String, Vec<String>>>,
+}
+
+impl SpellChecker {
+    pub(super) fn load(settings: &SpellingSettings, root: &Path) -> Result<Self, OperationError> {
+        let mut project: HashSet<String> = settings
+            .words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect();
+        for file in word_files(settings).iter() {
+            let absolute = root.join(file);
+            match std::fs::read_to_string(&absolute) {
+                Ok(contents) => {
+                    project.extend(parse_word_list(&contents).map(str::to_lowercase));
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(source) => {
+                    return Err(OperationError::Io {
+                        path: absolute,
+                        source,
+                    })
+                }
+            }
+        }
+        Ok(SpellChecker {
+            project,
+            suggestions: Mutex::default(),
+        })
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        bundled().contains(word) || self.project.contains(word)
+    }
+
+    fn is_known(&self, word: &str) -> bool {
+        if self.contains(word) || self.is_inflection(word) {
+            return true;
+        }
+        PREFIXES.iter().any(|prefix| {
+            word.strip_prefix(prefix)
+                .filter(|rest| rest.len() >= MIN_WORD_LENGTH)
+                .is_some_and(|rest| self.contains(rest) || self.is_inflection(rest))
+        })
+    }
+
+    fn is_inflection(&self, word: &str) -> bool {
+        SUFFIXES.iter().any(|(suffix, replacement)| {
+            let Some(stem) = word.strip_suffix(suffix) else {
+                return false;
+            };
+            if stem.len() < 2 {
+                return false;
+            }
+            if self.contains(&format!("{stem}{replacement}")) {
+                return true;
+            }
+            // running -> run, stopped -> stop
+            let mut chars = stem.chars().rev();
+            replacement.is_empty()
+                && matches!((chars.next(), chars.next()), (Some(a), Some(b)) if a == b)
+                && self.contains(&stem[..stem.len() - 1])
+        })
+    }
+
+    fn suggestions(&self, word: &str) -> Vec<String> {
+        if let Some(cached) = self.suggestions.lock().unwrap().get(word) {
+            return cached.clone();
+        }
+        let suggestions = self.closest_words(word);
+        self.suggestions
+            .lock()
+            .unwrap()
+            .insert(word.to_string(), suggestions.clone());
+        suggestions
+    }
+
+    /// Closest dictionary words by edit distance (transpositions count once).
+    fn closest_words(&self, word: &str) -> Vec<String> {
+        let length = word.chars().count();
+        let max_distance = if length >= 5 { 2 } else { 1 };
+        let first = word.chars().next();
+
+        let mut candidates: Vec<(usize, bool, &str)> = bundled()
+            .iter()
+            .copied()
+            .chain(self.project.iter().map(String::as_str))
+            .filter(|candidate| candidate.chars().count().abs_diff(length) <= max_distance)
+            .filter_map(|candidate| {
+                let distance = damerau_levenshtein(word, candidate);
+                (distance <= max_distance)
+                    .then(|| (distance, candidate.chars().next() != first, candidate))
+            })
+            .collect();
+        candidates.sort();
+        candidates.dedup_by(|a, b| a.2 == b.2);
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, _, candidate)| candidate.to_string())
+            .collect()
+    }
+}
+
+pub(super) fn evaluate_spelling(
+    snapshot: &FileSnapshot,
+    env: &LintEnvironment,
+) -> Vec<RuleFinding> {
+    let Some(checker) = env.spelling.as_ref() else {
+        return Vec::new();
+    };
+    let offsets = snapshot.line_offsets.as_slice();
+    let front_matter_end = snapshot.style().front_matter_end().unwrap_or(0);
+
+    let mut findings = Vec::new();
+    let mut reported: HashSet<(usize, String)> = HashSet::new();
+    let mut code_depth = 0usize;
+    let mut autolink_depth = 0usize;
+
+    let parser = Parser::new_ext(&snapshot.contents, Options::all());
+    for (event, range) in parser.into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => code_depth += 1,
+            Event::End(Tag::CodeBlock(_)) => code_depth = code_depth.saturating_sub(1),
+            Event::Start(Tag::Link(LinkType::Autolink | LinkType::Email, _, _)) => {
+                autolink_depth += 1
+            }
+            Event::End(Tag::Link(LinkType::Autolink | LinkType::Email, _, _)) => {
+                autolink_depth = autolink_depth.saturating_sub(1)
+            }
+            Event::Text(text) if code_depth == 0 && autolink_depth == 0 => {
+                let line = byte_to_line(range.start, offsets);
+                if line <= front_matter_end {
+                    continue;
+                }
+                for word in prose_words(&text) {
+                    let lower = word.to_lowercase();
+                    if checker.is_known(&lower) || !reported.insert((line, lower.clone())) {
+                        continue;
+                    }
+                    findings.push(RuleFinding {
+                        line,
+                        message: unknown_word_message(&lower, &checker.suggestions(&lower)),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    findings
+}
+
+/// Split prose into checkable words, dropping URL/path-like tokens, acronyms,
+/// mixed-case identifiers, and words containing digits.
+fn prose_words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for chunk in text.split_whitespace() {
+        if looks_technical(chunk) {
+            continue;
+        }
+        for token in chunk.split(|ch: char| !(ch.is_alphanumeric() || is_apostrophe(ch))) {
+            let token = token.trim_matches(is_apostrophe);
+            // Contractions and possessives: check the leading part only.
+            let word = token.split(is_apostrophe).next().unwrap_or("");
+            if word.chars().count() < MIN_WORD_LENGTH
+                || !word.chars().all(char::is_alphabetic)
+                || word.chars().skip(1).any(char::is_uppercase)
+            {
+                continue;
+            }
+            words.push(word);
+        }
+    }
+    words
+}
+
+fn is_apostrophe(ch: char) -> bool {
+    ch == '\'' || ch == '\u{2019}'
+}
+
+/// URLs, emails, paths, file names, and identifiers are never prose.
+fn looks_technical(chunk: &str) -> bool {
+    if chunk.contains("://")
+        || chunk.starts_with("www.")
+        || chunk.contains(['@', '/', '\\', '_', '=', '<', '>', '{', '}', '$', '#'])
+    {
+        return true;
+    }
+    // `file.rs`, `e.g.`, `v1.2`: a dot followed by another word character.
+    let bytes = chunk.as_bytes();
+    bytes
+        .windows(2)
+        .any(|pair| pair[0] == b'.' && pair[1].is_ascii_alphanumeric())
+}
+
+fn unknown_word_message(word: &str, suggestions: &[String]) -> String {
+    let mut message = format!("Unknown word '{word}'");
+    if let Some((last, rest)) = suggestions.split_last() {
+        let quoted: Vec<String> = rest.iter().map(|item| format!("'{item}'")).collect();
+        if quoted.is_empty() {
+            message.push_str(&format!(". Did you mean '{last}'?"));
+        } else {
+            message.push_str(&format!(
+                ". Did you mean {} or '{last}'?",
+                quoted.join(", ")
+            ));
+        }
+    }
+    message
+}
+
+/// Recover the word from a message produced by [`unknown_word_message`].
+fn unknown_word(message: &str) -> Option<&str> {
+    message
+        .strip_prefix("Unknown word '")
+        .and_then(|rest| rest.split('\'').next())
+}
+
+/// Append every word reported by the `spelling` rule to the project word list, returning
+/// the remaining findings and a summary of what was recorded.
+pub(crate) fn add_unknown_words(
+    root: &Path,
+    settings: &SpellingSettings,
+    findings: Vec<LintFinding>,
+) -> Result<(Vec<LintFinding>, LintWordsSummary), OperationError> {
+    let path = word_files(settings)[0].clone();
+    let absolute = root.join(&path);
+    let existing = match std::fs::read_to_string(&absolute) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(source) => {
+            return Err(OperationError::Io {
+                path: absolute,
+                source,
+            })
+        }
+    };
+    let known: HashSet<String> = parse_word_list(&existing).map(str::to_lowercase).collect();
+
+    let mut added = BTreeSet::new();
+    let mut remaining = Vec::new();
+    for finding in findings {
+        match unknown_word(&finding.message) {
+            Some(word) if finding.rule == LintRule::Spelling => {
+                if !known.contains(word) {
+                    added.insert(word.to_string());
+                }
+            }
+            _ => remaining.push(finding),
+        }
+    }
+
+    if !added.is_empty() {
+        let mut contents = existing;
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        for word in &added {
+            contents.push_str(word);
+            contents.push('\n');
+        }
+        atomic_write(&absolute, &contents)?;
+    }
+
+    Ok((
+        remaining,
+        LintWordsSummary {
+            path,
+            words: added.into_iter().collect(),
+        },
+    ))
+}
@@ -0,0 +1,412 @@
+//! Formatting and prose style rules evaluated on the pulldown-cmark event stream.
+
+use std::sync::OnceLock;
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
+use regex::Regex;
+
+use super::{FileSnapshot, LintEnvironment, RuleFinding};
+use crate::front_matter;
+use crate::lines::byte_to_line;
+
+/// Per-file structure shared by the style rules, computed once per snapshot.
+#[derive(Clone, Debug, Default)]
+pub(super) struct StyleScan {
+    /// Raw line text (1-based line `n` lives at index `n - 1`).
+    lines: Vec<String>,
+    /// Lines inside front matter, code blocks, or tables (exempt from prose checks).
+    exempt: Vec<bool>,
+    /// Last line of the YAML front matter block, if present.
+    front_matter_end: Option<usize>,
+    headings: Vec<HeadingSpan>,
+    fences: Vec<FenceSpan>,
+    bullets: Vec<Marker>,
+    emphasis: Vec<Marker>,
+}
+
+#[derive(Clone, Debug)]
+struct HeadingSpan {
+    text: String,
+    start_line: usize,
+    end_line: usize,
+}
+
+#[derive(Clone, Debug)]
+struct FenceSpan {
+    start_line: usize,
+    end_line: usize,
+    language: String,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Marker {
+    line: usize,
+    symbol: char,
+}
+
+impl StyleScan {
+    pub(super) fn build(snapshot: &FileSnapshot) -> Self {
+        let contents = snapshot.contents.as_str();
+        let offsets = snapshot.line_offsets.as_slice();
+        let lines: Vec<String> = contents
+            .lines()
+            .map(|line| line.trim_end_matches('\r').to_string())
+            .collect();
+        let mut exempt = vec![false; lines.len() + 1];
+
+        // Blank out front matter so pulldown-cmark does not read it as a setext heading.
+        let front_matter = front_matter::parse(contents);
+        let masked;
+        let source = match front_matter {
+            Some(front_matter) => {
+                let end_byte = front_matter.end;
+                // Byte for byte, so offsets still line up with `snapshot.line_offsets`.
+                masked = contents[..end_byte]
+                    .chars()
+                    .map(|ch| match ch {
+                        '\n' => "\n".to_string(),
+                        _ => " ".repeat(ch.len_utf8()),
+                    })
+                    .chain(std::iter::once(contents[end_byte..].to_string()))
+                    .collect::<String>();
+                mark(&mut exempt, 1, front_matter.end_line);
+                masked.as_str()
+            }
+            None => contents,
+        };
+
+        let mut fences = Vec::new();
+        let mut bullets = Vec::new();
+        let mut emphasis = Vec::new();
+        let mut ordered_stack: Vec<bool> = Vec::new();
+
+        for (event, range) in Parser::new_ext(source, Options::all()).into_offset_iter() {
+            let start_line = byte_to_line(range.start, offsets);
+            let end_line = byte_to_line(range.end.saturating_sub(1).max(range.start), offsets);
+            match event {
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    mark(&mut exempt, start_line, end_line);
+                    if let CodeBlockKind::Fenced(info) = kind {
+                        fences.push(FenceSpan {
+                            start_line,
+                            end_line,
+                            language: info.trim().to_string(),
+                        });
+                    }
+                }
+                Event::Start(Tag::Table(_)) => mark(&mut exempt, start_line, end_line),
+                Event::Start(Tag::List(first)) => ordered_stack.push(first.is_some()),
+                Event::End(Tag::List(_)) => {
+                    ordered_stack.pop();
+                }
+                Event::Start(Tag::Item) if ordered_stack.last() == Some(&false) => {
+                    let symbol = source[range.start..]
+                        .chars()
+                        .find(|ch| !ch.is_whitespace() && *ch != '>');
+                    if let Some(symbol @ ('-' | '*' | '+')) = symbol {
+                        bullets.push(Marker {
+                            line: start_line,
+                            symbol,
+                        });
+                    }
+                }
+                Event::Start(Tag::Emphasis) | Event::Start(Tag::Strong) => {
+                    if let Some(symbol @ ('*' | '_')) = source[range.start..].chars().next() {
+                        emphasis.push(Marker {
+                            line: start_line,
+                            symbol,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let headings = snapshot
+            .sections
+            .iter()
+            .map(|section| {
+                let range = &section.heading.byte_range;
+                HeadingSpan {
+                    text: section.heading.normalized.clone(),
+                    start_line: byte_to_line(range.start, offsets),
+                    end_line: byte_to_line(range.end.saturating_sub(1).max(range.start), offsets),
+                }
+            })
+            .collect();
+
+        StyleScan {
+            lines,
+            exempt,
+            front_matter_end: front_matter.map(|front_matter| front_matter.end_line),
+            headings,
+            fences,
+            bullets,
+            emphasis,
+        }
+    }
+
+    /// Last line of the YAML front matter block, if the file has one.
+    pub(super) fn front_matter_end(&self) -> Option<usize> {
+        self.front_matter_end
+    }
+
+    fn is_exempt(&self, line: usize) -> bool {
+        self.exempt.get(line).copied().unwrap_or(false)
+    }
+
+    fn is_blank(&self, line: usize) -> bool {
+        self.lines
+            .get(line.wrapping_sub(1))
+            .map(|text| {
+                text.trim_start_matches(|ch: char| ch.is_whitespace() || ch == '>')
+                    .is_empty()
+            })
+            .unwrap_or(true)
+    }
+
+    /// Whether `line` must be preceded by a blank line (not required at the top of
+    /// the file or directly after front matter).
+    fn needs_blank_before(&self, line: usize) -> bool {
+        line > 1 && self.front_matter_end != Some(line - 1)
+    }
+}
+
+fn mark(exempt: &mut [bool], start: usize, end: usize) {
+    for line in start..=end {
+        if let Some(slot) = exempt.get_mut(line) {
+            *slot = true;
+        }
+    }
+}
+
+pub(super) fn evaluate_line_length(
+    snapshot: &FileSnapshot,
+    env: &LintEnvironment,
+) -> Vec<RuleFinding> {
+    let scan = snapshot.style();
+    let max = env.config.lint.style.max_line_length;
+    scan.lines
+        .iter()
+        .enumerate()
+        .map(|(idx, text)| (idx + 1, text))
+        .filter(|(line, _)| !scan.is_exempt(*line))
+        .filter_map(|(line, text)| {
+            let length = text.chars().count();
+            if length <= max || is_link_only(text) {
+                return None;
+            }
+            // Long URLs cannot be wrapped, so they do not count against the limit.
+            let without_urls = url_pattern().replace_all(text, "");
+            if without_urls.chars().count() <= max {
+                return None;
+            }
+            Some(RuleFinding {
+                line,
+                message: format!("Line length {length} exceeds maximum of {max}"),
+            })
+        })
+        .collect()
+}
+
+/// URL spans on a line: inline link destinations, autolinks, and bare URLs.
+fn url_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"\]\([^)\s]*(?:\s+[^)]*)?\)|<[A-Za-z][\w+.-]*:[^>\s]*>|[A-Za-z][\w+.-]*://[^\s)>\]]+",
+        )
+        .expect("valid URL pattern")
+    })
+}
+
+/// Whether the line holds nothing but a link, image, reference definition, or URL
+/// (optionally inside a list item or block quote).
+fn is_link_only(text: &str) -> bool {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN
+        .get_or_init(|| {
+            Regex::new(
+                r"^\s*(?:(?:[-*+]|\d+[.)])\s+|>\s*)*(?:!?\[[^\]]*\]\([^)]*\)|\[[^\]]+\]:\s*\S+|<[^>\s]+>|\S+://\S+)\s*$",
+            )
+            .expect("valid link-only pattern")
+        })
+        .is_match(text)
+}
+
+pub(super) fn evaluate_trailing_whitespace(
+    snapshot: &FileSnapshot,
+    env: &LintEnvironment,
+) -> Vec<RuleFinding> {
+    let scan = snapshot.style();
+    let allow_hard_breaks = env.config.lint.style.allow_hard_breaks;
+    scan.lines
+        .iter()
+        .enumerate()
+        .map(|(idx, text)| (idx + 1, text))
+        .filter(|(line, _)| !scan.is_exempt(*line))
+        .filter_map(|(line, text)| {
+            let trimmed = text.trim_end_matches([' ', '\t']);
+            let trailing = &text[trimmed.len()..];
+            if trailing.is_empty() {
+                return None;
+            }
+            if allow_hard_breaks && trailing == "  " && !trimmed.trim().is_empty() {
+                return None;
+            }
+            Some(RuleFinding {
+                line,
+                message: format!(
+                    "Trailing whitespace ({} character{})",
+                    trailing.len(),
+                    if trailing.len() == 1 { "" } else { "s" }
+                ),
+            })
+        })
+        .collect()
+}
+
+pub(super) fn evaluate_heading_punctuation(
+    snapshot: &FileSnapshot,
+    env: &LintEnvironment,
+) -> Vec<RuleFinding> {
+    let punctuation = &env.config.lint.style.heading_punctuation;
+    snapshot
+        .style()
+        .headings
+        .iter()
+        .filter_map(|heading| {
+            let last = heading.text.trim_end().chars().last()?;
+            punctuation.contains(last).then(|| RuleFinding {
+                line: heading.start_line,
+                message: format!(
+                    "Heading '{}' ends with punctuation '{}'",
+                    heading.text, last
+                ),
+            })
+        })
+        .collect()
+}
+
+pub(super) fn evaluate_list_marker_style(
+    snapshot: &FileSnapshot,
+    env: &LintEnvironment,
+) -> Vec<RuleFinding> {
+    let bullets = &snapshot.style().bullets;
+    let Some(expected) = env
+        .config
+        .lint
+        .style
+        .list_marker
+        .marker()
+        .or_else(|| bullets.first().map(|marker| marker.symbol))
+    else {
+        return Vec::new();
+    };
+
+    bullets
+        .iter()
+        .filter(|marker| marker.symbol != expected)
+        .map(|marker| RuleFinding {
+            line: marker.line,
+            message: format!("List marker '{}' should be '{}'", marker.symbol, expected),
+        })
+        .collect()
+}
+
+pub(super) fn evaluate_emphasis_style(
+    snapshot: &FileSnapshot,
+    env: &LintEnvironment,
+) -> Vec<RuleFinding> {
+    let emphasis = &snapshot.style().emphasis;
+    let Some(expected) = env
+        .config
+        .lint
+        .style
+        .emphasis
+        .delimiter()
+        .or_else(|| emphasis.first().map(|marker| marker.symbol))
+    else {
+        return Vec::new();
+    };
+
+    emphasis
+        .iter()
+        .filter(|marker| marker.symbol != expected)
+        .map(|marker| RuleFinding {
+            line: marker.line,
+            message: format!(
+                "Emphasis delimiter '{}' should be '{}'",
+                marker.symbol, expected
+            ),
+        })
+        .collect()
+}
+
+pub(super) fn evaluate_blanks_around_headings(
+    snapshot: &FileSnapshot,
+    _env: &LintEnvironment,
+) -> Vec<RuleFinding> {
+    let scan = snapshot.style();
+    let mut findings = Vec::new();
+    for heading in &scan.headings {
+        if scan.needs_blank_before(heading.start_line) && !scan.is_blank(heading.start_line - 1) {
+            findings.push(RuleFinding {
+                line: heading.start_line,
+                message: format!(
+                    "Heading '{}' should be preceded by a blank line",
+                    heading.text
+                ),
+            });
+        }
+        if heading.end_line < scan.lines.len() && !scan.is_blank(heading.end_line + 1) {
+            findings.push(RuleFinding {
+                line: heading.end_line,
+                message: format!(
+                    "Heading '{}' should be followed by a blank line",
+                    heading.text
+                ),
+            });
+        }
+    }
+    findings
+}
+
+pub(super) fn evaluate_blanks_around_fences(
+    snapshot: &FileSnapshot,
+    _env: &LintEnvironment,
+) -> Vec<RuleFinding> {
+    let scan = snapshot.style();
+    let mut findings = Vec::new();
+    for fence in &scan.fences {
+        if scan.needs_blank_before(fence.start_line) && !scan.is_blank(fence.start_line - 1) {
+            findings.push(RuleFinding {
+                line: fence.start_line,
+                message: "Fenced code block should be preceded by a blank line".into(),
+            });
+        }
+        if fence.end_line < scan.lines.len() && !scan.is_blank(fence.end_line + 1) {
+            findings.push(RuleFinding {
+                line: fence.end_line,
+                message: "Fenced code block should be followed by a blank line".into(),
+            });
+        }
+    }
+    findings
+}
+
+pub(super) fn evaluate_fenced_code_language(
+    snapshot: &FileSnapshot,
+    _env: &LintEnvironment,
+) -> Vec<RuleFinding> {
+    snapshot
+        .style()
+        .fences
+        .iter()
+        .filter(|fence| fence.language.is_empty())
+        .map(|fence| RuleFinding {
+            line: fence.start_line,
+            message: "Fenced code block is missing a language".into(),
+        })
+        .collect()
+}
@@ -0,0 +1,300 @@
+//! Inline suppression directives embedded as HTML comments.
+//!
+//! Supported forms (rule lists are optional; omitting them targets every rule):
+//!
+//! - `<!-- markdown-doc-disable broken-anchors -->` … `<!-- markdown-doc-enable -->`
+//! - `<!-- markdown-doc-disable-next-line broken-links -->`
+//! - `<!-- markdown-doc-disable-file toc-sync -->`
+
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use pulldown_cmark::{Event, Options, Parser};
+use regex::Regex;
+
+use crate::lines::byte_to_line;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DirectiveKind {
+    Disable,
+    Enable,
+    DisableNextLine,
+    DisableFile,
+}
+
+impl DirectiveKind {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "disable" => Some(DirectiveKind::Disable),
+            "enable" => Some(DirectiveKind::Enable),
+            "disable-next-line" => Some(DirectiveKind::DisableNextLine),
+            "disable-file" => Some(DirectiveKind::DisableFile),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DirectiveKind::Disable => "disable",
+            DirectiveKind::Enable => "enable",
+            DirectiveKind::DisableNextLine => "disable-next-line",
+            DirectiveKind::DisableFile => "disable-file",
+        }
+    }
+}
+
+/// Rules covered by a suppression region.
+#[derive(Clone, Debug)]
+enum RuleSet {
+    /// Every rule except the listed ones (re-enabled after the region opened).
+    All {
+        except: BTreeSet<String>,
+    },
+    Only(BTreeSet<String>),
+}
+
+impl RuleSet {
+    fn from_names(names: Option<BTreeSet<String>>) -> Self {
+        match names {
+            Some(names) => RuleSet::Only(names),
+            None => RuleSet::All {
+                except: BTreeSet::new(),
+            },
+        }
+    }
+
+    fn contains(&self, rule: &str) -> bool {
+        match self {
+            RuleSet::All { except } => !except.contains(rule),
+            RuleSet::Only(names) => names.contains(rule),
+        }
+    }
+}
+
+/// A suppression directive found in the document.
+#[derive(Clone, Debug)]
+struct Directive {
+    kind: DirectiveKind,
+    line: usize,
+    rules: Option<BTreeSet<String>>,
+    used: bool,
+}
+
+impl Directive {
+    fn describe(&self) -> String {
+        let mut text = format!("markdown-doc-{}", self.kind.as_str());
+        if let Some(rules) = &self.rules {
+            for rule in rules {
+                text.push(' ');
+                text.push_str(rule);
+            }
+        }
+        text
+    }
+}
+
+/// Half-open line range `[start, end)` where a directive suppresses findings.
+#[derive(Clone, Debug)]
+struct Region {
+    directive: usize,
+    rules: RuleSet,
+    start: usize,
+    end: Option<usize>,
+}
+
+impl Region {
+    fn covers(&self, line: usize) -> bool {
+        line >= self.start && self.end.map(|end| line < end).unwrap_or(true)
+    }
+}
+
+/// Suppression state for a single file.
+#[derive(Debug, Default)]
+pub(super) struct Suppressions {
+    directives: Vec<Directive>,
+    regions: Vec<Region>,
+}
+
+/// Unused suppression directive reported back to the lint engine.
+pub(super) struct UnusedSuppression {
+    pub line: usize,
+    pub directive: String,
+}
+
+/// Rule name in a suppression directive that matches no built-in or custom rule.
+pub(super) struct UnknownSuppressionRule {
+    pub line: usize,
+    pub directive: String,
+    pub rule: String,
+}
+
+impl Suppressions {
+    /// Collect suppression directives from HTML comments, ignoring code blocks.
+    pub(super) fn parse(contents: &str, line_offsets: &[usize]) -> Self {
+        let mut suppressions = Suppressions::default();
+        if !contents.contains("markdown-doc-") {
+            return suppressions;
+        }
+
+        let parser = Parser::new_ext(contents, Options::all());
+        for (event, range) in parser.into_offset_iter() {
+            let Event::Html(html) = event else {
+                continue;
+            };
+            for captures in directive_pattern().captures_iter(&html) {
+                let Some(kind) = DirectiveKind::parse(&captures[1]) else {
+                    continue;
+                };
+                let offset = range.start + captures.get(0).map(|m| m.start()).unwrap_or(0);
+                let line = byte_to_line(offset, line_offsets);
+                let rules: BTreeSet<String> = captures
+                    .get(2)
+                    .map(|m| m.as_str())
+                    .unwrap_or("")
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                let rules = if rules.is_empty() { None } else { Some(rules) };
+                suppressions.push(kind, line, rules);
+            }
+        }
+
+        suppressions
+    }
+
+    fn push(&mut self, kind: DirectiveKind, line: usize, rules: Option<BTreeSet<String>>) {
+        let index = self.directives.len();
+        match kind {
+            DirectiveKind::Disable => self.regions.push(Region {
+                directive: index,
+                rules: RuleSet::from_names(rules.clone()),
+                start: line,
+                end: None,
+            }),
+            DirectiveKind::DisableNextLine => self.regions.push(Region {
+                directive: index,
+                rules: RuleSet::from_names(rules.clone()),
+                start: line + 1,
+                end: Some(line + 2),
+            }),
+            DirectiveKind::DisableFile => self.regions.push(Region {
+                directive: index,
+                rules: RuleSet::from_names(rules.clone()),
+                start: 0,
+                end: None,
+            }),
+            DirectiveKind::Enable => self.enable(line, rules.as_ref()),
+        }
+        self.directives.push(Directive {
+            kind,
+            line,
+            rules,
+            used: false,
+        });
+    }
+
+    /// Close open `disable` regions for the given rules (or all rules).
+    fn enable(&mut self, line: usize, rules: Option<&BTreeSet<String>>) {
+        let mut reopened = Vec::new();
+        for region in &mut self.regions {
+            if region.end.is_some() {
+                continue;
+            }
+            let Some(names) = rules else {
+                region.end = Some(line);
+                continue;
+            };
+
+            let remaining = match &region.rules {
+                RuleSet::Only(current) => {
+                    if current.is_disjoint(names) {
+                        continue;
+                    }
+                    let remaining: BTreeSet<String> = current.difference(names).cloned().collect();
+                    if remaining.is_empty() {
+                        None
+                    } else {
+                        Some(RuleSet::Only(remaining))
+                    }
+                }
+                RuleSet::All { except } => {
+                    if names.is_subset(except) {
+                        continue;
+                    }
+                    Some(RuleSet::All {
+                        except: except.union(names).cloned().collect(),
+                    })
+                }
+            };
+
+            region.end = Some(line);
+            if let Some(rules) = remaining {
+                reopened.push(Region {
+                    directive: region.directive,
+                    rules,
+                    start: line,
+                    end: None,
+                });
+            }
+        }
+        self.regions.extend(reopened);
+    }
+
+    /// Returns true when a finding for `rule` at `line` is suppressed, marking the
+    /// responsible directives as used.
+    pub(super) fn suppresses(&mut self, rule: &str, line: usize) -> bool {
+        let mut suppressed = false;
+        for region in &self.regions {
+            if region.covers(line) && region.rules.contains(rule) {
+                self.directives[region.directive].used = true;
+                suppressed = true;
+            }
+        }
+        suppressed
+    }
+
+    /// Disable directives that did not suppress any finding. Directives naming an
+    /// unknown rule are left to [`Suppressions::unknown_rules`].
+    pub(super) fn unused(&self, is_known: impl Fn(&str) -> bool) -> Vec<UnusedSuppression> {
+        self.directives
+            .iter()
+            .filter(|directive| directive.kind != DirectiveKind::Enable && !directive.used)
+            .filter(|directive| directive.rules.iter().flatten().all(|rule| is_known(rule)))
+            .map(|directive| UnusedSuppression {
+                line: directive.line,
+                directive: directive.describe(),
+            })
+            .collect()
+    }
+
+    /// Rule names that `is_known` rejects, one entry per name and directive.
+    pub(super) fn unknown_rules(
+        &self,
+        is_known: impl Fn(&str) -> bool,
+    ) -> Vec<UnknownSuppressionRule> {
+        self.directives
+            .iter()
+            .flat_map(|directive| {
+                directive
+                    .rules
+                    .iter()
+                    .flatten()
+                    .filter(|rule| !is_known(rule))
+                    .map(|rule| UnknownSuppressionRule {
+                        line: directive.line,
+                        directive: directive.describe(),
+                        rule: rule.clone(),
+                    })
+            })
+            .collect()
+    }
+}
+
+fn directive_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"<!--\s*markdown-doc-(disable-next-line|disable-file|disable|enable)(?:\s+([^>]*?))?\s*-->")
+            .expect("valid suppression pattern")
+    })
+}
@@ -0,0 +1,147 @@
+//! Batch move mappings for `markdown-doc mv --map`.
+//!
+//! A mapping is either CSV (`source,destination` per row, optional header, `#`
+//! comments) or JSON: an array of `{"from": ..., "to": ...}` objects, or an object
+//! mapping each source to its destination.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::refactor::rewrite::FileMove;
+use crate::OperationError;
+
+const HEADER_NAMES: [(&str, &str); 3] = [("from", "to"), ("source", "destination"), ("old", "new")];
+
+/// Read a move mapping file; `.json` files are parsed as JSON, anything else as CSV.
+pub fn load_move_map(path: &Path) -> Result<Vec<FileMove>, OperationError> {
+    let contents = fs::read_to_string(path).map_err(|source| OperationError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let moves = if is_json {
+        parse_json(&contents)
+    } else {
+        parse_csv(&contents)
+    }
+    .map_err(|message| {
+        OperationError::InvalidInput(format!("invalid move map '{}': {message}", path.display()))
+    })?;
+
+    if moves.is_empty() {
+        return Err(OperationError::InvalidInput(format!(
+            "move map '{}' does not contain any moves",
+            path.display()
+        )));
+    }
+    Ok(moves)
+}
+
+fn parse_json(contents: &str) -> Result<Vec<FileMove>, String> {
+    let value: Value = serde_json::from_str(contents).map_err(|err| err.to_string())?;
+    match value {
+        Value::Array(entries) => entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let field = |name: &str| {
+                    entry
+                        .get(name)
+                        .and_then(Value::as_str)
+                        .filter(|value| !value.is_empty())
+                        .map(PathBuf::from)
+                        .ok_or_else(|| format!("entry {} is missing a string '{name}'", idx + 1))
+                };
+                Ok(FileMove {
+                    from: field("from")?,
+                    to: field("to")?,
+                })
+            })
+            .collect(),
+        Value::Object(entries) => entries
+            .iter()
+            .map(|(from, to)| match to.as_str() {
+                Some(to) if !to.is_empty() => Ok(FileMove {
+                    from: PathBuf::from(from),
+                    to: PathBuf::from(to),
+                }),
+                _ => Err(format!("destination for '{from}' must be a string")),
+            })
+            .collect(),
+        _ => Err("expected an array of {\"from\", \"to\"} objects or an object".into()),
+    }
+}
+
+fn parse_csv(contents: &str) -> Result<Vec<FileMove>, String> {
+    let mut moves = Vec::new();
+    let mut first_row = true;
+    for (idx, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let fields =
+            split_csv_row(trimmed).map_err(|message| format!("line {}: {message}", idx + 1))?;
+        let [from, to] = fields.as_slice() else {
+            return Err(format!(
+                "line {}: expected 'source,destination', found {} field(s)",
+                idx + 1,
+                fields.len()
+            ));
+        };
+        let is_header = first_row
+            && HEADER_NAMES.iter().any(|(source, destination)| {
+                from.eq_ignore_ascii_case(source) && to.eq_ignore_ascii_case(destination)
+            });
+        first_row = false;
+        if is_header {
+            continue;
+        }
+        if from.is_empty() || to.is_empty() {
+            return Err(format!(
+                "line {}: source and destination must not be empty",
+                idx + 1
+            ));
+        }
+        moves.push(FileMove {
+            from: PathBuf::from(from),
+            to: PathBuf::from(to),
+        });
+    }
+    Ok(moves)
+}
+
+/// Split one CSV row, honouring double-quoted fields (`""` escapes a quote).
+fn split_csv_row(row: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = row.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(ch),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".into());
+    }
+    fields.push(field.trim().to_string());
+    Ok(fields)
+}
@@ -0,0 +1,181 @@
+//! Orphaned and unreachable document detection built on the link graph.
+
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use globset::{GlobSet, GlobSetBuilder};
+use markdown_doc_config::{OrphanSettings, PatternList};
+
+use crate::paths::normalize_path;
+use crate::refactor::graph::LinkGraph;
+
+/// Documents that are not linked from anywhere or cannot be reached from a root.
+#[derive(Clone, Debug, Default)]
+pub struct OrphanReport {
+    /// Entry points resolved from `lint.orphan_roots` that exist in the scanned set.
+    pub roots: Vec<PathBuf>,
+    /// Documents with no inbound links from any other document.
+    pub orphans: Vec<PathBuf>,
+    /// Documents with inbound links that are still unreachable from every root.
+    pub unreachable: Vec<PathBuf>,
+}
+
+impl OrphanReport {
+    /// True when neither orphaned nor unreachable documents were found.
+    pub fn is_empty(&self) -> bool {
+        self.orphans.is_empty() && self.unreachable.is_empty()
+    }
+
+    /// Describe why `path` was reported, if it was.
+    pub(crate) fn reason_for(&self, path: &Path) -> Option<String> {
+        if self.orphans.iter().any(|orphan| orphan == path) {
+            return Some("Document is not linked from any other document".to_string());
+        }
+        if self.unreachable.iter().any(|entry| entry == path) {
+            let roots = self
+                .roots
+                .iter()
+                .map(|root| root.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Some(format!("Document is not reachable from any root ({roots})"));
+        }
+        None
+    }
+}
+
+/// Analyse `graph` for orphaned and unreachable documents.
+pub(crate) fn analyze(graph: &LinkGraph, settings: &OrphanSettings, root: &Path) -> OrphanReport {
+    let files: BTreeSet<PathBuf> = graph
+        .files()
+        .map(|entry| entry.path().to_path_buf())
+        .collect();
+    let root_set = build_globset(&settings.roots);
+    let allow_set = build_globset(&settings.allow);
+
+    let mut roots: BTreeSet<PathBuf> = files
+        .iter()
+        .filter(|path| root_set.is_match(path))
+        .cloned()
+        .collect();
+    for pattern in settings.roots.iter() {
+        let original = pattern.original();
+        if original.ends_with(".yml") || original.ends_with(".yaml") {
+            for page in mkdocs_nav_pages(root, Path::new(original)) {
+                if files.contains(&page) {
+                    roots.insert(page);
+                }
+            }
+        }
+    }
+
+    let mut inbound: HashSet<PathBuf> = HashSet::new();
+    for source in &files {
+        inbound.extend(outbound_targets(graph, source));
+    }
+
+    let mut reachable: HashSet<PathBuf> = roots.iter().cloned().collect();
+    let mut queue: VecDeque<PathBuf> = roots.iter().cloned().collect();
+    while let Some(current) = queue.pop_front() {
+        for target in outbound_targets(graph, &current) {
+            if files.contains(&target) && reachable.insert(target.clone()) {
+                queue.push_back(target);
+            }
+        }
+    }
+
+    let mut orphans = Vec::new();
+    let mut unreachable = Vec::new();
+    for path in &files {
+        if roots.contains(path) || allow_set.is_match(path) {
+            continue;
+        }
+        if !inbound.contains(path) {
+            orphans.push(path.clone());
+        } else if !roots.is_empty() && !reachable.contains(path) {
+            unreachable.push(path.clone());
+        }
+    }
+
+    OrphanReport {
+        roots: roots.into_iter().collect(),
+        orphans,
+        unreachable,
+    }
+}
+
+/// Distinct documents linked from `source`, excluding self-references.
+fn outbound_targets(graph: &LinkGraph, source: &Path) -> Vec<PathBuf> {
+    let Some(entry) = graph.file(source) else {
+        return Vec::new();
+    };
+    let targets = entry
+        .links()
+        .iter()
+        .filter_map(|link| link.target.as_ref())
+        .chain(
+            entry
+                .definitions()
+                .iter()
+                .filter_map(|definition| definition.target.as_ref()),
+        )
+        .filter_map(|target| target.path.clone())
+        .filter(|path| path != source);
+
+    let mut seen = BTreeSet::new();
+    targets.filter(|path| seen.insert(path.clone())).collect()
+}
+
+fn build_globset(patterns: &PatternList) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns.iter() {
+        builder.add(pattern.glob().clone());
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+/// Extract Markdown pages listed under `nav:` in an mkdocs config, relative to the
+/// project root (honouring `docs_dir`, default `docs`).
+fn mkdocs_nav_pages(root: &Path, config: &Path) -> Vec<PathBuf> {
+    let Ok(contents) = std::fs::read_to_string(root.join(config)) else {
+        return Vec::new();
+    };
+
+    let base = config.parent().unwrap_or(Path::new(""));
+    let mut docs_dir = PathBuf::from("docs");
+    let mut pages = Vec::new();
+    let mut in_nav = false;
+
+    for line in contents.lines() {
+        let top_level = !line.starts_with(char::is_whitespace) && !line.starts_with('-');
+        if top_level {
+            in_nav = line.trim_end() == "nav:";
+            if let Some(value) = line.strip_prefix("docs_dir:") {
+                docs_dir = PathBuf::from(unquote(value));
+            }
+            continue;
+        }
+        if !in_nav {
+            continue;
+        }
+        let value = line
+            .trim()
+            .trim_start_matches('-')
+            .rsplit_once(':')
+            .map(|(_, value)| value)
+            .unwrap_or_else(|| line.trim().trim_start_matches('-'));
+        let value = unquote(value);
+        if value.ends_with(".md") || value.ends_with(".markdown") {
+            pages.push(value.to_string());
+        }
+    }
+
+    pages
+        .into_iter()
+        .map(|page| normalize_path(base.join(&docs_dir).join(page)))
+        .collect()
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim().trim_matches(|c| c == '"' || c == '\'')
+}
@@ -0,0 +1,233 @@
+//! Outbound link listing for `markdown-doc refs --from`.
+//!
+//! Internal links and reference definitions come from the link graph
+//! ([`LinkGraph::links_from`] / [`LinkGraph::definitions_from`]) and are checked
+//! against it; external URLs are not part of the graph and are collected with a
+//! separate pass over the document.
+
+use std::path::{Path, PathBuf};
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+use crate::anchors::normalize_anchor_fragment;
+use crate::lines::byte_to_line;
+use crate::paths::is_external;
+use crate::refactor::graph::{LinkGraph, LinkKind, LinkTarget};
+use crate::ScanOptions;
+
+/// Options for listing the links a document (or directory of documents) makes.
+pub struct OutboundOptions {
+    pub scan: ScanOptions,
+    /// Markdown file or directory, relative to the project root.
+    pub from: PathBuf,
+    /// Merge every document under `from` into a single group.
+    pub aggregate: bool,
+}
+
+/// Outbound links per source document (or one group with `aggregate`).
+#[derive(Clone, Debug, Default)]
+pub struct OutboundReport {
+    pub groups: Vec<OutboundGroup>,
+    /// Links whose target file or anchor does not exist, across all groups.
+    pub unresolved_count: usize,
+}
+
+/// Links from one document, or from every document under a directory.
+#[derive(Clone, Debug, Default)]
+pub struct OutboundGroup {
+    pub path: PathBuf,
+    pub resolved: Vec<OutboundLink>,
+    pub unresolved: Vec<OutboundLink>,
+    pub external: Vec<OutboundLink>,
+}
+
+#[derive(Clone, Debug)]
+pub struct OutboundLink {
+    pub source: PathBuf,
+    pub line: usize,
+    pub kind: OutboundLinkKind,
+    /// Destination as written in the document.
+    pub raw_target: String,
+    pub target_path: Option<PathBuf>,
+    pub target_anchor: Option<String>,
+    pub status: OutboundStatus,
+    pub display: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutboundLinkKind {
+    Link,
+    Image,
+    Definition,
+}
+
+impl OutboundLinkKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutboundLinkKind::Link => "link",
+            OutboundLinkKind::Image => "image",
+            OutboundLinkKind::Definition => "definition",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutboundStatus {
+    /// The target exists and, when the target is a scanned document, so does the anchor.
+    Resolved,
+    MissingFile,
+    MissingAnchor,
+    External,
+}
+
+impl OutboundStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutboundStatus::Resolved => "resolved",
+            OutboundStatus::MissingFile => "missing-file",
+            OutboundStatus::MissingAnchor => "missing-anchor",
+            OutboundStatus::External => "external",
+        }
+    }
+}
+
+/// Check `target` against the graph: files outside it must exist on disk, and
+/// anchors are verified for scanned documents.
+pub(crate) fn classify(graph: &LinkGraph, root: &Path, target: &LinkTarget) -> OutboundStatus {
+    let Some(path) = &target.path else {
+        return OutboundStatus::Resolved;
+    };
+    if graph.file(path).is_none() {
+        return if root.join(path).exists() {
+            OutboundStatus::Resolved
+        } else {
+            OutboundStatus::MissingFile
+        };
+    }
+    match target.anchor.as_deref() {
+        Some(anchor)
+            if !anchor.is_empty()
+                && !graph
+                    .anchors_in(path)
+                    .iter()
+                    .any(|record| normalize_anchor_fragment(&record.slug) == anchor) =>
+        {
+            OutboundStatus::MissingAnchor
+        }
+        _ => OutboundStatus::Resolved,
+    }
+}
+
+/// Collect and group the outbound links of `sources`.
+pub(crate) fn collect(
+    graph: &LinkGraph,
+    root: &Path,
+    from: &Path,
+    sources: &[PathBuf],
+    aggregate: bool,
+) -> OutboundReport {
+    let mut report = OutboundReport::default();
+    for source in sources {
+        let Some(entry) = graph.file(source) else {
+            continue;
+        };
+        let display = |line: usize| entry.line_text(line).unwrap_or_default();
+
+        let mut links = Vec::new();
+        let internal = graph
+            .links_from(source)
+            .into_iter()
+            .filter_map(|link| {
+                let kind = match link.kind {
+                    LinkKind::Inline => OutboundLinkKind::Link,
+                    LinkKind::Image => OutboundLinkKind::Image,
+                    // Reported once, through the definition it resolves to.
+                    LinkKind::Reference => return None,
+                };
+                Some((kind, link.line, link.raw_target, link.target?))
+            })
+            .chain(
+                graph
+                    .definitions_from(source)
+                    .into_iter()
+                    .filter_map(|definition| {
+                        Some((
+                            OutboundLinkKind::Definition,
+                            definition.line,
+                            definition.raw_target,
+                            definition.target?,
+                        ))
+                    }),
+            );
+        for (kind, line, raw_target, target) in internal {
+            links.push(OutboundLink {
+                source: source.clone(),
+                line,
+                kind,
+                raw_target,
+                status: classify(graph, root, &target),
+                target_path: target.path,
+                target_anchor: target.anchor,
+                display: display(line),
+            });
+        }
+
+        for (event, range) in Parser::new_ext(entry.contents(), Options::all()).into_offset_iter() {
+            let (dest, kind) = match event {
+                Event::Start(Tag::Link(_, dest, _)) => (dest, OutboundLinkKind::Link),
+                Event::Start(Tag::Image(_, dest, _)) => (dest, OutboundLinkKind::Image),
+                _ => continue,
+            };
+            if !is_external(&dest) {
+                continue;
+            }
+            let line = byte_to_line(range.start, entry.line_offsets());
+            links.push(OutboundLink {
+                source: source.clone(),
+                line,
+                kind,
+                raw_target: dest.to_string(),
+                target_path: None,
+                target_anchor: None,
+                status: OutboundStatus::External,
+                display: display(line),
+            });
+        }
+
+        if aggregate {
+            if report.groups.is_empty() {
+                report.groups.push(OutboundGroup {
+                    path: from.to_path_buf(),
+                    ..OutboundGroup::default()
+                });
+            }
+        } else {
+            report.groups.push(OutboundGroup {
+                path: source.clone(),
+                ..OutboundGroup::default()
+            });
+        }
+        let group = report.groups.last_mut().expect("group pushed above");
+        for link in links {
+            match link.status {
+                OutboundStatus::Resolved => group.resolved.push(link),
+                OutboundStatus::MissingFile | OutboundStatus::MissingAnchor => {
+                    report.unresolved_count += 1;
+                    group.unresolved.push(link);
+                }
+                OutboundStatus::External => group.external.push(link),
+            }
+        }
+    }
+
+    for group in &mut report.groups {
+        for links in [
+            &mut group.resolved,
+            &mut group.unresolved,
+            &mut group.external,
+        ] {
+            links.sort_by(|a, b| a.source.cmp(&b.source).then(a.line.cmp(&b.line)));
+        }
+    }
+    report
+}
@@ -5,7 +5,7 @@ use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use markdown_doc_parser::ParserContext;
+use markdown_doc_parser::{html_anchors, ParserContext};
 use pulldown_cmark::{Event, LinkType, Options, Parser, Tag};
 
 use crate::anchors::normalize_anchor_fragment;
@@ -26,20 +26,35 @@ impl LinkGraph {
         root: &Path,
         files: &[PathBuf],
     ) -> Result<LinkGraph, OperationError> {
+        let documents = files
+            .iter()
+            .map(|relative| {
+                let absolute = root.join(relative);
+                let contents =
+                    std::fs::read_to_string(&absolute).map_err(|source| OperationError::Io {
+                        path: absolute.clone(),
+                        source,
+                    })?;
+                Ok((relative.clone(), contents))
+            })
+            .collect::<Result<Vec<_>, OperationError>>()?;
+        Ok(LinkGraph::from_documents(parser, root, documents))
+    }
+
+    /// Build a link graph from documents whose contents are already loaded, such as
+    /// staged contents read from the git index.
+    pub(crate) fn from_documents(
+        parser: &ParserContext,
+        root: &Path,
+        documents: Vec<(PathBuf, String)>,
+    ) -> LinkGraph {
         let mut file_map = HashMap::new();
         let mut backrefs: HashMap<TargetKey, Vec<LinkLocation>> = HashMap::new();
 
-        for relative in files {
-            let absolute = root.join(relative);
-            let contents =
-                std::fs::read_to_string(&absolute).map_err(|source| OperationError::Io {
-                    path: absolute.clone(),
-                    source,
-                })?;
-
+        for (relative, contents) in documents {
             let contents_arc = Arc::new(contents);
             let line_offsets = Arc::new(compute_line_offsets(&contents_arc));
-            let sections = parser.sections_from_str(relative, &contents_arc);
+            let sections = parser.sections_from_str(&relative, &contents_arc);
 
             let anchors = sections
                 .iter()
@@ -49,14 +64,27 @@ impl LinkGraph {
                     line: byte_to_line(section.heading.byte_range.start, &line_offsets),
                     depth: section.heading.depth,
                     byte_range: section.heading.byte_range.clone(),
+                    kind: AnchorKind::Heading,
                 })
+                .chain(
+                    html_anchors(&contents_arc)
+                        .into_iter()
+                        .map(|anchor| AnchorRecord {
+                            normalized: anchor.id.clone(),
+                            slug: anchor.id,
+                            line: byte_to_line(anchor.byte_range.start, &line_offsets),
+                            depth: 0,
+                            byte_range: anchor.byte_range,
+                            kind: AnchorKind::Html,
+                        }),
+                )
                 .collect::<Vec<_>>();
 
             let inline_links =
-                collect_inline_links(&contents_arc, &line_offsets, relative, root, &mut backrefs);
+                collect_inline_links(&contents_arc, &line_offsets, &relative, root, &mut backrefs);
 
             let reference_definitions =
-                collect_reference_definitions(&contents_arc, relative, root, &mut backrefs);
+                collect_reference_definitions(&contents_arc, &relative, root, &mut backrefs);
 
             let entry = FileGraphEntry {
                 path: relative.clone(),
@@ -66,13 +94,13 @@ impl LinkGraph {
                 links: inline_links,
                 definitions: reference_definitions,
             };
-            file_map.insert(relative.clone(), entry);
+            file_map.insert(relative, entry);
         }
 
-        Ok(LinkGraph {
+        LinkGraph {
             files: file_map,
             backrefs,
-        })
+        }
     }
 
     /// Return the graph entry for `path`, if tracked.
@@ -218,14 +246,25 @@ impl FileGraphEntry {
     }
 }
 
-/// Representation of a heading anchor discovered within a file.
+/// Representation of an anchor discovered within a file.
 #[derive(Clone, Debug)]
 pub struct AnchorRecord {
     pub slug: String,
     pub normalized: String,
     pub line: usize,
+    /// Heading depth; `0` for HTML anchors.
     pub depth: usize,
     pub byte_range: Range<usize>,
+    pub kind: AnchorKind,
+}
+
+/// Where an anchor was declared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnchorKind {
+    /// Heading anchor, either slugged from the text or set with `{#id}`.
+    Heading,
+    /// HTML `<a id>` / `<a name>` target.
+    Html,
 }
 
 /// Normalised link target (path + optional anchor fragment).
@@ -62,6 +62,9 @@ pub enum RewriteError {
 
 /// Plan file move rewrites by analysing the link graph and computing the edits
 /// required to keep relative paths accurate.
+///
+/// Moves may name files that are not part of the graph (e.g. images); only the
+/// links pointing at them are rewritten.
 pub fn plan_file_moves(
     graph: &LinkGraph,
     root: &Path,
@@ -72,11 +75,6 @@ pub fn plan_file_moves(
 
     for mv in moves {
         let from = canonicalize_path(mv.from.clone());
-        // Ensure the moved file is represented in the graph so we can update outbound links.
-        let file_entry = graph
-            .file(&from)
-            .ok_or_else(|| RewriteError::MissingFile { path: from.clone() })?;
-
         let target_new = resolve_future_path(&from, &move_map);
 
         // Update inbound references pointing at the moved file.
@@ -84,6 +82,12 @@ pub fn plan_file_moves(
             plan_inbound_edit(occurrence, &move_map, root, &target_new, &mut edits)?;
         }
 
+        // Files outside the graph (images and other assets) have no links of their own;
+        // the caller relocates them without a `FileEdit`.
+        let Some(file_entry) = graph.file(&from) else {
+            continue;
+        };
+
         // Update outbound references within the moved file itself.
         plan_outbound_edits(file_entry, &from, &move_map, root, &mut edits)?;
 
@@ -112,7 +116,7 @@ pub fn plan_file_moves(
             continue;
         }
 
-        file_edits_list.sort_by(|a, b| b.range.start.cmp(&a.range.start));
+        file_edits_list.sort_by_key(|edit| std::cmp::Reverse(edit.range.start));
         let updated_contents = apply_edits(entry.contents(), &file_edits_list, &path)?;
         let output_path = resolve_future_path(&path, &move_map);
 
@@ -268,23 +272,12 @@ fn compute_replacement(
     }
 
     let future_source = resolve_future_path(source_path, move_map);
-    let source_abs = root.join(&future_source);
-    let source_dir = source_abs.parent().unwrap_or(root);
-    let target_abs = root.join(future_target);
-
-    let mut path_string = if path_part.starts_with('/') {
-        format!("/{}", to_markdown_path(future_target))
-    } else {
-        match relative_path(source_dir, &target_abs) {
-            Some(relative) => to_markdown_path(&relative),
-            None => {
-                return Err(RewriteError::RelativePathFailure {
-                    from: future_source,
-                    to: future_target.to_path_buf(),
-                })
-            }
-        }
-    };
+    let mut path_string = link_path(
+        root,
+        &future_source,
+        future_target,
+        path_part.starts_with('/'),
+    )?;
 
     if let Some(anchor) = anchor_part {
         path_string.push('#');
@@ -294,6 +287,28 @@ fn compute_replacement(
     Ok(path_string)
 }
 
+/// Path portion of a link written in `source` that points at `target` (both relative
+/// to `root`): root-absolute (`/docs/a.md`) when `absolute`, otherwise relative to
+/// the source's directory.
+pub(crate) fn link_path(
+    root: &Path,
+    source: &Path,
+    target: &Path,
+    absolute: bool,
+) -> Result<String, RewriteError> {
+    if absolute {
+        return Ok(format!("/{}", to_markdown_path(target)));
+    }
+    let source_abs = root.join(source);
+    let source_dir = source_abs.parent().unwrap_or(root);
+    relative_path(source_dir, &root.join(target))
+        .map(|relative| to_markdown_path(&relative))
+        .ok_or_else(|| RewriteError::RelativePathFailure {
+            from: source.to_path_buf(),
+            to: target.to_path_buf(),
+        })
+}
+
 fn record_edit(
     edits: &mut HashMap<PathBuf, Vec<TextEdit>>,
     path: PathBuf,
@@ -316,7 +331,12 @@ fn record_edit(
     Ok(())
 }
 
-fn apply_edits(original: &str, edits: &[TextEdit], path: &Path) -> Result<String, RewriteError> {
+/// Apply non-overlapping `edits`, which must be sorted by descending start offset.
+pub(crate) fn apply_edits(
+    original: &str,
+    edits: &[TextEdit],
+    path: &Path,
+) -> Result<String, RewriteError> {
     let mut updated = original.to_string();
     for edit in edits {
         if edit.range.end > updated.len() || edit.range.start > edit.range.end {
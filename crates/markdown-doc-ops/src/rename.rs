@@ -0,0 +1,224 @@
+//! Heading renames for `markdown-doc rename-heading`.
+//!
+//! Renaming a heading changes its anchor (and can shift the duplicate suffixes of
+//! later headings with the same text), so every anchor that changes is looked up
+//! with [`LinkGraph::links_to`] and the links pointing at it are rewritten.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use markdown_doc_parser::DocumentSection;
+
+use crate::anchors::normalize_anchor_fragment;
+use crate::paths::split_link_target;
+use crate::refactor::graph::{LinkGraph, LinkOccurrence};
+use crate::refactor::rewrite::{RewriteError, TextEdit};
+use crate::split::apply_sorted;
+use crate::{
+    apply_planned_writes, build_diff, is_markdown_path, resolve_input_path, MvFileChange,
+    MvFileStatus, MvOutcome, OperationError, Operations, PlannedWrite, ScanOptions,
+};
+
+/// Options for the rename-heading command.
+pub struct RenameHeadingOptions {
+    pub scan: ScanOptions,
+    pub path: PathBuf,
+    /// Current heading text, or `#anchor` to pick a heading by its anchor.
+    pub heading: String,
+    /// Replacement heading text (inline Markdown allowed).
+    pub new_heading: String,
+    pub dry_run: bool,
+    pub create_backup: bool,
+}
+
+impl Operations {
+    /// Rename a heading and rewrite every link to the anchors that change.
+    pub fn rename_heading(
+        &self,
+        options: RenameHeadingOptions,
+    ) -> Result<MvOutcome, OperationError> {
+        let root = &self.config.project.root;
+        let (path, absolute) = resolve_input_path(root, &options.path, true, "file")?;
+        if !absolute.is_file() || !is_markdown_path(&path.to_string_lossy()) {
+            return Err(OperationError::InvalidInput(format!(
+                "'{}' is not a Markdown file",
+                path.display()
+            )));
+        }
+        let new_heading = options.new_heading.trim();
+        if new_heading.is_empty() || new_heading.contains('\n') {
+            return Err(OperationError::InvalidInput(
+                "new heading must be a single non-empty line".into(),
+            ));
+        }
+
+        let mut targets = self.collect_targets(&options.scan)?;
+        targets.push(path.clone());
+        targets.sort();
+        targets.dedup();
+        let graph = LinkGraph::build(&self.parser, root, &targets)?;
+        let contents = graph
+            .file(&path)
+            .map(|entry| entry.contents().to_string())
+            .ok_or_else(|| RewriteError::MissingFile { path: path.clone() })?;
+
+        let before = self.parser.sections_from_str(&path, &contents);
+        let index = find_heading(&before, &options.heading, &path)?;
+        let heading = &before[index].heading;
+        if heading.raw == new_heading {
+            return Err(OperationError::InvalidInput(format!(
+                "heading '{}' is already named '{new_heading}'",
+                heading.raw
+            )));
+        }
+
+        // Replace the text only, keeping markers, closing hashes, and `{#id}` blocks.
+        let line = &contents[heading.byte_range.clone()];
+        let offset = line.find(heading.raw.as_str()).ok_or_else(|| {
+            OperationError::Other(format!(
+                "could not locate heading text '{}' in '{}'",
+                heading.raw,
+                path.display()
+            ))
+        })?;
+        let start = heading.byte_range.start + offset;
+        let heading_edit = TextEdit {
+            range: start..start + heading.raw.len(),
+            replacement: new_heading.to_string(),
+        };
+        let renamed = apply_sorted(&contents, vec![heading_edit.clone()], &path)?;
+
+        let after = self.parser.sections_from_str(&path, &renamed);
+        if after.len() != before.len() {
+            return Err(OperationError::InvalidInput(format!(
+                "renaming to '{new_heading}' changes the document's heading structure"
+            )));
+        }
+        let renamed_anchors: HashMap<String, String> = before
+            .iter()
+            .zip(&after)
+            .filter(|(old, new)| old.heading.anchor != new.heading.anchor)
+            .map(|(old, new)| {
+                (
+                    normalize_anchor_fragment(&old.heading.anchor),
+                    new.heading.anchor.clone(),
+                )
+            })
+            .collect();
+
+        let mut edits: HashMap<PathBuf, Vec<TextEdit>> = HashMap::new();
+        edits.entry(path.clone()).or_default().push(heading_edit);
+        for (old, new) in &renamed_anchors {
+            for occurrence in graph.links_to(&path, Some(old)) {
+                let (source, raw, span) = match occurrence {
+                    LinkOccurrence::Inline { source, link } => {
+                        (source, link.raw_target, link.destination_span)
+                    }
+                    LinkOccurrence::Definition { source, definition } => {
+                        (source, definition.raw_target, definition.destination_span)
+                    }
+                };
+                let Some(span) = span else {
+                    continue;
+                };
+                let (path_part, _) = split_link_target(&raw);
+                edits.entry(source).or_default().push(TextEdit {
+                    range: span,
+                    replacement: format!("{path_part}#{new}"),
+                });
+            }
+        }
+
+        let mut writes = Vec::new();
+        for (file, file_edits) in edits {
+            let original = graph
+                .file(&file)
+                .map(|entry| entry.contents().to_string())
+                .ok_or_else(|| RewriteError::MissingFile { path: file.clone() })?;
+            let updated = apply_sorted(&original, file_edits, &file)?;
+            writes.push(PlannedWrite {
+                path: file,
+                original: Some(original),
+                updated: Some(updated),
+            });
+        }
+        writes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let changes = writes
+            .iter()
+            .map(|write| MvFileChange {
+                original_path: write.path.clone(),
+                output_path: write.path.clone(),
+                status: MvFileStatus::Updated,
+                diff: options.dry_run.then(|| {
+                    build_diff(
+                        &write.path,
+                        write.original.as_deref().unwrap_or(""),
+                        write.updated.as_deref().unwrap_or(""),
+                    )
+                }),
+            })
+            .collect();
+
+        if !options.dry_run {
+            apply_planned_writes(root, &writes, options.create_backup)?;
+        }
+        Ok(MvOutcome {
+            changes,
+            exit_code: 0,
+            dry_run: options.dry_run,
+            dirty: Vec::new(),
+        })
+    }
+}
+
+/// Index of the heading named `pattern`: `#anchor`, or the heading text (exact
+/// first, then ignoring case). Several matches are an error.
+fn find_heading(
+    sections: &[DocumentSection],
+    pattern: &str,
+    path: &Path,
+) -> Result<usize, OperationError> {
+    let matching = |predicate: &dyn Fn(&DocumentSection) -> bool| -> Vec<usize> {
+        sections
+            .iter()
+            .enumerate()
+            .filter(|(_, section)| predicate(section))
+            .map(|(idx, _)| idx)
+            .collect()
+    };
+    let pattern = pattern.trim();
+    let mut matches = match pattern.strip_prefix('#') {
+        Some(anchor) => {
+            let anchor = normalize_anchor_fragment(anchor);
+            matching(&|section| normalize_anchor_fragment(&section.heading.anchor) == anchor)
+        }
+        None => matching(&|section| {
+            section.heading.raw == pattern || section.heading.normalized == pattern
+        }),
+    };
+    if matches.is_empty() && !pattern.starts_with('#') {
+        matches = matching(&|section| {
+            section.heading.raw.eq_ignore_ascii_case(pattern)
+                || section.heading.normalized.eq_ignore_ascii_case(pattern)
+        });
+    }
+
+    match matches.as_slice() {
+        [index] => Ok(*index),
+        [] => Err(OperationError::InvalidInput(format!(
+            "no heading '{pattern}' in '{}'",
+            path.display()
+        ))),
+        several => Err(OperationError::InvalidInput(format!(
+            "'{pattern}' matches {} headings in '{}' ({}); use #anchor to pick one",
+            several.len(),
+            path.display(),
+            several
+                .iter()
+                .map(|idx| format!("#{}", sections[*idx].heading.anchor))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
@@ -0,0 +1,255 @@
+//! Persistent search index stored at `search.index` (`.markdown-doc/index.json` by default).
+//!
+//! The index keeps one entry per document: its size, modification time, and content
+//! hash, plus the text and term frequencies of every section. On refresh an entry is
+//! reused while the file's size and mtime are unchanged, or while its content hash still
+//! matches; other files are re-parsed. The whole index is rebuilt when the tool version
+//! or the anchor dialect changes.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use markdown_doc_parser::ParserContext;
+use markdown_doc_utils::{atomic_write, content_hash};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::tokens;
+use crate::front_matter::front_matter_end;
+use crate::lines::{byte_to_line, compute_line_offsets};
+use crate::OperationError;
+
+/// Bumped whenever the on-disk layout or the meaning of an entry changes.
+const FORMAT_VERSION: u32 = 1;
+/// Heading terms count this many times towards a section's term frequencies.
+const HEADING_WEIGHT: u32 = 3;
+
+#[derive(Default, Deserialize, Serialize)]
+pub(crate) struct SearchIndex {
+    version: u32,
+    tool_version: String,
+    fingerprint: String,
+    pub(super) files: BTreeMap<String, IndexedFile>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub(super) struct IndexedFile {
+    content_hash: String,
+    size: u64,
+    /// Modification time in nanoseconds since the Unix epoch.
+    modified: Option<u128>,
+    pub(super) sections: Vec<IndexedSection>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub(super) struct IndexedSection {
+    /// Heading text; `None` for content before the first heading.
+    pub(super) heading: Option<String>,
+    pub(super) anchor: Option<String>,
+    /// Line of the heading (or of the first line for leading content).
+    pub(super) line: usize,
+    /// Line on which `text` starts.
+    pub(super) body_line: usize,
+    /// Section body up to the next heading of any level.
+    pub(super) text: String,
+    /// Number of (weighted) terms in the section.
+    pub(super) length: u32,
+    pub(super) terms: BTreeMap<String, u32>,
+}
+
+/// What a refresh did to the index.
+pub(crate) struct RefreshSummary {
+    pub(crate) refreshed: usize,
+    pub(crate) changed: bool,
+}
+
+impl SearchIndex {
+    /// Load the index at `path`, discarding it when it was built by another tool version
+    /// or with a different anchor dialect.
+    pub(crate) fn load(path: &Path, fingerprint: &str) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<SearchIndex>(&contents).ok())
+            .filter(|index| {
+                index.version == FORMAT_VERSION
+                    && index.tool_version == env!("CARGO_PKG_VERSION")
+                    && index.fingerprint == fingerprint
+            })
+            .unwrap_or_else(|| SearchIndex {
+                version: FORMAT_VERSION,
+                tool_version: env!("CARGO_PKG_VERSION").to_string(),
+                fingerprint: fingerprint.to_string(),
+                files: BTreeMap::new(),
+            })
+    }
+
+    /// Bring the index in line with `targets`, re-parsing only files that changed.
+    pub(crate) fn refresh(
+        &mut self,
+        root: &Path,
+        parser: &ParserContext,
+        targets: &[PathBuf],
+    ) -> Result<RefreshSummary, OperationError> {
+        let previous = std::mem::take(&mut self.files);
+        let updated = targets
+            .par_iter()
+            .map(|path| {
+                let name = index_name(path);
+                let absolute = root.join(path);
+                let metadata =
+                    std::fs::metadata(&absolute).map_err(|source| OperationError::Io {
+                        path: path.clone(),
+                        source,
+                    })?;
+                let size = metadata.len();
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .map(|elapsed| elapsed.as_nanos());
+
+                let cached = previous.get(&name);
+                if let Some(entry) = cached {
+                    if entry.size == size && entry.modified.is_some() && entry.modified == modified
+                    {
+                        return Ok((name, entry.clone(), false));
+                    }
+                }
+
+                let contents =
+                    std::fs::read_to_string(&absolute).map_err(|source| OperationError::Io {
+                        path: path.clone(),
+                        source,
+                    })?;
+                let hash = content_hash(contents.as_bytes());
+                if let Some(entry) = cached.filter(|entry| entry.content_hash == hash) {
+                    let mut entry = entry.clone();
+                    entry.size = size;
+                    entry.modified = modified;
+                    // Only the timestamp changed, but it must be persisted to keep the fast path.
+                    return Ok((name, entry, true));
+                }
+
+                let entry = IndexedFile {
+                    content_hash: hash,
+                    size,
+                    modified,
+                    sections: index_sections(parser, path, &contents),
+                };
+                Ok((name, entry, true))
+            })
+            .collect::<Result<Vec<_>, OperationError>>()?;
+
+        let mut refreshed = 0;
+        let mut changed = false;
+        for (name, entry, touched) in updated {
+            changed |= touched;
+            if touched
+                && previous.get(&name).map(|old| &old.content_hash) != Some(&entry.content_hash)
+            {
+                refreshed += 1;
+            }
+            self.files.insert(name, entry);
+        }
+        // Deleted documents also require a rewrite.
+        changed |= self.files.len() != previous.len();
+
+        Ok(RefreshSummary { refreshed, changed })
+    }
+
+    /// Persist the index, returning its size in bytes.
+    pub(crate) fn save(&self, path: &Path) -> Result<u64, OperationError> {
+        let serialized = serde_json::to_string(self)?;
+        atomic_write(path, &serialized)?;
+        Ok(serialized.len() as u64)
+    }
+
+    pub(crate) fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    pub(crate) fn section_count(&self) -> usize {
+        self.files.values().map(|file| file.sections.len()).sum()
+    }
+}
+
+/// Split a document into sections that end where the next heading (of any level) starts,
+/// so nested content is only indexed once.
+fn index_sections(parser: &ParserContext, path: &Path, contents: &str) -> Vec<IndexedSection> {
+    let sections = parser.sections_from_str(path, contents);
+    let offsets = compute_line_offsets(contents);
+    let mut indexed = Vec::new();
+
+    let first_heading = sections
+        .first()
+        .map_or(contents.len(), |section| section.heading.byte_range.start);
+    let start = front_matter_end(contents).min(first_heading);
+    let leading = contents.get(start..first_heading).unwrap_or("");
+    if !leading.trim().is_empty() {
+        let line = byte_to_line(start, &offsets);
+        indexed.push(build_section(None, None, line, line, leading));
+    }
+
+    for (idx, section) in sections.iter().enumerate() {
+        let end = sections
+            .get(idx + 1)
+            .map_or(contents.len(), |next| next.heading.byte_range.start);
+        let body_start = section.heading.byte_range.end.min(end);
+        indexed.push(build_section(
+            Some(section.heading.normalized.clone()),
+            Some(section.heading.anchor.clone()),
+            byte_to_line(section.heading.byte_range.start, &offsets),
+            byte_to_line(body_start, &offsets),
+            contents.get(body_start..end).unwrap_or(""),
+        ));
+    }
+    indexed
+}
+
+fn build_section(
+    heading: Option<String>,
+    anchor: Option<String>,
+    line: usize,
+    body_line: usize,
+    body: &str,
+) -> IndexedSection {
+    let mut terms: HashMap<String, u32> = HashMap::new();
+    let mut length = 0;
+    if let Some(heading) = &heading {
+        for (_, token) in tokens(heading) {
+            *terms.entry(token).or_default() += HEADING_WEIGHT;
+            length += HEADING_WEIGHT;
+        }
+    }
+    for (_, token) in tokens(body) {
+        *terms.entry(token).or_default() += 1;
+        length += 1;
+    }
+
+    // Drop the line break that ends the heading so `text` starts on `body_line`.
+    let (text, body_line) = match body
+        .strip_prefix("\r\n")
+        .or_else(|| body.strip_prefix('\n'))
+    {
+        Some(rest) => (rest, body_line + 1),
+        None => (body, body_line),
+    };
+
+    IndexedSection {
+        heading,
+        anchor,
+        line,
+        body_line,
+        text: text.trim_end().to_string(),
+        length,
+        terms: terms.into_iter().collect(),
+    }
+}
+
+fn index_name(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
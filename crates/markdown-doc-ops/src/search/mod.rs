@@ -0,0 +1,207 @@
+//! Section-level full-text search.
+//!
+//! Every section (heading to the next heading of any level, plus any text before the
+//! first heading) is a search document. Queries are tokenized like the index and ranked
+//! with Okapi BM25; heading terms carry extra weight. Each result carries a short
+//! snippet: the window of body lines with the most query-term hits.
+
+mod index;
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::PathBuf;
+
+use markdown_doc_parser::ParserContext;
+
+pub(crate) use self::index::SearchIndex;
+
+/// Body lines shown per result.
+const SNIPPET_LINES: usize = 3;
+/// BM25 term-frequency saturation.
+const K1: f64 = 1.2;
+/// BM25 length normalisation.
+const B: f64 = 0.75;
+
+/// A ranked search hit.
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub path: PathBuf,
+    /// Section heading; `None` for text before the first heading.
+    pub heading: Option<String>,
+    pub anchor: Option<String>,
+    pub line: usize,
+    pub score: f64,
+    /// Query terms found in the section.
+    pub terms: Vec<String>,
+    pub snippet: Vec<SnippetLine>,
+}
+
+/// One line of a result snippet.
+#[derive(Clone, Debug)]
+pub struct SnippetLine {
+    pub line: usize,
+    pub text: String,
+    /// Byte ranges of `text` matching a query term.
+    pub highlights: Vec<(usize, usize)>,
+}
+
+/// Index size and freshness reported alongside search results.
+#[derive(Clone, Debug, Default)]
+pub struct SearchIndexStats {
+    pub path: PathBuf,
+    pub files: usize,
+    pub sections: usize,
+    /// Documents (re-)parsed during this run.
+    pub refreshed: usize,
+    /// Size of the index file on disk.
+    pub bytes: u64,
+}
+
+/// Lowercased alphanumeric runs of at least two characters, with their byte ranges.
+pub(crate) fn tokens(text: &str) -> impl Iterator<Item = (Range<usize>, String)> + '_ {
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || loop {
+        let (start, first) = chars.next()?;
+        if !first.is_alphanumeric() {
+            continue;
+        }
+        let mut end = start + first.len_utf8();
+        while let Some(&(idx, ch)) = chars.peek() {
+            if !ch.is_alphanumeric() {
+                break;
+            }
+            end = idx + ch.len_utf8();
+            chars.next();
+        }
+        let token = &text[start..end];
+        if token.chars().nth(1).is_some() {
+            return Some((start..end, token.to_lowercase()));
+        }
+    })
+}
+
+/// Fingerprint of the settings that change what the index stores.
+pub(crate) fn index_fingerprint(parser: &ParserContext) -> String {
+    format!("{:?}", parser.config().project.anchor_dialect)
+}
+
+/// Rank indexed sections against `query`, restricted to `allowed` documents when given.
+pub(crate) fn rank(
+    index: &SearchIndex,
+    query: &str,
+    allowed: Option<&HashSet<PathBuf>>,
+    limit: usize,
+) -> Vec<SearchResult> {
+    let mut terms: Vec<String> = Vec::new();
+    for (_, token) in tokens(query) {
+        if !terms.contains(&token) {
+            terms.push(token);
+        }
+    }
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    // Statistics cover the whole index so scores do not depend on `--path` filters.
+    let mut total_length = 0u64;
+    let mut section_count = 0usize;
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for section in index.files.values().flat_map(|file| &file.sections) {
+        section_count += 1;
+        total_length += u64::from(section.length);
+        for term in &terms {
+            if section.terms.contains_key(term) {
+                *document_frequency.entry(term).or_default() += 1;
+            }
+        }
+    }
+    if section_count == 0 {
+        return Vec::new();
+    }
+    let average_length = total_length as f64 / section_count as f64;
+    let idf: HashMap<&str, f64> = document_frequency
+        .iter()
+        .map(|(term, &df)| {
+            let n = section_count as f64;
+            let df = df as f64;
+            (*term, ((n - df + 0.5) / (df + 0.5) + 1.0).ln())
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for (name, file) in &index.files {
+        let path = PathBuf::from(name);
+        if allowed.is_some_and(|allowed| !allowed.contains(&path)) {
+            continue;
+        }
+        for section in &file.sections {
+            let mut score = 0.0;
+            let mut matched = Vec::new();
+            for term in &terms {
+                let Some(&frequency) = section.terms.get(term) else {
+                    continue;
+                };
+                let tf = f64::from(frequency);
+                let norm = K1 * (1.0 - B + B * f64::from(section.length) / average_length);
+                score += idf[term.as_str()] * tf * (K1 + 1.0) / (tf + norm);
+                matched.push(term.clone());
+            }
+            if matched.is_empty() {
+                continue;
+            }
+            results.push(SearchResult {
+                path: path.clone(),
+                heading: section.heading.clone(),
+                anchor: section.anchor.clone(),
+                line: section.line,
+                score,
+                snippet: snippet(&section.text, section.body_line, &matched),
+                terms: matched,
+            });
+        }
+    }
+
+    results.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    results.truncate(limit);
+    results
+}
+
+/// Pick the window of non-blank body lines with the most matches; sections that only
+/// match in their heading show their first lines.
+fn snippet(text: &str, first_line: usize, terms: &[String]) -> Vec<SnippetLine> {
+    let lines: Vec<SnippetLine> = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(offset, line)| SnippetLine {
+            line: first_line + offset,
+            text: line.trim_end().to_string(),
+            highlights: highlights(line, terms),
+        })
+        .collect();
+    if lines.len() <= SNIPPET_LINES {
+        return lines;
+    }
+
+    let hits: Vec<usize> = lines.iter().map(|line| line.highlights.len()).collect();
+    let best = (0..=lines.len() - SNIPPET_LINES)
+        .max_by_key(|&start| {
+            let window: usize = hits[start..start + SNIPPET_LINES].iter().sum();
+            // Prefer earlier windows on ties.
+            (window, std::cmp::Reverse(start))
+        })
+        .unwrap_or(0);
+    lines[best..best + SNIPPET_LINES].to_vec()
+}
+
+fn highlights(line: &str, terms: &[String]) -> Vec<(usize, usize)> {
+    tokens(line)
+        .filter(|(_, token)| terms.contains(token))
+        .map(|(range, _)| (range.start, range.end))
+        .collect()
+}
@@ -0,0 +1,531 @@
+//! Section-level refactoring for `markdown-doc split` and `markdown-doc merge`.
+//!
+//! Both commands move a block of Markdown to the end of another document: `split`
+//! moves one section (with its subsections) out of a file, `merge` folds a whole
+//! file into another and removes it. Heading depths are adjusted to fit the
+//! destination, and every link to a moved anchor, found through the [`LinkGraph`]
+//! backrefs, is redirected to the anchor's new location.
+
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use markdown_doc_parser::{DocumentSection, HeadingKind, ParserContext};
+use regex::RegexBuilder;
+
+use crate::anchors::normalize_anchor_fragment;
+use crate::front_matter::front_matter_end;
+use crate::paths::split_link_target;
+use crate::refactor::graph::LinkGraph;
+use crate::refactor::rewrite::{apply_edits, link_path, RewriteError, TextEdit};
+use crate::{
+    apply_planned_writes, build_diff, is_markdown_path, resolve_input_path, MvFileChange,
+    MvFileStatus, MvOutcome, OperationError, Operations, PlannedWrite, ScanOptions,
+};
+
+/// Options for the split command.
+pub struct SplitOptions {
+    pub scan: ScanOptions,
+    pub source: PathBuf,
+    /// Case-insensitive regex matched against heading text, or `#anchor` to pick a
+    /// heading by its anchor. Must select exactly one section.
+    pub section: String,
+    /// Document receiving the section; created when missing, appended to otherwise.
+    pub destination: PathBuf,
+    /// Leave the heading behind with a link to the section's new location.
+    pub stub: bool,
+    pub dry_run: bool,
+    pub create_backup: bool,
+}
+
+/// Options for the merge command.
+pub struct MergeOptions {
+    pub scan: ScanOptions,
+    /// Document appended to `destination` and then removed.
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    pub dry_run: bool,
+    pub create_backup: bool,
+}
+
+/// Block of Markdown leaving `source` for the end of `destination`.
+struct SectionMove {
+    source: PathBuf,
+    /// Bytes of `source` being moved.
+    range: Range<usize>,
+    destination: PathBuf,
+    /// Keep the first moved heading in `source`, followed by a link to its new home.
+    stub: bool,
+    /// Delete `source` afterwards; links to the file itself then point at the moved text.
+    remove_source: bool,
+}
+
+impl Operations {
+    /// Move one section of a document (with its subsections) into another document.
+    pub fn split(&self, options: SplitOptions) -> Result<MvOutcome, OperationError> {
+        let (source, destination) =
+            self.section_move_paths(&options.source, &options.destination, false)?;
+        let contents = self.read_document(&source)?;
+        let sections = self.parser.sections_from_str(&source, &contents);
+        let section = select_section(&sections, &options.section, &source)?;
+
+        self.move_section(
+            &options.scan,
+            SectionMove {
+                source,
+                range: section.byte_range.clone(),
+                destination,
+                stub: options.stub,
+                remove_source: false,
+            },
+            options.dry_run,
+            options.create_backup,
+        )
+    }
+
+    /// Append a whole document (minus front matter) to another and remove it.
+    pub fn merge(&self, options: MergeOptions) -> Result<MvOutcome, OperationError> {
+        let (source, destination) =
+            self.section_move_paths(&options.source, &options.destination, true)?;
+        let contents = self.read_document(&source)?;
+        let start = front_matter_end(&contents);
+        if contents[start..].trim().is_empty() {
+            return Err(OperationError::InvalidInput(format!(
+                "'{}' has no content to merge",
+                source.display()
+            )));
+        }
+
+        self.move_section(
+            &options.scan,
+            SectionMove {
+                source,
+                range: start..contents.len(),
+                destination,
+                stub: false,
+                remove_source: true,
+            },
+            options.dry_run,
+            options.create_backup,
+        )
+    }
+
+    fn section_move_paths(
+        &self,
+        source: &Path,
+        destination: &Path,
+        destination_must_exist: bool,
+    ) -> Result<(PathBuf, PathBuf), OperationError> {
+        let root = &self.config.project.root;
+        let (source_rel, source_abs) = resolve_input_path(root, source, true, "source")?;
+        if !source_abs.is_file() || !is_markdown_path(&source_rel.to_string_lossy()) {
+            return Err(OperationError::InvalidInput(format!(
+                "source '{}' is not a Markdown file",
+                source_rel.display()
+            )));
+        }
+        let (dest_rel, dest_abs) =
+            resolve_input_path(root, destination, destination_must_exist, "destination")?;
+        if !is_markdown_path(&dest_rel.to_string_lossy()) {
+            return Err(OperationError::InvalidInput(format!(
+                "destination '{}' must end with .md or .markdown",
+                dest_rel.display()
+            )));
+        }
+        if dest_abs.is_dir() {
+            return Err(OperationError::InvalidInput(format!(
+                "destination '{}' is a directory",
+                dest_rel.display()
+            )));
+        }
+        if source_rel == dest_rel {
+            return Err(OperationError::InvalidInput(
+                "source and destination refer to the same path".into(),
+            ));
+        }
+        Ok((source_rel, dest_rel))
+    }
+
+    fn read_document(&self, path: &Path) -> Result<String, OperationError> {
+        let absolute = self.config.project.root.join(path);
+        fs::read_to_string(&absolute).map_err(|source| OperationError::Io {
+            path: absolute,
+            source,
+        })
+    }
+
+    fn move_section(
+        &self,
+        scan: &ScanOptions,
+        mv: SectionMove,
+        dry_run: bool,
+        create_backup: bool,
+    ) -> Result<MvOutcome, OperationError> {
+        let root = &self.config.project.root;
+        let dest_exists = root.join(&mv.destination).is_file();
+
+        let mut targets = self.collect_targets(scan)?;
+        targets.push(mv.source.clone());
+        if dest_exists {
+            targets.push(mv.destination.clone());
+        }
+        targets.sort();
+        targets.dedup();
+        let graph = LinkGraph::build(&self.parser, root, &targets)?;
+        let contents_of = |path: &Path| {
+            graph
+                .file(path)
+                .map(|entry| entry.contents().to_string())
+                .ok_or_else(|| {
+                    OperationError::Rewrite(RewriteError::MissingFile {
+                        path: path.to_path_buf(),
+                    })
+                })
+        };
+        let source_contents = contents_of(&mv.source)?;
+        let dest_contents = if dest_exists {
+            contents_of(&mv.destination)?
+        } else {
+            String::new()
+        };
+
+        let moved = &source_contents[mv.range.clone()];
+        let moved_headings: Vec<DocumentSection> = self
+            .parser
+            .sections_from_str(&mv.source, &source_contents)
+            .into_iter()
+            .filter(|section| mv.range.contains(&section.heading.byte_range.start))
+            .collect();
+
+        // Anchors depend on the headings already in the destination (duplicate
+        // suffixes), so slug the moved headings in place at the end of it.
+        let prefix = destination_prefix(&dest_contents);
+        let draft = format!("{prefix}{moved}");
+        let landed: Vec<DocumentSection> = self
+            .parser
+            .sections_from_str(&mv.destination, &draft)
+            .into_iter()
+            .filter(|section| section.heading.byte_range.start >= prefix.len())
+            .collect();
+        if landed.len() != moved_headings.len() {
+            return Err(OperationError::Other(format!(
+                "could not map the headings moved from '{}' to '{}'",
+                mv.source.display(),
+                mv.destination.display()
+            )));
+        }
+        let anchors: HashMap<String, String> = moved_headings
+            .iter()
+            .zip(&landed)
+            .map(|(old, new)| {
+                (
+                    normalize_anchor_fragment(&old.heading.anchor),
+                    new.heading.anchor.clone(),
+                )
+            })
+            .collect();
+        let file_anchor = landed
+            .first()
+            .filter(|_| mv.remove_source)
+            .map(|section| section.heading.anchor.clone());
+
+        let mut edits = plan_link_edits(&graph, root, &mv, &anchors, file_anchor.as_deref())?;
+
+        // Moved text: links first (edit offsets are relative to the source), then depths.
+        let (inside, mut outside): (Vec<TextEdit>, Vec<TextEdit>) = edits
+            .remove(&mv.source)
+            .unwrap_or_default()
+            .into_iter()
+            .partition(|edit| mv.range.contains(&edit.range.start));
+        let inside: Vec<TextEdit> = inside
+            .into_iter()
+            .map(|edit| TextEdit {
+                range: edit.range.start - mv.range.start..edit.range.end - mv.range.start,
+                replacement: edit.replacement,
+            })
+            .collect();
+        let mut moved_text = apply_sorted(moved, inside, &mv.source)?;
+        let dest_depth = self
+            .parser
+            .sections_from_str(&mv.destination, &dest_contents)
+            .iter()
+            .map(|section| section.heading.depth)
+            .min();
+        if let Some(top) = moved_headings.iter().map(|s| s.heading.depth).min() {
+            let target = dest_depth.map_or(1, |depth| (depth + 1).min(6));
+            moved_text = shift_headings(
+                &self.parser,
+                &mv.destination,
+                &moved_text,
+                target as isize - top as isize,
+            );
+        }
+        moved_text.truncate(moved_text.trim_end().len());
+        moved_text.push('\n');
+
+        let leftover = match (mv.stub, moved_headings.first(), landed.first()) {
+            (true, Some(old), Some(new)) => {
+                let heading = source_contents[old.heading.byte_range.clone()].trim_end();
+                let link = link_path(root, &mv.source, &mv.destination, false)?;
+                let mut stub = format!(
+                    "{heading}\n\nMoved to [{}]({link}#{}).\n",
+                    old.heading.normalized, new.heading.anchor
+                );
+                if mv.range.end < source_contents.len() {
+                    stub.push('\n');
+                }
+                stub
+            }
+            _ => String::new(),
+        };
+        outside.push(TextEdit {
+            range: mv.range.clone(),
+            replacement: leftover,
+        });
+        let updated_source = apply_sorted(&source_contents, outside, &mv.source)?;
+
+        let dest_edits = edits.remove(&mv.destination).unwrap_or_default();
+        let updated_dest = format!(
+            "{}{moved_text}",
+            destination_prefix(&apply_sorted(&dest_contents, dest_edits, &mv.destination)?)
+        );
+
+        let mut writes = vec![
+            PlannedWrite {
+                path: mv.source.clone(),
+                original: Some(source_contents),
+                updated: (!mv.remove_source).then_some(updated_source),
+            },
+            PlannedWrite {
+                path: mv.destination.clone(),
+                original: dest_exists.then_some(dest_contents),
+                updated: Some(updated_dest),
+            },
+        ];
+        for (path, file_edits) in edits {
+            let original = contents_of(&path)?;
+            let updated = apply_sorted(&original, file_edits, &path)?;
+            writes.push(PlannedWrite {
+                path,
+                original: Some(original),
+                updated: Some(updated),
+            });
+        }
+        writes.retain(|write| write.original != write.updated);
+        writes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let changes = writes
+            .iter()
+            .map(|write| {
+                let before = write.original.as_deref().unwrap_or("");
+                let after = write.updated.as_deref().unwrap_or("");
+                MvFileChange {
+                    original_path: write.path.clone(),
+                    output_path: write.path.clone(),
+                    status: match (&write.original, &write.updated) {
+                        (None, _) => MvFileStatus::Created,
+                        (_, None) => MvFileStatus::Removed,
+                        _ => MvFileStatus::Updated,
+                    },
+                    diff: dry_run.then(|| build_diff(&write.path, before, after)),
+                }
+            })
+            .collect();
+
+        if !dry_run {
+            apply_planned_writes(root, &writes, create_backup)?;
+        }
+        Ok(MvOutcome {
+            changes,
+            exit_code: 0,
+            dry_run,
+            dirty: Vec::new(),
+        })
+    }
+}
+
+/// Pick the one section matching `pattern` (`#anchor`, or a case-insensitive regex
+/// over heading text).
+fn select_section<'a>(
+    sections: &'a [DocumentSection],
+    pattern: &str,
+    path: &Path,
+) -> Result<&'a DocumentSection, OperationError> {
+    let matches: Vec<&DocumentSection> = match pattern.strip_prefix('#') {
+        Some(anchor) => {
+            let anchor = normalize_anchor_fragment(anchor);
+            sections
+                .iter()
+                .filter(|section| normalize_anchor_fragment(&section.heading.anchor) == anchor)
+                .collect()
+        }
+        None => {
+            let regex = RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|err| {
+                    OperationError::InvalidInput(format!("invalid section pattern: {err}"))
+                })?;
+            sections
+                .iter()
+                .filter(|section| regex.is_match(&section.heading.normalized))
+                .collect()
+        }
+    };
+
+    match matches.as_slice() {
+        [section] => Ok(section),
+        [] => Err(OperationError::InvalidInput(format!(
+            "no section in '{}' matches '{pattern}'",
+            path.display()
+        ))),
+        several => Err(OperationError::InvalidInput(format!(
+            "'{pattern}' matches {} sections in '{}' ({}); use #anchor to pick one",
+            several.len(),
+            path.display(),
+            several
+                .iter()
+                .map(|section| format!("#{}", section.heading.anchor))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Link destination edits, per file, for moving `mv.range` to `mv.destination`.
+/// Edits inside the moved range are computed for the text's new location.
+fn plan_link_edits(
+    graph: &LinkGraph,
+    root: &Path,
+    mv: &SectionMove,
+    anchors: &HashMap<String, String>,
+    file_anchor: Option<&str>,
+) -> Result<HashMap<PathBuf, Vec<TextEdit>>, OperationError> {
+    let same_dir = mv.source.parent() == mv.destination.parent();
+    let mut edits: HashMap<PathBuf, Vec<TextEdit>> = HashMap::new();
+
+    for entry in graph.files() {
+        let path = entry.path();
+        let links = entry
+            .links()
+            .iter()
+            .map(|link| (&link.raw_target, &link.target, &link.destination_span))
+            .chain(entry.definitions().iter().map(|definition| {
+                (
+                    &definition.raw_target,
+                    &definition.target,
+                    &definition.destination_span,
+                )
+            }));
+
+        for (raw, target, span) in links {
+            let (Some(target), Some(span)) = (target, span) else {
+                continue;
+            };
+            let Some(target_path) = &target.path else {
+                continue;
+            };
+            let moves_with_text = path == mv.source && mv.range.contains(&span.start);
+            let (path_part, anchor_part) = split_link_target(raw);
+
+            let mut new_target = target_path;
+            let mut new_anchor = anchor_part.map(str::to_string);
+            if *target_path == mv.source {
+                if let Some(anchor) = target.anchor.as_ref().and_then(|a| anchors.get(a)) {
+                    new_target = &mv.destination;
+                    new_anchor = Some(anchor.clone());
+                } else if mv.remove_source {
+                    new_target = &mv.destination;
+                    if new_anchor.is_none() {
+                        new_anchor = file_anchor.map(str::to_string);
+                    }
+                }
+            }
+
+            let retargeted = new_target != target_path || new_anchor.as_deref() != anchor_part;
+            let rebased = moves_with_text
+                && (path_part.is_empty() || !same_dir || *target_path == mv.destination);
+            if !retargeted && !rebased {
+                continue;
+            }
+
+            let from = if moves_with_text {
+                &mv.destination
+            } else {
+                path
+            };
+            let replacement = match &new_anchor {
+                Some(anchor) if new_target == from => format!("#{anchor}"),
+                _ => {
+                    let mut text = link_path(root, from, new_target, path_part.starts_with('/'))?;
+                    if let Some(anchor) = &new_anchor {
+                        text.push('#');
+                        text.push_str(anchor);
+                    }
+                    text
+                }
+            };
+            if replacement != *raw {
+                edits.entry(path.to_path_buf()).or_default().push(TextEdit {
+                    range: span.clone(),
+                    replacement,
+                });
+            }
+        }
+    }
+
+    Ok(edits)
+}
+
+/// Apply `edits` in any order (they must not overlap).
+pub(crate) fn apply_sorted(
+    contents: &str,
+    mut edits: Vec<TextEdit>,
+    path: &Path,
+) -> Result<String, OperationError> {
+    edits.sort_by_key(|edit| std::cmp::Reverse(edit.range.start));
+    Ok(apply_edits(contents, &edits, path)?)
+}
+
+/// Existing destination text followed by a blank line, or nothing for an empty file.
+fn destination_prefix(contents: &str) -> String {
+    if contents.trim().is_empty() {
+        String::new()
+    } else {
+        format!("{}\n\n", contents.trim_end_matches(['\n', '\r']))
+    }
+}
+
+/// Change the depth of every heading in `text` by `delta`, clamped to 1..=6.
+/// Setext headings are rewritten as ATX headings.
+fn shift_headings(parser: &ParserContext, path: &Path, text: &str, delta: isize) -> String {
+    let mut shifted = text.to_string();
+    if delta == 0 {
+        return shifted;
+    }
+    for section in parser.sections_from_str(path, text).iter().rev() {
+        let heading = &section.heading;
+        let depth = (heading.depth as isize + delta).clamp(1, 6) as usize;
+        let original = &text[heading.byte_range.clone()];
+        let replacement = match heading.kind {
+            HeadingKind::Atx => format!(
+                "{}{}",
+                "#".repeat(depth),
+                original.trim_start().trim_start_matches('#')
+            ),
+            HeadingKind::Setext => {
+                let mut line = format!("{} {}", "#".repeat(depth), heading.raw);
+                if let Some(id) = &heading.explicit_id {
+                    line.push_str(&format!(" {{#{id}}}"));
+                }
+                if original.ends_with('\n') {
+                    line.push('\n');
+                }
+                line
+            }
+        };
+        shifted.replace_range(heading.byte_range.clone(), &replacement);
+    }
+    shifted
+}
@@ -0,0 +1,222 @@
+//! Documentation health metrics for `markdown-doc stats`.
+//!
+//! Per-document metrics come from the link graph (links, inbound links, anchors) and
+//! the parsed sections; lint findings, schema checks, and modification times are
+//! gathered by [`Operations::stats`](crate::Operations::stats) and folded in here.
+
+use std::path::{Path, PathBuf};
+
+use chrono::DateTime;
+use markdown_doc_parser::DocumentSection;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+use crate::catalog::word_count;
+use crate::front_matter::front_matter_end;
+use crate::lines::{byte_to_line, compute_line_offsets};
+use crate::outbound::{classify, OutboundStatus};
+use crate::paths::is_external;
+use crate::refactor::graph::{LinkGraph, LinkKind, LinkOccurrence};
+
+/// Metrics for one document.
+#[derive(Clone, Debug, Default)]
+pub struct FileStats {
+    pub path: PathBuf,
+    /// Words outside front matter.
+    pub words: usize,
+    /// Heading counts by depth (`headings[0]` counts `#` headings).
+    pub headings: [usize; 6],
+    pub links: LinkStats,
+    /// Links from other documents pointing at this one.
+    pub inbound_links: usize,
+    /// Not linked from any other document.
+    pub orphan: bool,
+    pub lint_findings: usize,
+    /// Schema the document is validated against.
+    pub schema: String,
+    pub schema_conforms: bool,
+    /// Last commit time (falling back to the file's mtime), RFC 3339.
+    pub last_modified: Option<String>,
+}
+
+/// Outbound link counts; images are not counted.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LinkStats {
+    pub internal: usize,
+    pub external: usize,
+    /// Internal links to a missing file or anchor.
+    pub broken: usize,
+}
+
+/// One of the largest sections (heading to the next heading of any level).
+#[derive(Clone, Debug)]
+pub struct SectionStats {
+    pub path: PathBuf,
+    pub heading: String,
+    pub anchor: String,
+    pub line: usize,
+    pub words: usize,
+}
+
+/// Aggregate metrics across every document in the report.
+#[derive(Clone, Debug, Default)]
+pub struct StatsSummary {
+    pub files: usize,
+    pub words: usize,
+    pub headings: [usize; 6],
+    pub links: LinkStats,
+    pub inbound_links: usize,
+    pub orphans: usize,
+    pub lint_findings: usize,
+    /// Lint findings per 1,000 words.
+    pub lint_density: f64,
+    pub schema_conforming: usize,
+    /// Share of documents passing their schema, between 0 and 1.
+    pub schema_conformance: f64,
+}
+
+/// Full `stats` result.
+#[derive(Clone, Debug, Default)]
+pub struct StatsReport {
+    /// When the report was produced (UTC, RFC 3339), for tracking metrics over time.
+    pub generated_at: String,
+    pub files: Vec<FileStats>,
+    pub summary: StatsSummary,
+    /// Largest sections by word count, largest first.
+    pub largest_sections: Vec<SectionStats>,
+    /// Documents with the oldest modification time, oldest first.
+    pub oldest: Vec<FileStats>,
+}
+
+/// Graph-derived metrics for `path`, plus its sections sized by word count.
+pub(crate) fn document_stats(
+    graph: &LinkGraph,
+    root: &Path,
+    path: &Path,
+    contents: &str,
+    sections: &[DocumentSection],
+) -> (FileStats, Vec<SectionStats>) {
+    let mut stats = FileStats {
+        path: path.to_path_buf(),
+        words: word_count(&contents[front_matter_end(contents)..]),
+        ..FileStats::default()
+    };
+
+    let offsets = compute_line_offsets(contents);
+    let mut sized = Vec::with_capacity(sections.len());
+    for (idx, section) in sections.iter().enumerate() {
+        if let Some(count) = stats
+            .headings
+            .get_mut(section.heading.depth.saturating_sub(1))
+        {
+            *count += 1;
+        }
+        let end = sections
+            .get(idx + 1)
+            .map_or(contents.len(), |next| next.heading.byte_range.start);
+        let body = contents
+            .get(section.heading.byte_range.end.min(end)..end)
+            .unwrap_or("");
+        sized.push(SectionStats {
+            path: path.to_path_buf(),
+            heading: section.heading.normalized.clone(),
+            anchor: section.heading.anchor.clone(),
+            line: byte_to_line(section.heading.byte_range.start, &offsets),
+            words: word_count(body),
+        });
+    }
+
+    if let Some(entry) = graph.file(path) {
+        for link in entry.links() {
+            if matches!(link.kind, LinkKind::Image) {
+                continue;
+            }
+            if let Some(target) = &link.target {
+                stats.links.internal += 1;
+                if classify(graph, root, target) != OutboundStatus::Resolved {
+                    stats.links.broken += 1;
+                }
+            }
+        }
+    }
+    stats.links.external = Parser::new_ext(contents, Options::all())
+        .filter(|event| matches!(event, Event::Start(Tag::Link(_, dest, _)) if is_external(dest)))
+        .count();
+
+    stats.inbound_links = graph
+        .links_to(path, None)
+        .iter()
+        .filter(|occurrence| match occurrence {
+            LinkOccurrence::Inline { source, link } => {
+                source != path && !matches!(link.kind, LinkKind::Image)
+            }
+            // Reference-style uses are already counted through their inline records.
+            LinkOccurrence::Definition { .. } => false,
+        })
+        .count();
+
+    (stats, sized)
+}
+
+/// Aggregate per-document metrics and pick the largest sections and oldest documents.
+pub(crate) fn summarize(
+    mut files: Vec<FileStats>,
+    mut sections: Vec<SectionStats>,
+    top: usize,
+) -> StatsReport {
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut summary = StatsSummary {
+        files: files.len(),
+        ..StatsSummary::default()
+    };
+    for file in &files {
+        summary.words += file.words;
+        for (total, count) in summary.headings.iter_mut().zip(file.headings) {
+            *total += count;
+        }
+        summary.links.internal += file.links.internal;
+        summary.links.external += file.links.external;
+        summary.links.broken += file.links.broken;
+        summary.inbound_links += file.inbound_links;
+        summary.orphans += usize::from(file.orphan);
+        summary.lint_findings += file.lint_findings;
+        summary.schema_conforming += usize::from(file.schema_conforms);
+    }
+    if summary.words > 0 {
+        summary.lint_density = summary.lint_findings as f64 * 1000.0 / summary.words as f64;
+    }
+    if summary.files > 0 {
+        summary.schema_conformance = summary.schema_conforming as f64 / summary.files as f64;
+    }
+
+    sections.sort_by(|a, b| {
+        b.words
+            .cmp(&a.words)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    sections.truncate(top);
+
+    let mut oldest: Vec<(i64, &FileStats)> = files
+        .iter()
+        .filter_map(|file| {
+            let time = DateTime::parse_from_rfc3339(file.last_modified.as_deref()?).ok()?;
+            Some((time.timestamp(), file))
+        })
+        .collect();
+    // Stable, so ties stay in path order.
+    oldest.sort_by_key(|(time, _)| *time);
+    let oldest = oldest
+        .into_iter()
+        .take(top)
+        .map(|(_, file)| file.clone())
+        .collect();
+
+    StatsReport {
+        generated_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        files,
+        summary,
+        largest_sections: sections,
+        oldest,
+    }
+}
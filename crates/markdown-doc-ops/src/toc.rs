@@ -1,7 +1,16 @@
-use markdown_doc_config::TocSettings;
+//! TOC block discovery and generation shared by the `toc` command and `toc-sync` lint.
+//!
+//! A start marker may carry options overriding `[lint.toc]` for its block, e.g.
+//! `<!-- toc depth=2..3 numbered exclude="^Changelog$" -->`. The first block in a file
+//! covers the whole document; later blocks cover only the section they sit in.
+
+use markdown_doc_config::{TocFormat, TocInsertPosition, TocSettings};
 use markdown_doc_parser::DocumentSection;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use regex::Regex;
 
 use crate::anchors::normalize_anchor_fragment;
+use crate::front_matter::front_matter_end;
 
 #[derive(Clone, Debug)]
 pub struct TocEntry {
@@ -10,14 +19,28 @@ pub struct TocEntry {
     pub line: usize,
 }
 
+/// Headings a block lists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TocScope {
+    /// Every heading in the document.
+    Document,
+    /// Headings nested under the section containing the block.
+    Section,
+}
+
 #[derive(Clone, Debug)]
-#[allow(dead_code)]
 pub struct TocBlock {
     pub start_line: usize,
     pub end_line: usize,
+    /// Byte offset of the start marker line.
+    pub marker_offset: usize,
     pub start_offset: usize,
     pub end_offset: usize,
     pub entries: Vec<TocEntry>,
+    pub format: TocFormat,
+    pub scope: TocScope,
+    /// Inline options that could not be applied.
+    pub errors: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -27,75 +50,196 @@ pub struct GeneratedItem {
     pub anchor: String,
 }
 
-#[allow(unused_assignments)]
-pub fn locate_block(contents: &str, settings: &TocSettings) -> Option<TocBlock> {
-    let mut in_block = false;
-    let mut start_line = 0usize;
-    let mut start_offset = 0usize;
-    let mut entries = Vec::new();
-
+/// Find every closed marker pair outside fenced code, in document order.
+pub fn locate_blocks(contents: &str, settings: &TocSettings) -> Vec<TocBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<TocBlock> = None;
+    let mut fence: Option<(char, usize)> = None;
     let mut offset = 0usize;
-    let mut line_number = 0usize;
 
-    for segment in contents.split_inclusive('\n') {
-        line_number += 1;
-        let line_len = segment.len();
+    for (index, segment) in contents.split_inclusive('\n').enumerate() {
+        let line_number = index + 1;
         let line_body = segment.trim_end_matches(&['\r', '\n'][..]);
         let trimmed = line_body.trim();
+        let line_start = offset;
+        offset += segment.len();
 
-        if !in_block {
-            if trimmed == settings.start_marker {
-                in_block = true;
-                start_line = line_number;
-                start_offset = offset + line_len;
+        if let Some(marker) = fence_marker(trimmed) {
+            match fence {
+                None => fence = Some(marker),
+                Some((ch, len)) if marker.0 == ch && marker.1 >= len => fence = None,
+                Some(_) => {}
             }
-        } else {
-            if trimmed == settings.end_marker {
-                return Some(TocBlock {
-                    start_line,
-                    end_line: line_number,
-                    start_offset,
-                    end_offset: offset,
-                    entries,
-                });
+            continue;
+        }
+        if fence.is_some() {
+            continue;
+        }
+
+        match open.take() {
+            None => {
+                if let Some(options) = start_marker_options(trimmed, &settings.start_marker) {
+                    let scope = if blocks.is_empty() {
+                        TocScope::Document
+                    } else {
+                        TocScope::Section
+                    };
+                    let mut block = TocBlock {
+                        start_line: line_number,
+                        end_line: 0,
+                        marker_offset: line_start,
+                        start_offset: offset,
+                        end_offset: 0,
+                        entries: Vec::new(),
+                        format: settings.format.clone(),
+                        scope,
+                        errors: Vec::new(),
+                    };
+                    apply_options(&mut block, options);
+                    open = Some(block);
+                }
             }
-            if let Some(entry) = parse_entry(line_body, line_number) {
-                entries.push(entry);
+            Some(mut block) => {
+                if trimmed == settings.end_marker {
+                    block.end_line = line_number;
+                    block.end_offset = line_start;
+                    blocks.push(block);
+                } else {
+                    if let Some(entry) = parse_entry(line_body, line_number) {
+                        block.entries.push(entry);
+                    }
+                    open = Some(block);
+                }
             }
         }
+    }
 
-        offset += line_len;
+    blocks
+}
+
+/// Options text following the start marker, or `None` when `line` is not a start marker.
+/// Markers ending in `-->` accept options before the closing `-->`.
+fn start_marker_options<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    if line == marker {
+        return Some("");
     }
+    let prefix = marker.strip_suffix("-->")?.trim_end();
+    let inner = line.strip_prefix(prefix)?.strip_suffix("-->")?;
+    inner.starts_with(char::is_whitespace).then(|| inner.trim())
+}
 
-    if offset < contents.len() {
-        let line = &contents[offset..];
-        if !line.is_empty() {
-            line_number += 1;
-            let trimmed = line.trim();
-            if !in_block {
-                if trimmed == settings.start_marker {
-                    in_block = true;
-                    start_line = line_number;
-                    start_offset = offset + line.len();
-                }
-            } else {
-                if trimmed == settings.end_marker {
-                    return Some(TocBlock {
-                        start_line,
-                        end_line: line_number,
-                        start_offset,
-                        end_offset: offset,
-                        entries,
-                    });
-                }
-                if let Some(entry) = parse_entry(line, line_number) {
-                    entries.push(entry);
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let ch = line.chars().next().filter(|ch| *ch == '`' || *ch == '~')?;
+    let len = line.chars().take_while(|c| *c == ch).count();
+    (len >= 3).then_some((ch, len))
+}
+
+fn apply_options(block: &mut TocBlock, options: &str) {
+    for token in split_options(options) {
+        let (key, value) = match token.split_once('=') {
+            Some((key, value)) => (key, Some(value)),
+            None => (token.as_str(), None),
+        };
+        if let Err(message) = apply_option(block, key, value) {
+            block.errors.push(message);
+        }
+    }
+    let format = &block.format;
+    if format.min_depth > format.max_depth {
+        block.errors.push(format!(
+            "min_depth ({}) cannot exceed max_depth ({})",
+            format.min_depth, format.max_depth
+        ));
+    }
+}
+
+fn apply_option(block: &mut TocBlock, key: &str, value: Option<&str>) -> Result<(), String> {
+    let format = &mut block.format;
+    match key {
+        "depth" => {
+            let value = value.ok_or("depth requires a value such as depth=2..3")?;
+            let (min, max) = match value.split_once("..") {
+                Some((min, max)) => (
+                    parse_depth(min, format.min_depth)?,
+                    parse_depth(max, format.max_depth)?,
+                ),
+                None => {
+                    let depth = parse_depth(value, 0)?;
+                    (depth, depth)
                 }
+            };
+            format.min_depth = min;
+            format.max_depth = max;
+        }
+        "min_depth" => format.min_depth = parse_depth(value.unwrap_or(""), 0)?,
+        "max_depth" => format.max_depth = parse_depth(value.unwrap_or(""), 0)?,
+        "numbered" => format.numbered = parse_flag(key, value)?,
+        "after_block" => format.after_block = parse_flag(key, value)?,
+        "bullet" => match value {
+            Some(bullet @ ("-" | "*" | "+")) => format.bullet = bullet.chars().next().unwrap(),
+            _ => return Err("bullet must be one of '-', '*', or '+'".into()),
+        },
+        "exclude" => {
+            let pattern = value.ok_or("exclude requires a pattern")?;
+            let regex = Regex::new(pattern)
+                .map_err(|err| format!("invalid exclude pattern '{pattern}': {err}"))?;
+            format.exclude.push(regex);
+        }
+        "scope" => {
+            block.scope = match value {
+                Some("document") => TocScope::Document,
+                Some("section") => TocScope::Section,
+                _ => return Err("scope must be 'document' or 'section'".into()),
             }
         }
+        other => return Err(format!("unknown TOC option '{other}'")),
     }
+    Ok(())
+}
 
-    None
+fn parse_depth(value: &str, default: usize) -> Result<usize, String> {
+    if value.is_empty() && default > 0 {
+        return Ok(default);
+    }
+    match value.parse::<usize>() {
+        Ok(depth @ 1..=6) => Ok(depth),
+        _ => Err(format!(
+            "heading depth must be between 1 and 6 (received '{value}')"
+        )),
+    }
+}
+
+fn parse_flag(key: &str, value: Option<&str>) -> Result<bool, String> {
+    match value {
+        None | Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        Some(other) => Err(format!("{key} must be true or false (received '{other}')")),
+    }
+}
+
+/// Whitespace-separated `key` / `key=value` tokens; values may be single- or
+/// double-quoted.
+fn split_options(options: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    for ch in options.chars() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            None => current.push(ch),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
 }
 
 fn parse_entry(line: &str, line_number: usize) -> Option<TocEntry> {
@@ -104,12 +248,22 @@ fn parse_entry(line: &str, line_number: usize) -> Option<TocEntry> {
         return None;
     }
 
-    let bullet = trimmed_start.chars().next()?;
-    if bullet != '-' && bullet != '*' && bullet != '+' {
-        return None;
-    }
+    let digits = trimmed_start
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .count();
+    let after_marker = if digits > 0 {
+        let rest = &trimmed_start[digits..];
+        rest.strip_prefix('.').or_else(|| rest.strip_prefix(')'))?
+    } else {
+        let bullet = trimmed_start.chars().next()?;
+        if bullet != '-' && bullet != '*' && bullet != '+' {
+            return None;
+        }
+        &trimmed_start[1..]
+    };
 
-    let after_bullet = trimmed_start[1..].trim_start();
+    let after_bullet = after_marker.trim_start();
     if !after_bullet.starts_with('[') {
         return None;
     }
@@ -135,40 +289,202 @@ fn parse_entry(line: &str, line_number: usize) -> Option<TocEntry> {
     })
 }
 
-pub fn generate_items(sections: &[DocumentSection]) -> Vec<GeneratedItem> {
-    sections
+/// Entries `block` should list, honouring its scope and format.
+pub fn generate_items(sections: &[DocumentSection], block: &TocBlock) -> Vec<GeneratedItem> {
+    let format = &block.format;
+    let mut candidates = sections;
+    let mut base_depth = format.min_depth;
+
+    if block.scope == TocScope::Section {
+        let parent = sections
+            .iter()
+            .rposition(|section| section.heading.byte_range.start < block.marker_offset);
+        if let Some(parent) = parent {
+            let parent_depth = sections[parent].heading.depth;
+            let nested = sections[parent + 1..]
+                .iter()
+                .take_while(|section| section.heading.depth > parent_depth)
+                .count();
+            candidates = &sections[parent + 1..parent + 1 + nested];
+            base_depth = base_depth.max(parent_depth + 1);
+        }
+    }
+
+    candidates
         .iter()
-        .filter_map(|section| {
+        .filter(|section| {
             let depth = section.heading.depth;
-            if depth < 2 {
-                return None;
-            }
-            let indent = depth.saturating_sub(2);
-            Some(GeneratedItem {
-                indent,
-                text: section.heading.raw.trim().to_string(),
-                anchor: section.heading.anchor.clone(),
-            })
+            depth >= base_depth
+                && depth <= format.max_depth
+                && (!format.after_block || section.heading.byte_range.start >= block.end_offset)
+                && !format
+                    .exclude
+                    .iter()
+                    .any(|pattern| pattern.is_match(&section.heading.normalized))
+        })
+        .map(|section| GeneratedItem {
+            indent: section.heading.depth - base_depth,
+            text: section.heading.raw.trim().to_string(),
+            anchor: section.heading.anchor.clone(),
         })
         .collect()
 }
 
-#[allow(dead_code)]
-pub fn render_items(items: &[GeneratedItem]) -> String {
-    render_items_with_separator(items, "\n")
+/// Rebuild `contents` with the body of every block regenerated.
+pub fn render_blocks(
+    contents: &str,
+    sections: &[DocumentSection],
+    blocks: &[TocBlock],
+    line_sep: &str,
+) -> String {
+    let mut updated = String::with_capacity(contents.len());
+    let mut cursor = 0;
+    for block in blocks {
+        let items = generate_items(sections, block);
+        updated.push_str(&contents[cursor..block.start_offset]);
+        updated.push_str(&render_items(&items, &block.format, line_sep));
+        cursor = block.end_offset;
+    }
+    updated.push_str(&contents[cursor..]);
+    updated
+}
+
+/// Heading `contents-heading` placement looks for, and adds when missing.
+const CONTENTS_HEADING: &str = "Contents";
+
+/// `contents` with an empty marker block spliced in at `position`, separated from
+/// the surrounding text by blank lines. Fill it with [`render_blocks`].
+pub fn insert_block(
+    contents: &str,
+    sections: &[DocumentSection],
+    settings: &TocSettings,
+    position: TocInsertPosition,
+    line_sep: &str,
+) -> String {
+    let title = sections
+        .first()
+        .filter(|section| section.heading.depth == 1);
+    let first_section = sections.iter().find(|section| match title {
+        Some(title) => section.heading.byte_range.start > title.heading.byte_range.start,
+        None => true,
+    });
+    let body_start = match title {
+        Some(title) => line_end(contents, title.heading.byte_range.end),
+        None => front_matter_end(contents),
+    };
+    let section_start =
+        first_section.map_or(contents.len(), |section| section.heading.byte_range.start);
+
+    let mut heading = None;
+    let mut marker = settings.start_marker.clone();
+    let offset = match position {
+        TocInsertPosition::AfterIntro => intro_end(contents, body_start, section_start),
+        TocInsertPosition::BeforeFirstSection => section_start,
+        TocInsertPosition::ContentsHeading => {
+            let existing = sections.iter().find(|section| {
+                let text = section.heading.normalized.as_str();
+                text.eq_ignore_ascii_case(CONTENTS_HEADING)
+                    || text.eq_ignore_ascii_case("Table of Contents")
+            });
+            let text = existing.map_or(CONTENTS_HEADING, |section| {
+                section.heading.normalized.as_str()
+            });
+            // Keep the heading out of its own TOC when the marker accepts options.
+            if let Some(prefix) = settings.start_marker.strip_suffix("-->") {
+                marker = format!(
+                    "{} exclude=\"^{}$\" -->",
+                    prefix.trim_end(),
+                    regex::escape(text)
+                );
+            }
+            match existing {
+                Some(section) => line_end(contents, section.heading.byte_range.end),
+                None => {
+                    let depth = first_section.map_or(2, |section| section.heading.depth.max(2));
+                    heading = Some(format!("{} {CONTENTS_HEADING}", "#".repeat(depth)));
+                    section_start
+                }
+            }
+        }
+    };
+
+    let before = &contents[..offset];
+    let after = &contents[offset..];
+    let mut inserted = String::with_capacity(contents.len() + 64);
+    inserted.push_str(before);
+    if !before.is_empty() {
+        if !before.ends_with('\n') {
+            inserted.push_str(line_sep);
+        }
+        if !before.trim_end_matches(['\r', '\n']).is_empty() && !ends_with_blank_line(before) {
+            inserted.push_str(line_sep);
+        }
+    }
+    if let Some(heading) = heading {
+        inserted.push_str(&heading);
+        inserted.push_str(line_sep);
+        inserted.push_str(line_sep);
+    }
+    inserted.push_str(&marker);
+    inserted.push_str(line_sep);
+    inserted.push_str(&settings.end_marker);
+    inserted.push_str(line_sep);
+    if !after.is_empty() && !after.starts_with(['\r', '\n']) {
+        inserted.push_str(line_sep);
+    }
+    inserted.push_str(after);
+    inserted
+}
+
+/// End of the first paragraph between `start` and `limit`, or `start` when there is none.
+fn intro_end(contents: &str, start: usize, limit: usize) -> usize {
+    Parser::new_ext(contents, Options::all())
+        .into_offset_iter()
+        .find_map(|(event, range)| match event {
+            Event::Start(Tag::Paragraph) if range.start >= start && range.start < limit => {
+                Some(line_end(contents, range.end))
+            }
+            _ => None,
+        })
+        .unwrap_or(start)
 }
 
-pub fn render_items_with_separator(items: &[GeneratedItem], line_sep: &str) -> String {
-    if items.is_empty() {
-        return String::new();
+/// Offset just past the line containing the byte before `offset`.
+fn line_end(contents: &str, offset: usize) -> usize {
+    if offset == 0 || contents[..offset].ends_with('\n') {
+        return offset;
     }
+    contents[offset..]
+        .find('\n')
+        .map_or(contents.len(), |found| offset + found + 1)
+}
+
+fn ends_with_blank_line(text: &str) -> bool {
+    text.ends_with("\n\n") || text.ends_with("\n\r\n")
+}
 
+/// Render items as a nested list. Bullets indent by two spaces per level, ordered
+/// lists by three so nested items stay inside their parent item.
+pub fn render_items(items: &[GeneratedItem], format: &TocFormat, line_sep: &str) -> String {
+    let mut counters: Vec<usize> = Vec::new();
+    // Column width of the latest marker at each level; children line up with its text.
+    let mut widths: Vec<usize> = Vec::new();
     let mut output = String::new();
     for item in items {
-        let indent = "  ".repeat(item.indent);
+        counters.resize(item.indent + 1, 0);
+        counters[item.indent] += 1;
+        let marker = if format.numbered {
+            format!("{}.", counters[item.indent])
+        } else {
+            format.bullet.to_string()
+        };
+        let fallback = if format.numbered { 3 } else { 2 };
+        widths.resize(item.indent + 1, fallback);
+        let indent = " ".repeat(widths[..item.indent].iter().sum());
+        widths[item.indent] = format!("{marker} ").len();
         output.push_str(&format!(
-            "{indent}- [{}](#{}){}",
-            item.text, item.anchor, line_sep
+            "{indent}{marker} [{}](#{}){line_sep}",
+            item.text, item.anchor
         ));
     }
     output
@@ -0,0 +1,513 @@
+//! Incremental re-processing for `markdown-doc watch`.
+//!
+//! A [`WatchSession`] remembers the content hash and lint findings of every document.
+//! Each batch of (debounced) filesystem events is reduced to the documents whose
+//! contents actually changed; those get their TOC blocks refreshed, and they are
+//! re-linted together with every document that links to them, since anchors and
+//! link targets may have moved. With the `orphans` or `duplicate-sections` rules
+//! enabled, documents whose project-wide orphan/duplicate verdict changed are
+//! re-linted as well. The report lists the findings that appeared and disappeared
+//! instead of the full lint output.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ffi::OsStr;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use markdown_doc_config::{LintRule, SeverityLevel};
+use markdown_doc_format::{CatalogFormat, LintFinding, LintFormat};
+use markdown_doc_utils::content_hash;
+use notify_debouncer_mini::new_debouncer;
+use notify_debouncer_mini::notify::RecursiveMode;
+
+use crate::lint::verdict_context;
+use crate::refactor::graph::{LinkGraph, LinkOccurrence};
+use crate::{
+    count_severities, is_markdown_path, CatalogMode, CatalogOptions, CatalogStatus, LintOptions,
+    OperationError, Operations, ScanOptions, TocMode, TocOptions, TocStatus,
+};
+
+/// Watch execution options.
+pub struct WatchOptions {
+    /// Documents to watch; `staged` and `changed_since` are ignored.
+    pub scan: ScanOptions,
+    pub lint: bool,
+    pub toc: bool,
+    pub catalog: bool,
+    /// Quiet period collected into one batch of changes.
+    pub debounce: Duration,
+}
+
+/// What one processing pass did.
+#[derive(Debug, Default)]
+pub struct WatchReport {
+    /// Documents whose contents changed (or that were added).
+    pub changed: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+    /// Unchanged documents re-linted because they link to a changed or removed one.
+    pub dependents: Vec<PathBuf>,
+    /// Unchanged documents re-linted because their orphan/duplicate verdict changed.
+    pub reclassified: Vec<PathBuf>,
+    pub toc_updated: Vec<PathBuf>,
+    /// Documents whose TOC markers carry options that could not be parsed.
+    pub toc_invalid: Vec<PathBuf>,
+    pub catalog: Option<CatalogStatus>,
+    /// Lint findings that were not reported before this pass.
+    pub added: Vec<LintFinding>,
+    /// Lint findings that no longer occur.
+    pub resolved: Vec<LintFinding>,
+    /// Totals across every watched document after this pass.
+    pub error_count: usize,
+    pub warning_count: usize,
+    pub rendered: String,
+}
+
+impl WatchReport {
+    /// True when no watched document changed, so nothing was processed.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Long-lived state for `markdown-doc watch`.
+pub struct WatchSession<'a> {
+    ops: &'a Operations,
+    options: WatchOptions,
+    /// Canonical project root, which is what filesystem events are reported against.
+    watch_root: PathBuf,
+    /// Content hash of every watched document; events that leave it unchanged
+    /// (including the session's own TOC and catalog writes) are dropped.
+    hashes: HashMap<PathBuf, String>,
+    findings: BTreeMap<PathBuf, Vec<LintFinding>>,
+    /// Orphan/duplicate verdict of every document that has one (see [`verdict_context`]).
+    verdicts: HashMap<PathBuf, String>,
+}
+
+impl Operations {
+    /// Create a watch session; call [`WatchSession::scan`] for the initial pass.
+    pub fn watch_session(&self, options: WatchOptions) -> WatchSession<'_> {
+        let root = &self.config.project.root;
+        WatchSession {
+            ops: self,
+            options,
+            watch_root: root.canonicalize().unwrap_or_else(|_| root.clone()),
+            hashes: HashMap::new(),
+            findings: BTreeMap::new(),
+            verdicts: HashMap::new(),
+        }
+    }
+}
+
+impl WatchSession<'_> {
+    /// Process every watched document, recording the state later passes compare against.
+    pub fn scan(&mut self) -> Result<WatchReport, OperationError> {
+        let documents = self.documents()?;
+        let changed = documents.iter().cloned().collect();
+        self.apply(changed, Vec::new(), &documents, true)
+    }
+
+    /// Process the documents affected by changes to `paths` (absolute, or relative to
+    /// the project root). Directories cover every document beneath them.
+    pub fn process(&mut self, paths: &[PathBuf]) -> Result<WatchReport, OperationError> {
+        let root = &self.ops.config.project.root;
+        let touched: Vec<PathBuf> = paths
+            .iter()
+            .filter_map(|path| self.relative(path))
+            .filter(|path| {
+                is_markdown_path(&path.to_string_lossy())
+                    || root.join(path).is_dir()
+                    || self.hashes.keys().any(|known| known.starts_with(path))
+            })
+            .collect();
+        if touched.is_empty() {
+            return Ok(WatchReport::default());
+        }
+
+        let documents = self.documents()?;
+        let covered = |path: &Path| touched.iter().any(|touched| path.starts_with(touched));
+        let mut changed = Vec::new();
+        for path in documents.iter().filter(|path| covered(path)) {
+            let hash = match std::fs::read(root.join(path)) {
+                Ok(contents) => content_hash(&contents),
+                // Deleted between the event and now; the next batch reports the removal.
+                Err(_) => continue,
+            };
+            if self.hashes.get(path) != Some(&hash) {
+                changed.push(path.clone());
+            }
+        }
+        let mut removed: Vec<PathBuf> = self
+            .hashes
+            .keys()
+            .filter(|path| covered(path) && !documents.contains(*path))
+            .cloned()
+            .collect();
+        removed.sort();
+
+        self.apply(changed, removed, &documents, false)
+    }
+
+    /// Watch the project root and process each debounced batch of changes, handing
+    /// non-empty reports to `on_report` until it breaks.
+    pub fn run<F>(&mut self, mut on_report: F) -> Result<(), OperationError>
+    where
+        F: FnMut(&WatchReport) -> ControlFlow<()>,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let mut debouncer = new_debouncer(self.options.debounce, sender).map_err(watch_error)?;
+        debouncer
+            .watcher()
+            .watch(&self.watch_root, RecursiveMode::Recursive)
+            .map_err(watch_error)?;
+
+        for events in receiver {
+            let paths: Vec<PathBuf> = events
+                .map_err(watch_error)?
+                .into_iter()
+                .map(|event| event.path)
+                .collect();
+            let report = self.process(&paths)?;
+            if !report.is_empty() && on_report(&report).is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply(
+        &mut self,
+        changed: Vec<PathBuf>,
+        removed: Vec<PathBuf>,
+        documents: &BTreeSet<PathBuf>,
+        initial: bool,
+    ) -> Result<WatchReport, OperationError> {
+        let mut report = WatchReport {
+            changed,
+            removed,
+            ..WatchReport::default()
+        };
+        if report.is_empty() && !initial {
+            return Ok(report);
+        }
+        let root = &self.ops.config.project.root;
+
+        if self.options.toc && !report.changed.is_empty() {
+            let outcome = self.ops.toc(TocOptions {
+                scan: self.scan_options(report.changed.clone()),
+                mode: TocMode::Update,
+                quiet: true,
+                insert_at: None,
+            })?;
+            for change in outcome.changes {
+                match change.status {
+                    TocStatus::Updated => report.toc_updated.push(change.path),
+                    TocStatus::InvalidOptions => report.toc_invalid.push(change.path),
+                    _ => {}
+                }
+            }
+        }
+
+        // Hash after the TOC pass so the session's own writes do not trigger another one.
+        for path in &report.changed {
+            let contents = std::fs::read(root.join(path)).map_err(|source| OperationError::Io {
+                path: path.clone(),
+                source,
+            })?;
+            self.hashes.insert(path.clone(), content_hash(&contents));
+        }
+        for path in &report.removed {
+            self.hashes.remove(path);
+        }
+
+        if self.options.lint {
+            self.relint(&mut report, documents, initial)?;
+        }
+
+        if self.options.catalog {
+            let outcome = self.ops.catalog(CatalogOptions {
+                scan: self.scan_options(self.options.scan.paths.clone()),
+                format: CatalogFormat::Markdown,
+                output_path: None,
+                write_to_disk: true,
+                mode: CatalogMode::Update,
+                timestamp: None,
+                regen: false,
+            })?;
+            report.catalog = Some(outcome.status);
+        }
+
+        report.rendered = render(&report, initial, documents.len(), self.options.lint);
+        Ok(report)
+    }
+
+    fn relint(
+        &mut self,
+        report: &mut WatchReport,
+        documents: &BTreeSet<PathBuf>,
+        initial: bool,
+    ) -> Result<(), OperationError> {
+        let mut targets: BTreeSet<PathBuf> = report.changed.iter().cloned().collect();
+        if !initial {
+            let files: Vec<PathBuf> = documents.iter().cloned().collect();
+            let graph = LinkGraph::build(&self.ops.parser, &self.ops.config.project.root, &files)?;
+            for target in report.changed.iter().chain(&report.removed) {
+                for occurrence in graph.links_to(target, None) {
+                    let source = match occurrence {
+                        LinkOccurrence::Inline { source, .. }
+                        | LinkOccurrence::Definition { source, .. } => source,
+                    };
+                    if targets.insert(source.clone()) {
+                        report.dependents.push(source);
+                    }
+                }
+            }
+            report.dependents.sort();
+        }
+
+        // Orphan and duplicate findings depend on every document in the project, so an
+        // edit elsewhere can change them without touching this document or its links.
+        let verdicts = self.verdicts()?;
+        if !initial {
+            let reclassified: BTreeSet<&PathBuf> = verdicts
+                .keys()
+                .chain(self.verdicts.keys())
+                .filter(|path| {
+                    verdicts.get(*path) != self.verdicts.get(*path) && documents.contains(*path)
+                })
+                .collect();
+            for path in reclassified {
+                if targets.insert(path.clone()) {
+                    report.reclassified.push(path.clone());
+                }
+            }
+        }
+        self.verdicts = verdicts;
+
+        for path in &report.removed {
+            report
+                .resolved
+                .extend(self.findings.remove(path).unwrap_or_default());
+        }
+
+        if !targets.is_empty() {
+            let outcome = self.ops.lint(LintOptions {
+                scan: self.scan_options(targets.iter().cloned().collect()),
+                format: LintFormat::Plain,
+                baseline: None,
+                write_baseline: false,
+                add_words: false,
+                // Findings are kept in memory; the on-disk cache is left to `lint` runs.
+                cache: false,
+            })?;
+            let mut fresh: BTreeMap<PathBuf, Vec<LintFinding>> =
+                targets.into_iter().map(|path| (path, Vec::new())).collect();
+            for finding in outcome.report.findings {
+                fresh.entry(finding.path.clone()).or_default().push(finding);
+            }
+            for (path, findings) in fresh {
+                let previous = self.findings.remove(&path).unwrap_or_default();
+                report.added.extend(difference(&findings, &previous));
+                report.resolved.extend(difference(&previous, &findings));
+                if !findings.is_empty() {
+                    self.findings.insert(path, findings);
+                }
+            }
+        }
+
+        let all: Vec<LintFinding> = self.findings.values().flatten().cloned().collect();
+        (report.error_count, report.warning_count) = count_severities(&all);
+        Ok(())
+    }
+
+    /// Current orphan/duplicate verdicts, or none when neither rule is enabled.
+    fn verdicts(&self) -> Result<HashMap<PathBuf, String>, OperationError> {
+        let lint = &self.ops.config.lint;
+        let enabled = |rule: LintRule| lint.rules.contains(&rule) && lint.is_rule_enabled(rule);
+        // Computed over the whole project, as `lint` does.
+        let scan = || ScanOptions {
+            respect_ignore: self.options.scan.respect_ignore,
+            ..ScanOptions::default()
+        };
+        let orphans = if enabled(LintRule::Orphans) {
+            Some(self.ops.orphans(scan())?)
+        } else {
+            None
+        };
+        let duplicates = if enabled(LintRule::DuplicateSections) {
+            Some(self.ops.duplicates(scan())?)
+        } else {
+            None
+        };
+
+        let mut candidates: BTreeSet<&PathBuf> = BTreeSet::new();
+        if let Some(report) = &orphans {
+            candidates.extend(report.orphans.iter().chain(&report.unreachable));
+        }
+        if let Some(report) = &duplicates {
+            for cluster in &report.clusters {
+                candidates.extend(cluster.sections.iter().map(|section| &section.path));
+            }
+        }
+        Ok(candidates
+            .into_iter()
+            .map(|path| {
+                let context = verdict_context(path, orphans.as_ref(), duplicates.as_ref());
+                (path.clone(), context)
+            })
+            .collect())
+    }
+
+    /// Watched documents, excluding the catalog pages the session writes itself.
+    fn documents(&self) -> Result<BTreeSet<PathBuf>, OperationError> {
+        let ops = self.ops;
+        let catalog = &ops.config.catalog;
+        let output = ops.resolve_output_path(&catalog.output);
+        let output = output
+            .strip_prefix(&ops.config.project.root)
+            .unwrap_or(&output);
+        Ok(ops
+            .collect_targets(&self.scan_options(self.options.scan.paths.clone()))?
+            .into_iter()
+            .filter(|path| match catalog.directory_index.as_deref() {
+                _ if !self.options.catalog => true,
+                Some(name) => path.file_name() != Some(OsStr::new(name)),
+                None => path != output,
+            })
+            .collect())
+    }
+
+    fn scan_options(&self, paths: Vec<PathBuf>) -> ScanOptions {
+        ScanOptions {
+            paths,
+            staged: false,
+            changed_since: None,
+            respect_ignore: self.options.scan.respect_ignore,
+        }
+    }
+
+    fn relative(&self, path: &Path) -> Option<PathBuf> {
+        if path.is_relative() {
+            return Some(path.to_path_buf());
+        }
+        path.strip_prefix(&self.watch_root)
+            .or_else(|_| path.strip_prefix(&self.ops.config.project.root))
+            .ok()
+            .filter(|relative| !relative.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+    }
+}
+
+/// Findings in `left` without a counterpart in `right`. Lines are ignored so that
+/// findings shifted by an edit are not reported as new.
+fn difference(left: &[LintFinding], right: &[LintFinding]) -> Vec<LintFinding> {
+    let mut remaining: Vec<&LintFinding> = right.iter().collect();
+    left.iter()
+        .filter(|finding| {
+            match remaining
+                .iter()
+                .position(|other| other.rule == finding.rule && other.message == finding.message)
+            {
+                Some(idx) => {
+                    remaining.swap_remove(idx);
+                    false
+                }
+                None => true,
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+fn render(report: &WatchReport, initial: bool, documents: usize, lint: bool) -> String {
+    let mut lines = Vec::new();
+    if initial {
+        lines.push(format!("👀 watching {documents} documents"));
+    } else {
+        for path in &report.changed {
+            lines.push(format!("🔄 {} changed", path.display()));
+        }
+        for path in &report.removed {
+            lines.push(format!("🗑️  {} removed", path.display()));
+        }
+        if !report.dependents.is_empty() {
+            lines.push(format!(
+                "🔗 re-linting {} linking document(s): {}",
+                report.dependents.len(),
+                report
+                    .dependents
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        if !report.reclassified.is_empty() {
+            lines.push(format!(
+                "🧭 re-linting {} document(s) whose orphan/duplicate status changed: {}",
+                report.reclassified.len(),
+                report
+                    .reclassified
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+    for path in &report.toc_updated {
+        lines.push(format!("✏️  updated TOC in {}", path.display()));
+    }
+    for path in &report.toc_invalid {
+        lines.push(format!(
+            "❌ {} has invalid TOC options; run `markdown-doc toc --check`",
+            path.display()
+        ));
+    }
+    if report.catalog == Some(CatalogStatus::Written) {
+        lines.push("✏️  catalog updated".to_string());
+    }
+    if lint {
+        let prefix = if initial { "" } else { "+ " };
+        for finding in &report.added {
+            lines.push(format!("{prefix}{}", finding_line(finding)));
+        }
+        for finding in &report.resolved {
+            lines.push(format!("- {}", finding_line(finding)));
+        }
+        let delta = if initial {
+            String::new()
+        } else {
+            format!(
+                " (+{} new, -{} resolved)",
+                report.added.len(),
+                report.resolved.len()
+            )
+        };
+        lines.push(format!(
+            "lint: {} errors, {} warnings{delta}",
+            report.error_count, report.warning_count
+        ));
+    }
+    lines.join("\n")
+}
+
+fn finding_line(finding: &LintFinding) -> String {
+    let marker = match finding.severity {
+        SeverityLevel::Error => "❌",
+        SeverityLevel::Warning => "⚠️ ",
+        SeverityLevel::Ignore => "ℹ️ ",
+    };
+    format!(
+        "{marker} {}:{} [{}] {}",
+        finding.path.display(),
+        finding.line,
+        finding.rule.as_str(),
+        finding.message
+    )
+}
+
+fn watch_error(err: notify_debouncer_mini::notify::Error) -> OperationError {
+    OperationError::Other(format!("file watcher error: {err}"))
+}
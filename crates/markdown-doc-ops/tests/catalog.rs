@@ -0,0 +1,334 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use markdown_doc_config::{Config, LoadOptions};
+use markdown_doc_format::CatalogFormat;
+use markdown_doc_ops::{CatalogMode, CatalogOptions, Operations, ScanOptions};
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn write_file(dir: &Path, name: &str, contents: &str) {
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent directory");
+    }
+    fs::write(path, contents).expect("write file");
+}
+
+fn render(dir: &Path, format: CatalogFormat) -> String {
+    let config = Config::load(LoadOptions::default().with_working_dir(dir)).expect("load config");
+    Operations::new(config)
+        .catalog(CatalogOptions {
+            scan: ScanOptions::default(),
+            format,
+            output_path: None,
+            write_to_disk: false,
+            mode: CatalogMode::Update,
+            timestamp: None,
+            regen: false,
+        })
+        .expect("catalog")
+        .rendered
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .env("GIT_AUTHOR_DATE", "2025-01-02T03:04:05+00:00")
+        .env("GIT_COMMITTER_DATE", "2025-01-02T03:04:05+00:00")
+        .env("GIT_AUTHOR_NAME", "Test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "Test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+const GUIDE: &str = "---\ntitle: \"User Guide\"\nowner: docs\ntags:\n  - intro\n---\n# Guide\n\nThis guide explains setup. It has more detail later.\n\n## Install\n\nRun `cargo install` to get the binary!\nThen verify it.\n\n### Linux\n\nUse the package.\n";
+
+#[test]
+fn catalog_fields_default_to_headings_only() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "guide.md", GUIDE);
+
+    let json: Value = serde_json::from_str(&render(temp.path(), CatalogFormat::Json)).unwrap();
+    let file = &json["files"][0];
+    assert!(file.get("last_modified").is_none());
+    assert!(file.get("front_matter").is_none());
+    assert_eq!(
+        file["headings"][1],
+        serde_json::json!({"level": 2, "text": "Install", "anchor": "install"})
+    );
+
+    let markdown = render(temp.path(), CatalogFormat::Markdown);
+    assert!(markdown.contains("## guide.md\n\n- [Guide](#guide)\n  - [Install](#install)\n"));
+}
+
+#[test]
+fn catalog_records_section_metadata() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        ".markdown-doc.toml",
+        "[catalog]\nfields = [\"summary\", \"words\", \"tokens\", \"lines\", \"modified\", \"front-matter\"]\n",
+    );
+    write_file(temp.path(), "guide.md", GUIDE);
+    git(temp.path(), &["init", "-q"]);
+    git(temp.path(), &["add", "guide.md"]);
+    git(temp.path(), &["commit", "-q", "-m", "add guide"]);
+
+    let json: Value = serde_json::from_str(&render(temp.path(), CatalogFormat::Json)).unwrap();
+    let file = &json["files"][0];
+    assert_eq!(file["last_modified"], "2025-01-02T03:04:05+00:00");
+    assert_eq!(
+        file["front_matter"],
+        serde_json::json!({"owner": "docs", "title": "User Guide"})
+    );
+
+    let guide = &file["headings"][0];
+    assert_eq!(guide["summary"], "This guide explains setup.");
+    assert_eq!(
+        (guide["line_start"].as_u64(), guide["line_end"].as_u64()),
+        (Some(7), Some(18))
+    );
+    let install = &file["headings"][1];
+    assert_eq!(install["summary"], "Run cargo install to get the binary!");
+    assert_eq!(
+        (install["line_start"].as_u64(), install["line_end"].as_u64()),
+        (Some(11), Some(18))
+    );
+    assert_eq!(install["words"], 15);
+    assert_eq!(install["tokens"], 24);
+    let linux = &file["headings"][2];
+    assert_eq!(linux["summary"], "Use the package.");
+    assert_eq!(linux["words"], 4);
+
+    let markdown = render(temp.path(), CatalogFormat::Markdown);
+    assert!(
+        markdown.contains("Last modified: 2025-01-02T03:04:05+00:00  \nFront matter: `owner`: docs, `title`: User Guide\n\n"),
+        "{markdown}"
+    );
+    assert!(
+        markdown.contains("  - [Install](#install) — Run cargo install to get the binary! (lines 11-18, 15 words, ~24 tokens)\n"),
+        "{markdown}"
+    );
+}
+
+#[test]
+fn catalog_git_timestamp_uses_latest_commit() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        ".markdown-doc.toml",
+        "[catalog]\ntimestamp = \"git\"\n",
+    );
+    write_file(temp.path(), "guide.md", GUIDE);
+    let markdown = render(temp.path(), CatalogFormat::Markdown);
+    assert!(!markdown.contains("Last updated"), "{markdown}");
+
+    git(temp.path(), &["init", "-q"]);
+    git(temp.path(), &["add", "guide.md"]);
+    git(temp.path(), &["commit", "-q", "-m", "add guide"]);
+
+    let markdown = render(temp.path(), CatalogFormat::Markdown);
+    assert!(
+        markdown
+            .starts_with("# Documentation Catalog\n\nLast updated: 2025-01-02T03:04:05+00:00\n\n"),
+        "{markdown}"
+    );
+    assert_eq!(markdown, render(temp.path(), CatalogFormat::Markdown));
+}
+
+#[test]
+fn catalog_falls_back_to_mtime_outside_git() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        ".markdown-doc.toml",
+        "[catalog]\nfields = [\"modified\"]\n",
+    );
+    write_file(temp.path(), "notes.md", "# Notes\n");
+
+    let json: Value = serde_json::from_str(&render(temp.path(), CatalogFormat::Json)).unwrap();
+    let modified = json["files"][0]["last_modified"]
+        .as_str()
+        .expect("timestamp");
+    assert!(
+        modified.ends_with('Z') && modified.contains('T'),
+        "{modified}"
+    );
+    assert!(json["files"][0]["headings"][0].get("summary").is_none());
+}
+
+#[test]
+fn unknown_catalog_field_is_rejected() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        ".markdown-doc.toml",
+        "[catalog]\nfields = [\"author\"]\n",
+    );
+    let err = Config::load(LoadOptions::default().with_working_dir(temp.path()))
+        .expect_err("invalid field");
+    assert!(
+        err.to_string().contains("unknown catalog field 'author'"),
+        "{err}"
+    );
+}
+
+fn run_catalog(dir: &Path, mode: CatalogMode) -> markdown_doc_ops::CatalogOutcome {
+    let config = Config::load(LoadOptions::default().with_working_dir(dir)).expect("load config");
+    Operations::new(config)
+        .catalog(CatalogOptions {
+            scan: ScanOptions::default(),
+            format: CatalogFormat::Markdown,
+            output_path: None,
+            write_to_disk: true,
+            mode,
+            timestamp: None,
+            regen: false,
+        })
+        .expect("catalog")
+}
+
+#[test]
+fn catalog_template_groups_documents_by_directory() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        ".markdown-doc.toml",
+        "[catalog]\ntimestamp = \"omit\"\ntemplate = \"docs/catalog.md.j2\"\n",
+    );
+    write_file(
+        temp.path(),
+        "docs/catalog.md.j2",
+        "# Docs ({{ file_count }} files)\n\
+         {% for dir in directories %}\n\
+         \n<details><summary>{{ dir.path }}</summary>\n\n\
+         {% for entry in dir.entries %}\n\
+         - [{{ entry.name }}]({{ entry.path }}){{ \": \" ~ entry.headings[0].text if entry.headings }}\n\
+         {% endfor %}\n\
+         \n</details>\n\
+         {% endfor %}\n",
+    );
+    write_file(temp.path(), "README.md", "# Project\n");
+    write_file(temp.path(), "docs/guide.md", "# Guide\n\n## Setup\n");
+    write_file(temp.path(), "docs/api.md", "# API\n");
+
+    let outcome = run_catalog(temp.path(), CatalogMode::Update);
+    assert_eq!(
+        outcome.rendered,
+        "# Docs (3 files)\n\
+         \n<details><summary>.</summary>\n\n\
+         - [README.md](README.md): Project\n\
+         \n</details>\n\
+         \n<details><summary>docs</summary>\n\n\
+         - [api.md](docs/api.md): API\n\
+         - [guide.md](docs/guide.md): Guide\n\
+         \n</details>\n"
+    );
+    assert_eq!(
+        fs::read_to_string(temp.path().join("DOC_CATALOG.md")).unwrap(),
+        outcome.rendered
+    );
+}
+
+#[test]
+fn catalog_template_errors_are_reported() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        ".markdown-doc.toml",
+        "[catalog]\ntemplate = \"catalog.j2\"\n",
+    );
+    write_file(temp.path(), "catalog.j2", "{% for entry in entries %}\n");
+    write_file(temp.path(), "README.md", "# Project\n");
+
+    let config = Config::load(LoadOptions::default().with_working_dir(temp.path())).unwrap();
+    let err = Operations::new(config)
+        .catalog(CatalogOptions {
+            scan: ScanOptions::default(),
+            format: CatalogFormat::Markdown,
+            output_path: None,
+            write_to_disk: true,
+            mode: CatalogMode::Update,
+            timestamp: None,
+            regen: false,
+        })
+        .err()
+        .expect("template error");
+    assert!(
+        err.to_string().starts_with("catalog template error"),
+        "{err}"
+    );
+    assert!(!temp.path().join("DOC_CATALOG.md").exists());
+}
+
+#[test]
+fn catalog_directory_index_writes_one_catalog_per_folder() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        ".markdown-doc.toml",
+        "[catalog]\ntimestamp = \"omit\"\ndirectory_index = \"INDEX.md\"\n",
+    );
+    write_file(temp.path(), "README.md", "# Project\n");
+    write_file(temp.path(), "docs/guide.md", "# Guide\n");
+    write_file(temp.path(), "docs/deep/nested/notes.md", "# Notes\n");
+
+    let outcome = run_catalog(temp.path(), CatalogMode::Update);
+    assert_eq!(outcome.exit_code, 0);
+    assert_eq!(
+        outcome.rendered,
+        "✏️  wrote INDEX.md\n✏️  wrote docs/INDEX.md\n✏️  wrote docs/deep/nested/INDEX.md"
+    );
+    assert!(!temp.path().join("DOC_CATALOG.md").exists());
+    assert!(!temp.path().join("docs/deep/INDEX.md").exists());
+
+    let docs_index = fs::read_to_string(temp.path().join("docs/INDEX.md")).unwrap();
+    assert_eq!(
+        docs_index,
+        "# Documentation Catalog\n\n## Catalog\n\n- [guide.md](guide.md)\n\n---\n\n## guide.md\n\n- [Guide](#guide)\n\n"
+    );
+
+    // Index files are not catalogued themselves, so a second run is a no-op.
+    let outcome = run_catalog(temp.path(), CatalogMode::Check);
+    assert_eq!(outcome.exit_code, 0, "{}", outcome.rendered);
+    write_file(temp.path(), "docs/guide.md", "# Guide v2\n");
+    let outcome = run_catalog(temp.path(), CatalogMode::Check);
+    assert_eq!(outcome.exit_code, 1);
+    assert!(outcome.rendered.contains("❌ docs/INDEX.md is out of date"));
+    assert!(outcome.rendered.contains("✅ INDEX.md is up to date"));
+}
+
+#[test]
+fn catalog_template_exposes_subdirectories_for_directory_indexes() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        ".markdown-doc.toml",
+        "[catalog]\ntimestamp = \"omit\"\ndirectory_index = \"INDEX.md\"\ntemplate = \"index.j2\"\n",
+    );
+    write_file(
+        temp.path(),
+        "index.j2",
+        "# {{ directory }}\n{% for sub in subdirectories %}\n- [{{ sub }}/]({{ sub }}/INDEX.md)\n{% endfor %}\n",
+    );
+    write_file(temp.path(), "README.md", "# Project\n");
+    write_file(temp.path(), "docs/guide.md", "# Guide\n");
+    write_file(temp.path(), "docs/deep/nested/notes.md", "# Notes\n");
+
+    run_catalog(temp.path(), CatalogMode::Update);
+    assert_eq!(
+        fs::read_to_string(temp.path().join("INDEX.md")).unwrap(),
+        "# .\n- [docs/](docs/INDEX.md)\n"
+    );
+    assert_eq!(
+        fs::read_to_string(temp.path().join("docs/INDEX.md")).unwrap(),
+        "# docs\n- [deep/nested/](deep/nested/INDEX.md)\n"
+    );
+}
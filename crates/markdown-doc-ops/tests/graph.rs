@@ -2,7 +2,9 @@ use std::path::Path;
 
 use markdown_doc_config::{Config, LoadOptions};
 use markdown_doc_ops::refactor::graph::LinkOccurrence;
-use markdown_doc_ops::{Operations, ScanOptions};
+use markdown_doc_ops::{
+    GraphEdgeKind, GraphExportOptions, GraphNodeKind, OperationError, Operations, ScanOptions,
+};
 use tempfile::TempDir;
 
 fn write_file(base: &TempDir, path: &str, contents: &str) {
@@ -112,3 +114,227 @@ Go back to [Intro](../intro.md#overview).
         "faq definition in guide.md should be indexed"
     );
 }
+
+#[test]
+fn orphans_report_unlinked_and_unreachable_documents() {
+    let temp = TempDir::new().expect("tempdir");
+
+    write_file(&temp, "README.md", "# Home\n\n[Guide](docs/guide.md)\n");
+    write_file(&temp, "docs/guide.md", "# Guide\n\n[Back](../README.md)\n");
+    write_file(&temp, "docs/island-a.md", "# A\n\n[B](island-b.md)\n");
+    write_file(&temp, "docs/island-b.md", "# B\n\n[A](island-a.md)\n");
+    write_file(&temp, "docs/lonely.md", "# Lonely\n\n[Self](#lonely)\n");
+    write_file(&temp, "CHANGELOG.md", "# Changes\n");
+    write_file(
+        &temp,
+        ".markdown-doc.toml",
+        "[lint]\norphan_allow = [\"CHANGELOG.md\"]\n",
+    );
+
+    let config =
+        Config::load(LoadOptions::default().with_working_dir(temp.path())).expect("load config");
+    let ops = Operations::new(config);
+    let report = ops.orphans(ScanOptions::default()).expect("orphan report");
+
+    assert_eq!(report.roots, vec![Path::new("README.md").to_path_buf()]);
+    assert_eq!(
+        report.orphans,
+        vec![Path::new("docs/lonely.md").to_path_buf()]
+    );
+    assert_eq!(
+        report.unreachable,
+        vec![
+            Path::new("docs/island-a.md").to_path_buf(),
+            Path::new("docs/island-b.md").to_path_buf(),
+        ]
+    );
+}
+
+#[test]
+fn orphans_use_mkdocs_nav_pages_as_roots() {
+    let temp = TempDir::new().expect("tempdir");
+
+    write_file(
+        &temp,
+        "mkdocs.yml",
+        "site_name: Demo\nnav:\n  - Home: index.md\n  - Guides:\n      - 'Setup': guides/setup.md\n",
+    );
+    write_file(&temp, "docs/index.md", "# Home\n");
+    write_file(
+        &temp,
+        "docs/guides/setup.md",
+        "# Setup\n\n[Tips](tips.md)\n",
+    );
+    write_file(&temp, "docs/guides/tips.md", "# Tips\n");
+    write_file(
+        &temp,
+        ".markdown-doc.toml",
+        "[lint]\norphan_roots = [\"mkdocs.yml\"]\n",
+    );
+
+    let config =
+        Config::load(LoadOptions::default().with_working_dir(temp.path())).expect("load config");
+    let ops = Operations::new(config);
+    let report = ops.orphans(ScanOptions::default()).expect("orphan report");
+
+    assert_eq!(
+        report.roots,
+        vec![
+            Path::new("docs/guides/setup.md").to_path_buf(),
+            Path::new("docs/index.md").to_path_buf(),
+        ]
+    );
+    assert!(report.is_empty());
+}
+
+fn export_fixture() -> (TempDir, Operations) {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        &temp,
+        "README.md",
+        "# Home\n\n[Setup](docs/guide.md#setup), [again](docs/guide.md#setup), ![logo](img/logo.png) and [ref][g].\n\n[g]: docs/guide.md\n",
+    );
+    write_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\n## Setup\n\n[Home](../README.md), [deep](deep.md), [setup](#setup).\n",
+    );
+    write_file(
+        &temp,
+        "docs/deep.md",
+        "# Deep\n\n[Far](far.md)\n\n[unused]: ../README.md\n",
+    );
+    write_file(&temp, "docs/far.md", "# Far\n");
+    let config =
+        Config::load(LoadOptions::default().with_working_dir(temp.path())).expect("load config");
+    (temp, Operations::new(config))
+}
+
+fn edge_list(
+    export: &markdown_doc_ops::GraphExport,
+) -> Vec<(String, String, GraphEdgeKind, usize)> {
+    export
+        .edges
+        .iter()
+        .map(|edge| {
+            (
+                edge.source.clone(),
+                edge.target.clone(),
+                edge.kind,
+                edge.count,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn export_graph_aggregates_edges_and_optional_anchor_nodes() {
+    let (_temp, ops) = export_fixture();
+
+    let export = ops
+        .export_graph(GraphExportOptions::default())
+        .expect("export");
+    let edge = |source: &str, target: &str, kind, count| {
+        (source.to_string(), target.to_string(), kind, count)
+    };
+    assert_eq!(
+        edge_list(&export),
+        vec![
+            edge("README.md", "docs/guide.md", GraphEdgeKind::Link, 3),
+            edge("README.md", "img/logo.png", GraphEdgeKind::Image, 1),
+            edge("docs/deep.md", "README.md", GraphEdgeKind::Definition, 1),
+            edge("docs/deep.md", "docs/far.md", GraphEdgeKind::Link, 1),
+            edge("docs/guide.md", "README.md", GraphEdgeKind::Link, 1),
+            edge("docs/guide.md", "docs/deep.md", GraphEdgeKind::Link, 1),
+        ]
+    );
+    let logo = export
+        .nodes
+        .iter()
+        .find(|node| node.id == "img/logo.png")
+        .expect("asset node");
+    assert_eq!(logo.kind, GraphNodeKind::File);
+
+    let export = ops
+        .export_graph(GraphExportOptions {
+            anchors: true,
+            ..GraphExportOptions::default()
+        })
+        .expect("export with anchors");
+    let setup = export
+        .nodes
+        .iter()
+        .find(|node| node.id == "docs/guide.md#setup")
+        .expect("anchor node");
+    assert_eq!(setup.kind, GraphNodeKind::Anchor);
+    assert_eq!(setup.label, "Setup");
+    let edges = edge_list(&export);
+    assert!(edges.contains(&edge(
+        "README.md",
+        "docs/guide.md#setup",
+        GraphEdgeKind::Link,
+        2
+    )));
+    assert!(edges.contains(&edge(
+        "docs/guide.md",
+        "docs/guide.md#setup",
+        GraphEdgeKind::Link,
+        1
+    )));
+    assert!(edges.contains(&edge(
+        "docs/guide.md",
+        "docs/guide.md#setup",
+        GraphEdgeKind::Contains,
+        1
+    )));
+
+    let dot = export.to_dot();
+    assert!(dot.starts_with("digraph docs {"));
+    assert!(dot.contains("\"docs/guide.md#setup\" [label=\"Setup\", shape=ellipse];"));
+    assert!(dot.contains("\"README.md\" -> \"docs/guide.md#setup\" [weight=2, label=\"2\"];"));
+    let mermaid = export.to_mermaid();
+    assert!(mermaid.starts_with("flowchart LR\n"));
+    assert!(mermaid.contains("-->|ref|"));
+    let graphml = export.to_graphml();
+    assert!(graphml.contains("<node id=\"docs/guide.md#setup\">"));
+    assert!(graphml.contains("<data key=\"edge_kind\">contains</data>"));
+}
+
+#[test]
+fn export_graph_filters_by_glob_and_depth_from_root() {
+    let (_temp, ops) = export_fixture();
+
+    let export = ops
+        .export_graph(GraphExportOptions {
+            include: vec!["docs/**".into()],
+            ..GraphExportOptions::default()
+        })
+        .expect("glob export");
+    let ids: Vec<_> = export.nodes.iter().map(|node| node.id.as_str()).collect();
+    assert_eq!(ids, vec!["docs/deep.md", "docs/far.md", "docs/guide.md"]);
+    assert_eq!(export.edges.len(), 2);
+
+    let export = ops
+        .export_graph(GraphExportOptions {
+            root: Some("README.md".into()),
+            depth: Some(2),
+            ..GraphExportOptions::default()
+        })
+        .expect("depth export");
+    let documents: Vec<_> = export
+        .nodes
+        .iter()
+        .filter(|node| node.kind == GraphNodeKind::Document)
+        .map(|node| node.id.as_str())
+        .collect();
+    assert_eq!(
+        documents,
+        vec!["README.md", "docs/deep.md", "docs/guide.md"]
+    );
+
+    let invalid = ops.export_graph(GraphExportOptions {
+        depth: Some(1),
+        ..GraphExportOptions::default()
+    });
+    assert!(matches!(invalid, Err(OperationError::InvalidInput(_))));
+}
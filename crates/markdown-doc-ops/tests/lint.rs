@@ -1,10 +1,13 @@
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
+use std::process::Command;
 
 use markdown_doc_config::{Config, LintRule, LoadOptions};
 use markdown_doc_format::LintFormat;
-use markdown_doc_ops::{LintOptions, Operations, ScanOptions};
+use markdown_doc_ops::{
+    ChangedSince, LintOptions, OperationError, Operations, ScanOptions, DEFAULT_WORDS_FILE,
+};
 use tempfile::TempDir;
 
 fn setup_file(dir: &TempDir, name: &str, contents: &str) {
@@ -25,9 +28,14 @@ fn lint_options(paths: &[&str]) -> LintOptions {
         scan: ScanOptions {
             paths: paths.iter().map(PathBuf::from).collect(),
             staged: false,
+            changed_since: None,
             respect_ignore: true,
         },
         format: LintFormat::Plain,
+        baseline: None,
+        write_baseline: false,
+        add_words: false,
+        cache: false,
     }
 }
 
@@ -210,3 +218,993 @@ fn per_path_severity_override_reenables_rule() {
         "expected broken-links finding even though base severity was ignore",
     );
 }
+
+#[test]
+fn custom_rules_report_matches_within_scope() {
+    let temp = TempDir::new().expect("tempdir");
+    let config_contents = concat!(
+        "[lint]\n",
+        "rules = []\n\n",
+        "[[lint.custom]]\n",
+        "id = \"no-todo\"\n",
+        "pattern = \"TODO\"\n",
+        "paths = [\"docs/**\"]\n",
+        "message = \"Remove '{match}' from published docs\"\n\n",
+        "[[lint.custom]]\n",
+        "id = \"fence-language\"\n",
+        "target = \"code-block\"\n",
+        "pattern = \"^$\"\n",
+        "message = \"Fenced code blocks need a language\"\n",
+        "severity = \"warning\"\n",
+    );
+    setup_file(&temp, ".markdown-doc.toml", config_contents);
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\nTODO: finish\n\n```\nplain\n```\n",
+    );
+    setup_file(&temp, "notes.md", "# Notes\n\nTODO: allowed here\n");
+
+    let ops = Operations::new(base_config(&temp));
+    let outcome = ops
+        .lint(lint_options(&["docs/guide.md", "notes.md"]))
+        .expect("lint execution");
+
+    assert_eq!(outcome.exit_code, 1);
+    assert_eq!(outcome.report.error_count, 1);
+    assert_eq!(outcome.report.warning_count, 1);
+
+    let todo = outcome
+        .report
+        .findings
+        .iter()
+        .find(|finding| finding.rule.as_str() == "no-todo")
+        .expect("no-todo finding");
+    assert_eq!(todo.path, PathBuf::from("docs/guide.md"));
+    assert_eq!(todo.line, 3);
+    assert_eq!(todo.message, "Remove 'TODO' from published docs");
+
+    let fence = outcome
+        .report
+        .findings
+        .iter()
+        .find(|finding| finding.rule.as_str() == "fence-language")
+        .expect("fence-language finding");
+    assert_eq!(fence.line, 5);
+}
+
+#[test]
+fn custom_require_and_section_rules() {
+    let temp = TempDir::new().expect("tempdir");
+    let config_contents = concat!(
+        "[lint]\n",
+        "rules = []\n\n",
+        "[[lint.custom]]\n",
+        "id = \"ci-badge\"\n",
+        "pattern = \"!\\\\[CI\\\\]\"\n",
+        "require = true\n",
+        "paths = [\"README.md\"]\n",
+        "message = \"README must include the CI badge\"\n\n",
+        "[[lint.custom]]\n",
+        "id = \"section-length\"\n",
+        "target = \"section\"\n",
+        "max_lines = 3\n",
+        "message = \"Section '{match}' is too long\"\n",
+    );
+    setup_file(&temp, ".markdown-doc.toml", config_contents);
+    setup_file(
+        &temp,
+        "README.md",
+        "# Project\n\n## Long\n\none\ntwo\nthree\n\n## Short\n",
+    );
+
+    let ops = Operations::new(base_config(&temp));
+    let outcome = ops
+        .lint(lint_options(&["README.md"]))
+        .expect("lint execution");
+
+    let rules: Vec<&str> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| finding.rule.as_str())
+        .collect();
+    assert_eq!(rules, vec!["ci-badge", "section-length"]);
+    assert!(outcome.report.findings[1]
+        .message
+        .starts_with("Section 'Long' is too long"));
+}
+
+#[test]
+fn inline_suppressions_silence_findings_by_line_range() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Overview\n\n\
+<!-- markdown-doc-disable-next-line broken-anchors -->\n\
+See [one](#one).\n\
+See [two](#two).\n\n\
+<!-- markdown-doc-disable broken-anchors -->\n\
+See [three](#three).\n\
+<!-- markdown-doc-enable broken-anchors -->\n\
+See [four](#four).\n",
+    );
+    setup_file(
+        &temp,
+        "docs/legacy.md",
+        "<!-- markdown-doc-disable-file -->\n# Legacy\n\nSee [gone](#gone).\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BrokenAnchors];
+
+    let ops = Operations::new(config);
+    let outcome = ops.lint(lint_options(&["docs"])).expect("lint execution");
+
+    let lines: Vec<usize> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| {
+            assert_eq!(finding.path, PathBuf::from("docs/guide.md"));
+            finding.line
+        })
+        .collect();
+    assert_eq!(lines, vec![5, 10]);
+}
+
+#[test]
+fn unused_suppressions_are_reported_when_enabled() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Overview\n\n\
+<!-- markdown-doc-disable-next-line broken-anchors -->\n\
+See [overview](#overview).\n\n\
+```markdown\n<!-- markdown-doc-disable-file -->\n```\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BrokenAnchors, LintRule::UnusedSuppressions];
+
+    let ops = Operations::new(config);
+    let outcome = ops
+        .lint(lint_options(&["docs/guide.md"]))
+        .expect("lint execution");
+
+    assert_eq!(outcome.report.findings.len(), 1);
+    let finding = &outcome.report.findings[0];
+    assert_eq!(finding.rule, LintRule::UnusedSuppressions);
+    assert_eq!(finding.line, 3);
+    assert!(finding
+        .message
+        .contains("markdown-doc-disable-next-line broken-anchors"));
+}
+
+#[test]
+fn suppressions_naming_unknown_rules_are_reported() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Overview\n\n\
+<!-- markdown-doc-disable-next-line broken-link -->\n\
+See [missing](missing.md).\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BrokenLinks];
+
+    let ops = Operations::new(config.clone());
+    let outcome = ops
+        .lint(lint_options(&["docs/guide.md"]))
+        .expect("lint execution");
+
+    let mut rules: Vec<_> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| (finding.rule.clone(), finding.line))
+        .collect();
+    rules.sort_by_key(|(_, line)| *line);
+    assert_eq!(
+        rules,
+        vec![
+            (LintRule::UnusedSuppressions, 3),
+            (LintRule::BrokenLinks, 4),
+        ]
+    );
+    assert!(outcome
+        .report
+        .findings
+        .iter()
+        .any(|finding| finding.message.contains("names unknown rule 'broken-link'")));
+
+    // Reported once, not again as an unused directive.
+    config.lint.rules = vec![LintRule::BrokenLinks, LintRule::UnusedSuppressions];
+    let outcome = Operations::new(config)
+        .lint(lint_options(&["docs/guide.md"]))
+        .expect("lint execution");
+    assert_eq!(
+        outcome
+            .report
+            .findings
+            .iter()
+            .filter(|finding| finding.rule == LintRule::UnusedSuppressions)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn baseline_matches_file_level_findings_after_first_line_edits() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        ".markdown-doc.toml",
+        concat!(
+            "[lint]\n",
+            "rules = [\"orphans\"]\n\n",
+            "[[lint.custom]]\n",
+            "id = \"ci-badge\"\n",
+            "pattern = \"!\\\\[CI\\\\]\"\n",
+            "require = true\n",
+            "paths = [\"docs/**\"]\n",
+            "message = \"Add the CI badge\"\n",
+        ),
+    );
+    setup_file(&temp, "README.md", "# Home\n");
+    setup_file(&temp, "docs/lonely.md", "# Lonely\n\nNothing links here.\n");
+
+    let ops = Operations::new(base_config(&temp));
+    let mut options = lint_options(&[]);
+    options.write_baseline = true;
+    let written = ops.lint(options).expect("write baseline");
+    let summary = written.report.baseline.as_ref().expect("baseline summary");
+    assert_eq!(summary.known, 2);
+
+    setup_file(
+        &temp,
+        "docs/lonely.md",
+        "# A lonely page\n\nNothing links here.\n",
+    );
+    let mut options = lint_options(&[]);
+    options.baseline = Some(PathBuf::from(".markdown-doc-baseline.json"));
+    let outcome = ops.lint(options).expect("lint with baseline");
+
+    assert!(
+        outcome.report.findings.is_empty(),
+        "{:?}",
+        outcome.report.findings
+    );
+    let summary = outcome.report.baseline.as_ref().expect("baseline summary");
+    assert_eq!((summary.known, summary.new), (2, 0));
+}
+
+#[test]
+fn baseline_suppresses_known_findings_and_reports_new_ones() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Overview\n\nSee [old](#old).\nSee [stale](#stale).\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BrokenAnchors];
+    let ops = Operations::new(config);
+
+    let mut options = lint_options(&["docs"]);
+    options.write_baseline = true;
+    let written = ops.lint(options).expect("write baseline");
+    assert_eq!(written.exit_code, 0);
+    assert!(temp.path().join(".markdown-doc-baseline.json").exists());
+
+    // Shift existing findings down, fix one, and introduce a new one.
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Overview\n\nIntro paragraph.\n\nSee [old](#old).\nSee [new](#new).\n",
+    );
+
+    let mut options = lint_options(&["docs"]);
+    options.baseline = Some(PathBuf::from(".markdown-doc-baseline.json"));
+    let outcome = ops.lint(options).expect("lint with baseline");
+
+    assert_eq!(outcome.exit_code, 1);
+    assert_eq!(outcome.report.findings.len(), 1);
+    assert_eq!(outcome.report.findings[0].line, 6);
+    let summary = outcome.report.baseline.expect("baseline summary");
+    assert_eq!((summary.known, summary.new, summary.fixed), (1, 1, 1));
+    assert!(outcome.rendered.contains("1 known, 1 new, 1 fixed"));
+}
+
+#[test]
+fn orphans_rule_flags_documents_outside_lint_targets_links() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(&temp, "README.md", "# Home\n\n[Guide](docs/guide.md)\n");
+    setup_file(&temp, "docs/guide.md", "# Guide\n");
+    setup_file(&temp, "docs/stale.md", "# Stale\n");
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::Orphans];
+
+    let ops = Operations::new(config);
+    let outcome = ops.lint(lint_options(&["docs"])).expect("lint execution");
+
+    assert_eq!(outcome.report.findings.len(), 1);
+    let finding = &outcome.report.findings[0];
+    assert_eq!(finding.rule, LintRule::Orphans);
+    assert_eq!(finding.path, PathBuf::from("docs/stale.md"));
+}
+
+#[test]
+fn style_rules_report_formatting_issues() {
+    let temp = TempDir::new().expect("tempdir");
+    let long_line = "word ".repeat(30).trim_end().to_string();
+    let contents = format!(
+        "---\ntitle: Demo\n---\n# Guide:\n\
+Intro text.   \n\
+Hard break  \n\
+{long_line}\n\
+See https://example.com/{}\n\n\
+- one\n\
+* two\n\n\
+*first* and _second_\n\
+```\n{long_line}   \n```\n\
+## Next\n",
+        "a".repeat(120)
+    );
+    setup_file(&temp, "docs/style.md", &contents);
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![
+        LintRule::LineLength,
+        LintRule::TrailingWhitespace,
+        LintRule::HeadingPunctuation,
+        LintRule::ListMarkerStyle,
+        LintRule::EmphasisStyle,
+        LintRule::BlanksAroundHeadings,
+        LintRule::BlanksAroundFences,
+        LintRule::FencedCodeLanguage,
+    ];
+
+    let ops = Operations::new(config);
+    let outcome = ops
+        .lint(lint_options(&["docs/style.md"]))
+        .expect("lint execution");
+
+    let mut found: Vec<(String, usize)> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| (finding.rule.as_str().to_string(), finding.line))
+        .collect();
+    found.sort();
+
+    let mut expected: Vec<(String, usize)> = [
+        ("blanks-around-fences", 14),
+        ("blanks-around-fences", 16),
+        ("blanks-around-headings", 4),
+        ("blanks-around-headings", 17),
+        ("emphasis-style", 13),
+        ("fenced-code-language", 14),
+        ("heading-punctuation", 4),
+        ("line-length", 7),
+        ("list-marker-style", 11),
+        ("trailing-whitespace", 5),
+    ]
+    .iter()
+    .map(|(rule, line)| (rule.to_string(), *line))
+    .collect();
+    expected.sort();
+
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn style_rules_keep_line_numbers_after_multibyte_front_matter() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/doc.md",
+        "---\ntitle: \"日本語のタイトル、とても長い説明文です\"\nsummary: \"Ünïcödé ✓ — ещё\"\n---\n# Guide\n\nIntro text.\n\nMore text.\n\n```\ncode\n```\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BlanksAroundFences, LintRule::FencedCodeLanguage];
+
+    let ops = Operations::new(config);
+    let outcome = ops
+        .lint(lint_options(&["docs/doc.md"]))
+        .expect("lint execution");
+
+    let found: Vec<(&str, usize)> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| (finding.rule.as_str(), finding.line))
+        .collect();
+    assert_eq!(found, vec![("fenced-code-language", 11)]);
+}
+
+#[test]
+fn style_rules_check_documents_that_open_with_a_thematic_break() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/doc.md",
+        "---\n\nIntro text.\t\n\n---\n\nMore text.\t\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::TrailingWhitespace];
+
+    let ops = Operations::new(config);
+    let outcome = ops
+        .lint(lint_options(&["docs/doc.md"]))
+        .expect("lint execution");
+
+    let lines: Vec<usize> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| finding.line)
+        .collect();
+    assert_eq!(lines, vec![3, 7]);
+}
+
+#[test]
+fn style_options_are_configurable() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        ".markdown-doc.toml",
+        "[lint]\nrules = [\"line-length\", \"list-marker-style\", \"trailing-whitespace\"]\n\n\
+[lint.style]\nmax_line_length = 20\nlist_marker = \"asterisk\"\nallow_hard_breaks = false\n",
+    );
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\n- this line is longer than twenty\n- short  \n",
+    );
+
+    let ops = Operations::new(base_config(&temp));
+    let outcome = ops
+        .lint(lint_options(&["docs/guide.md"]))
+        .expect("lint execution");
+
+    let rules: Vec<&str> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| finding.rule.as_str())
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            "line-length",
+            "list-marker-style",
+            "list-marker-style",
+            "trailing-whitespace"
+        ]
+    );
+}
+
+#[test]
+fn line_length_only_exempts_long_urls() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\n\
+This prose line keeps going well past the limit, see https://e.io for more.\n\n\
+Read [the guide](https://example.com/a/very/long/path/to/the/guide/page).\n\n\
+- [A link whose text alone runs past the configured limit](https://e.io)\n\n\
+https://example.com/a/very/long/url/that/cannot/be/wrapped/at/all\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::LineLength];
+    config.lint.style.max_line_length = 40;
+
+    let ops = Operations::new(config);
+    let outcome = ops
+        .lint(lint_options(&["docs/guide.md"]))
+        .expect("lint execution");
+
+    let lines: Vec<usize> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| finding.line)
+        .collect();
+    assert_eq!(lines, vec![3]);
+}
+
+#[test]
+fn spelling_flags_unknown_prose_words_with_suggestions() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "---\ntitle: Qwzx\n---\n# Installation Guide\n\n\
+We recieve requests and don't drop them.\n\n\
+Run `frobnicate --qwzx` or visit https://example.com/qwzx <https://qwzx.dev>.\n\n\
+```sh\nqwzx --install\n```\n\n\
+The GitHub API uses README files; see config.toml.\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::Spelling];
+
+    let ops = Operations::new(config);
+    let outcome = ops
+        .lint(lint_options(&["docs/guide.md"]))
+        .expect("lint execution");
+
+    assert_eq!(outcome.report.findings.len(), 1, "{:?}", outcome.report);
+    let finding = &outcome.report.findings[0];
+    assert_eq!(finding.rule, LintRule::Spelling);
+    assert_eq!(finding.line, 6);
+    assert!(
+        finding
+            .message
+            .starts_with("Unknown word 'recieve'. Did you mean 'receive'"),
+        "{}",
+        finding.message
+    );
+}
+
+#[test]
+fn spelling_accepts_project_words_and_add_words_records_unknown_ones() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        ".markdown-doc.toml",
+        "[lint]\nrules = [\"spelling\"]\n\n[lint.spelling]\nwords = [\"Frobnicator\"]\n",
+    );
+    setup_file(&temp, ".markdown-doc-words", "# Project terms\nwidgetry\n");
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\nThe frobnicator and widgetry handle zorblax and quuxing.\n",
+    );
+
+    let ops = Operations::new(base_config(&temp));
+    let outcome = ops
+        .lint(lint_options(&["docs/guide.md"]))
+        .expect("lint execution");
+    let words: Vec<&str> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| finding.message.split('\'').nth(1).unwrap())
+        .collect();
+    assert_eq!(words, vec!["zorblax", "quuxing"]);
+
+    let mut options = lint_options(&["docs/guide.md"]);
+    options.add_words = true;
+    let added = ops.lint(options).expect("add words");
+    assert_eq!(added.exit_code, 0);
+    let summary = added.report.words_added.expect("words summary");
+    assert_eq!(summary.words, vec!["quuxing", "zorblax"]);
+    assert_eq!(
+        fs::read_to_string(temp.path().join(".markdown-doc-words")).unwrap(),
+        "# Project terms\nwidgetry\nquuxing\nzorblax\n"
+    );
+
+    let ops = Operations::new(base_config(&temp));
+    let clean = ops
+        .lint(lint_options(&["docs/guide.md"]))
+        .expect("lint execution");
+    assert!(clean.report.findings.is_empty());
+}
+
+#[test]
+fn add_words_without_word_files_are_read_back_on_the_next_run() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\nThe zorblax handles quuxing.\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::Spelling];
+    config.lint.spelling.word_files = Vec::new();
+    let ops = Operations::new(config);
+
+    let mut options = lint_options(&["docs/guide.md"]);
+    options.add_words = true;
+    let added = ops.lint(options).expect("add words");
+    let summary = added.report.words_added.expect("words summary");
+    assert_eq!(summary.words, vec!["quuxing", "zorblax"]);
+    assert!(temp.path().join(DEFAULT_WORDS_FILE).exists());
+
+    let clean = ops
+        .lint(lint_options(&["docs/guide.md"]))
+        .expect("lint execution");
+    assert!(clean.report.findings.is_empty(), "{:?}", clean.report);
+}
+
+#[test]
+fn duplicate_sections_cluster_similar_bodies_across_files() {
+    let temp = TempDir::new().expect("tempdir");
+    let setup = "Install the toolkit with cargo install, then run the bootstrap script from the \
+repository root to fetch fixtures and configure the local environment for development work.";
+    setup_file(
+        &temp,
+        "docs/a.md",
+        &format!("# A\n\n## Setup\n\n{setup}\n\n## Usage\n\nRun it.\n"),
+    );
+    setup_file(
+        &temp,
+        "docs/b.md",
+        &format!("# B\n\nIntro.\n\n## Setup\n\n{setup} Restart your shell afterwards.\n"),
+    );
+    setup_file(
+        &temp,
+        "docs/c.md",
+        "# C\n\n## Setup\n\nThis setup section describes something else entirely, with \
+different words about databases, migrations, credentials, and scheduled backups of production.\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::DuplicateSections];
+    let ops = Operations::new(config);
+
+    let report = ops
+        .duplicates(ScanOptions::default())
+        .expect("duplicate analysis");
+    assert_eq!(report.clusters.len(), 1);
+    let cluster = &report.clusters[0];
+    assert_eq!(cluster.heading, "setup");
+    assert!(cluster.similarity >= 0.7, "{}", cluster.similarity);
+    let members: Vec<(PathBuf, usize)> = cluster
+        .sections
+        .iter()
+        .map(|section| (section.path.clone(), section.line))
+        .collect();
+    assert_eq!(
+        members,
+        vec![
+            (PathBuf::from("docs/a.md"), 3),
+            (PathBuf::from("docs/b.md"), 5)
+        ]
+    );
+    let contents = fs::read_to_string(temp.path().join("docs/a.md")).unwrap();
+    let range = cluster.sections[0].byte_range.clone();
+    assert!(contents[range].starts_with("## Setup"));
+
+    // Only the linted file is reported, but it is compared against the whole project.
+    let outcome = ops
+        .lint(lint_options(&["docs/b.md"]))
+        .expect("lint execution");
+    assert_eq!(outcome.report.findings.len(), 1);
+    let finding = &outcome.report.findings[0];
+    assert_eq!(finding.rule, LintRule::DuplicateSections);
+    assert_eq!(finding.line, 5);
+    assert!(
+        finding.message.contains("docs/a.md:3"),
+        "{}",
+        finding.message
+    );
+}
+
+fn cached_lint_options(paths: &[&str]) -> LintOptions {
+    LintOptions {
+        cache: true,
+        ..lint_options(paths)
+    }
+}
+
+#[test]
+fn lint_cache_reuses_unchanged_files_until_content_or_config_changes() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(&temp, "docs/a.md", "# A\n\n## Setup\n\n## Setup\n");
+    setup_file(&temp, "docs/b.md", "# B\n\nText.\n");
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::DuplicateAnchors, LintRule::BrokenLinks];
+    let ops = Operations::new(config.clone());
+    let paths = ["docs/a.md", "docs/b.md"];
+
+    let first = ops.lint(cached_lint_options(&paths)).expect("first run");
+    assert_eq!(first.files_cached, 0);
+    assert_eq!(first.report.findings.len(), 1);
+    assert!(temp.path().join(".markdown-doc/cache/lint.json").is_file());
+
+    let second = ops.lint(cached_lint_options(&paths)).expect("second run");
+    assert_eq!(second.files_cached, 2);
+    assert_eq!(second.rendered, first.rendered);
+
+    setup_file(&temp, "docs/b.md", "# B\n\nSee [missing](missing.md).\n");
+    let edited = ops.lint(cached_lint_options(&paths)).expect("edited run");
+    assert_eq!(edited.files_cached, 1);
+    assert_eq!(edited.report.findings.len(), 2);
+
+    config.lint.rules = vec![LintRule::DuplicateAnchors];
+    let reconfigured = Operations::new(config)
+        .lint(cached_lint_options(&paths))
+        .expect("reconfigured run");
+    assert_eq!(reconfigured.files_cached, 0);
+    assert_eq!(reconfigured.report.findings.len(), 1);
+}
+
+#[test]
+fn lint_cache_rechecks_files_when_linked_anchors_change() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(&temp, "docs/a.md", "# A\n\nSee [intro](b.md#intro).\n");
+    setup_file(&temp, "docs/b.md", "# B\n\n## Intro\n\nHello.\n");
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BrokenAnchors];
+    let ops = Operations::new(config);
+
+    let first = ops.lint(cached_lint_options(&["docs/a.md"])).expect("lint");
+    assert!(first.report.findings.is_empty());
+
+    // Editing prose leaves the anchors intact, so the cached result still holds.
+    setup_file(&temp, "docs/b.md", "# B\n\n## Intro\n\nHello again.\n");
+    let unchanged = ops.lint(cached_lint_options(&["docs/a.md"])).expect("lint");
+    assert_eq!(unchanged.files_cached, 1);
+
+    setup_file(
+        &temp,
+        "docs/b.md",
+        "# B\n\n## Introduction\n\nHello again.\n",
+    );
+    let renamed = ops.lint(cached_lint_options(&["docs/a.md"])).expect("lint");
+    assert_eq!(renamed.files_cached, 0);
+    assert_eq!(renamed.report.findings.len(), 1);
+    assert_eq!(renamed.report.findings[0].rule, LintRule::BrokenAnchors);
+}
+
+fn git(dir: &TempDir, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir.path())
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+fn linted_paths(outcome: &markdown_doc_ops::LintOutcome) -> Vec<PathBuf> {
+    outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| finding.path.clone())
+        .collect()
+}
+
+#[test]
+fn staged_lint_checks_index_contents_and_follows_renames() {
+    let temp = TempDir::new().expect("tempdir");
+    git(&temp, &["init", "-q"]);
+    setup_file(&temp, "docs/guide.md", "# Guide\n\nSee [api](api.md).\n");
+    setup_file(&temp, "docs/api.md", "# API\n");
+    setup_file(&temp, "docs/old.md", "# Old\n");
+    setup_file(&temp, "docs/gone.md", "# Gone\n");
+    git(&temp, &["add", "."]);
+    git(&temp, &["commit", "-q", "-m", "init"]);
+
+    // Stage a broken link, then fix it only in the working tree.
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\nSee [api](missing.md).\n",
+    );
+    git(&temp, &["add", "docs/guide.md"]);
+    setup_file(&temp, "docs/guide.md", "# Guide\n\nSee [api](api.md).\n");
+    git(&temp, &["mv", "docs/old.md", "docs/new.md"]);
+    setup_file(&temp, "docs/new.md", "# New\n\nSee [gone](gone.md).\n");
+    git(&temp, &["add", "docs/new.md"]);
+    git(&temp, &["rm", "-q", "docs/gone.md"]);
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BrokenLinks];
+    let ops = Operations::new(config);
+    let mut options = lint_options(&[]);
+    options.scan.staged = true;
+    let outcome = ops.lint(options).expect("lint execution");
+
+    assert_eq!(outcome.report.files_scanned, 2);
+    assert_eq!(
+        linted_paths(&outcome),
+        vec![PathBuf::from("docs/guide.md"), PathBuf::from("docs/new.md")]
+    );
+    assert!(outcome.report.findings[0].message.contains("missing.md"));
+    // `gone.md` was deleted from the index, so links to it are broken.
+    assert!(outcome.report.findings[1].message.contains("gone.md"));
+}
+
+#[test]
+fn staged_orphans_count_links_from_index_contents() {
+    let temp = TempDir::new().expect("tempdir");
+    git(&temp, &["init", "-q"]);
+    setup_file(&temp, "README.md", "# Home\n");
+    setup_file(&temp, "docs/page.md", "# Page\n");
+    git(&temp, &["add", "."]);
+    git(&temp, &["commit", "-q", "-m", "init"]);
+
+    // The inbound link exists only in the index; the working tree drops it again.
+    setup_file(&temp, "README.md", "# Home\n\n[Page](docs/page.md)\n");
+    setup_file(&temp, "docs/page.md", "# Page\n\nMore.\n");
+    git(&temp, &["add", "."]);
+    setup_file(&temp, "README.md", "# Home\n");
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::Orphans];
+    let ops = Operations::new(config);
+
+    let mut options = lint_options(&[]);
+    options.scan.staged = true;
+    let staged = ops.lint(options).expect("staged lint");
+    assert!(staged.report.findings.is_empty(), "{:?}", staged.report);
+
+    let working = ops.lint(lint_options(&["docs"])).expect("lint execution");
+    assert_eq!(linted_paths(&working), vec![PathBuf::from("docs/page.md")]);
+}
+
+#[test]
+fn staged_baseline_fingerprints_index_contents() {
+    let temp = TempDir::new().expect("tempdir");
+    git(&temp, &["init", "-q"]);
+    setup_file(&temp, "docs/guide.md", "# Guide\n\nSee [x](missing.md).\n");
+    git(&temp, &["add", "."]);
+    git(&temp, &["commit", "-q", "-m", "init"]);
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BrokenLinks];
+    let ops = Operations::new(config);
+    let mut options = lint_options(&["docs"]);
+    options.write_baseline = true;
+    ops.lint(options).expect("write baseline");
+    let baseline_path = temp.path().join(".markdown-doc-baseline.json");
+    let recorded = fs::read_to_string(&baseline_path).expect("read baseline");
+
+    // Stage an unrelated edit, then shift the broken link only in the working tree.
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\nSee [x](missing.md).\n\nMore.\n",
+    );
+    git(&temp, &["add", "docs/guide.md"]);
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\nIntro.\n\nSee [x](missing.md).\n\nMore.\n",
+    );
+
+    let mut options = lint_options(&[]);
+    options.scan.staged = true;
+    options.baseline = Some(PathBuf::from(".markdown-doc-baseline.json"));
+    let outcome = ops.lint(options).expect("lint with baseline");
+    let summary = outcome.report.baseline.expect("baseline summary");
+    assert_eq!((summary.known, summary.new, summary.fixed), (1, 0, 0));
+    assert_eq!(outcome.exit_code, 0);
+
+    let mut options = lint_options(&[]);
+    options.scan.staged = true;
+    options.write_baseline = true;
+    ops.lint(options).expect("write staged baseline");
+    assert_eq!(
+        fs::read_to_string(&baseline_path).expect("read baseline"),
+        recorded
+    );
+}
+
+#[test]
+fn since_and_changed_from_limit_lint_to_changed_files() {
+    let temp = TempDir::new().expect("tempdir");
+    git(&temp, &["init", "-q", "-b", "main"]);
+    setup_file(&temp, "a.md", "# A\n\n[x](missing.md)\n");
+    setup_file(&temp, "b.md", "# B\n\n[x](missing.md)\n");
+    setup_file(&temp, "c.md", "# C\n\n[x](missing.md)\n");
+    git(&temp, &["add", "."]);
+    git(&temp, &["commit", "-q", "-m", "init"]);
+    git(&temp, &["checkout", "-q", "-b", "feature"]);
+    setup_file(&temp, "b.md", "# B\n\n[y](missing.md)\n");
+    git(&temp, &["commit", "-q", "-am", "edit b"]);
+    setup_file(&temp, "c.md", "# C\n\n[z](missing.md)\n");
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BrokenLinks];
+    let ops = Operations::new(config);
+
+    let mut options = lint_options(&[]);
+    options.scan.changed_since = Some(ChangedSince::MergeBase("main".into()));
+    let outcome = ops.lint(options).expect("lint execution");
+    assert_eq!(linted_paths(&outcome), vec![PathBuf::from("b.md")]);
+
+    let mut options = lint_options(&[]);
+    options.scan.changed_since = Some(ChangedSince::Revision("HEAD".into()));
+    let outcome = ops.lint(options).expect("lint execution");
+    assert_eq!(linted_paths(&outcome), vec![PathBuf::from("c.md")]);
+
+    let mut options = lint_options(&[]);
+    options.scan.staged = true;
+    options.scan.changed_since = Some(ChangedSince::Revision("HEAD".into()));
+    assert!(ops.lint(options).is_err());
+
+    // Revisions are never passed through as git options.
+    for changed_since in [
+        ChangedSince::Revision("--output=out.txt".into()),
+        ChangedSince::MergeBase("--output=out.txt".into()),
+    ] {
+        let mut options = lint_options(&[]);
+        options.scan.changed_since = Some(changed_since);
+        let result = ops.lint(options);
+        assert!(
+            matches!(&result, Err(OperationError::InvalidInput(message)) if message.contains("cannot start with '-'"))
+        );
+    }
+    assert!(!temp.path().join("out.txt").exists());
+}
+
+#[test]
+fn suffixed_anchors_resolve_for_repeated_headings() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/api.md",
+        "# API\n\n## Usage\n\n## Usage\n\nSee [second](#usage-1) and [third](#usage-2).\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BrokenAnchors, LintRule::DuplicateAnchors];
+    let ops = Operations::new(config);
+    let outcome = ops
+        .lint(lint_options(&["docs/api.md"]))
+        .expect("lint execution");
+
+    let messages: Vec<(LintRule, usize, &str)> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| (finding.rule.clone(), finding.line, finding.message.as_str()))
+        .collect();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+    assert_eq!(messages[0].0, LintRule::DuplicateAnchors);
+    assert!(messages[0].2.contains("resolves to '#usage-1'"));
+    assert_eq!(messages[1].0, LintRule::BrokenAnchors);
+    assert!(messages[1].2.contains("#usage-2"));
+}
+
+#[test]
+fn explicit_heading_ids_and_html_anchors_resolve() {
+    let temp = TempDir::new().expect("tempdir");
+    setup_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\n## Setup {#install}\n\n<a id=\"FAQ-1\"></a>\nQuestion.\n\n<a name=\"legacy\"></a>\n\n```html\n<a id=\"in-code\"></a>\n```\n\nSee [install](#install), [faq](#faq-1), and [code](#in-code).\n",
+    );
+    setup_file(
+        &temp,
+        "docs/index.md",
+        "# Index\n\n[Setup](guide.md#install), [FAQ](guide.md#FAQ-1), [legacy](guide.md#legacy), [old](guide.md#setup).\n",
+    );
+
+    let mut config = base_config(&temp);
+    config.lint.rules = vec![LintRule::BrokenAnchors];
+    let ops = Operations::new(config);
+    let outcome = ops
+        .lint(lint_options(&["docs/guide.md", "docs/index.md"]))
+        .expect("lint execution");
+
+    let messages: Vec<String> = outcome
+        .report
+        .findings
+        .iter()
+        .map(|finding| format!("{}:{}", finding.path.display(), finding.message))
+        .collect();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+    assert!(messages[0].contains("guide.md") && messages[0].contains("#in-code"));
+    assert!(messages[1].contains("index.md") && messages[1].contains("#setup"));
+}
@@ -1,8 +1,12 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use markdown_doc_config::{Config, LoadOptions};
-use markdown_doc_ops::{MvFileStatus, MvOptions, Operations, ScanOptions};
+use markdown_doc_ops::refactor::rewrite::FileMove;
+use markdown_doc_ops::{
+    load_move_map, MvFileStatus, MvOptions, OperationError, Operations, ScanOptions,
+};
 use tempfile::TempDir;
 
 fn write_file(base: &TempDir, path: &str, contents: &str) {
@@ -49,11 +53,15 @@ fn mv_updates_links_and_moves_file() {
     let outcome = ops
         .mv(MvOptions {
             scan: ScanOptions::default(),
-            source: PathBuf::from("intro.md"),
-            destination: PathBuf::from("docs/intro.md"),
+            moves: vec![FileMove {
+                from: PathBuf::from("intro.md"),
+                to: PathBuf::from("docs/intro.md"),
+            }],
             dry_run: false,
             force: false,
             create_backup: false,
+            git: Some(false),
+            allow_dirty: false,
             quiet: false,
             json: false,
         })
@@ -75,7 +83,7 @@ fn mv_updates_links_and_moves_file() {
     let relocated = outcome
         .changes
         .iter()
-        .find(|change| change.original_path == PathBuf::from("intro.md"))
+        .find(|change| change.original_path == Path::new("intro.md"))
         .expect("rename change present");
     assert_eq!(relocated.status, MvFileStatus::Relocated);
 }
@@ -110,11 +118,15 @@ fn mv_dry_run_emits_diff_without_writing() {
     let outcome = ops
         .mv(MvOptions {
             scan: ScanOptions::default(),
-            source: PathBuf::from("intro.md"),
-            destination: PathBuf::from("docs/intro.md"),
+            moves: vec![FileMove {
+                from: PathBuf::from("intro.md"),
+                to: PathBuf::from("docs/intro.md"),
+            }],
             dry_run: true,
             force: false,
             create_backup: false,
+            git: Some(false),
+            allow_dirty: false,
             quiet: false,
             json: false,
         })
@@ -160,11 +172,15 @@ fn mv_creates_backups_when_requested() {
 
     ops.mv(MvOptions {
         scan: ScanOptions::default(),
-        source: PathBuf::from("intro.md"),
-        destination: PathBuf::from("docs/intro.md"),
+        moves: vec![FileMove {
+            from: PathBuf::from("intro.md"),
+            to: PathBuf::from("docs/intro.md"),
+        }],
         dry_run: false,
         force: false,
         create_backup: true,
+        git: Some(false),
+        allow_dirty: false,
         quiet: false,
         json: false,
     })
@@ -209,11 +225,15 @@ fn mv_respects_ignore_filters() {
 
     ops.mv(MvOptions {
         scan: ScanOptions::default(),
-        source: PathBuf::from("intro.md"),
-        destination: PathBuf::from("docs/intro.md"),
+        moves: vec![FileMove {
+            from: PathBuf::from("intro.md"),
+            to: PathBuf::from("docs/intro.md"),
+        }],
         dry_run: false,
         force: false,
         create_backup: false,
+        git: Some(false),
+        allow_dirty: false,
         quiet: false,
         json: false,
     })
@@ -236,13 +256,18 @@ fn mv_respects_ignore_filters() {
         scan: ScanOptions {
             paths: Vec::new(),
             staged: false,
+            changed_since: None,
             respect_ignore: false,
         },
-        source: PathBuf::from("intro.md"),
-        destination: PathBuf::from("docs/intro.md"),
+        moves: vec![FileMove {
+            from: PathBuf::from("intro.md"),
+            to: PathBuf::from("docs/intro.md"),
+        }],
         dry_run: false,
         force: false,
         create_backup: false,
+        git: Some(false),
+        allow_dirty: false,
         quiet: false,
         json: false,
     })
@@ -254,3 +279,318 @@ fn mv_respects_ignore_filters() {
         "guide should be updated when ignores are disabled"
     );
 }
+
+fn batch_options(moves: Vec<FileMove>) -> MvOptions {
+    MvOptions {
+        scan: ScanOptions::default(),
+        moves,
+        dry_run: false,
+        force: false,
+        create_backup: false,
+        git: Some(false),
+        allow_dirty: false,
+        quiet: false,
+        json: false,
+    }
+}
+
+#[test]
+fn mv_moves_directories_including_assets() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        &temp,
+        "README.md",
+        "# Index\n\nSee [setup](docs/old/guide.md#setup) and ![logo](docs/old/img/logo.png).\n",
+    );
+    write_file(
+        &temp,
+        "docs/old/guide.md",
+        "# Guide\n\n## Setup\n\n![arch](img/arch.png) back to [home](../../README.md).\n",
+    );
+    write_file(&temp, "docs/old/img/logo.png", "png");
+    write_file(&temp, "docs/old/img/arch.png", "png");
+
+    let ops = load_ops(&temp);
+    let outcome = ops
+        .mv(batch_options(vec![FileMove {
+            from: PathBuf::from("docs/old"),
+            to: PathBuf::from("guides"),
+        }]))
+        .expect("directory move");
+
+    let summary: Vec<_> = outcome
+        .changes
+        .iter()
+        .map(|change| {
+            (
+                change.original_path.to_string_lossy().into_owned(),
+                change.output_path.to_string_lossy().into_owned(),
+                change.status,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "README.md".into(),
+                "README.md".into(),
+                MvFileStatus::Updated
+            ),
+            (
+                "docs/old/guide.md".into(),
+                "guides/guide.md".into(),
+                MvFileStatus::Relocated
+            ),
+            (
+                "docs/old/img/arch.png".into(),
+                "guides/img/arch.png".into(),
+                MvFileStatus::Relocated
+            ),
+            (
+                "docs/old/img/logo.png".into(),
+                "guides/img/logo.png".into(),
+                MvFileStatus::Relocated
+            ),
+        ]
+    );
+
+    let readme = fs::read_to_string(temp.path().join("README.md")).expect("read readme");
+    assert!(readme.contains("(guides/guide.md#setup)"));
+    assert!(readme.contains("(guides/img/logo.png)"));
+    let guide = fs::read_to_string(temp.path().join("guides/guide.md")).expect("read guide");
+    assert!(guide.contains("(img/arch.png)"));
+    assert!(guide.contains("(../README.md)"));
+    assert!(temp.path().join("guides/img/arch.png").exists());
+    assert!(
+        !temp.path().join("docs/old").exists(),
+        "emptied source directories are removed"
+    );
+}
+
+#[test]
+fn mv_applies_batch_map_files() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        &temp,
+        "README.md",
+        "# Index\n\n[Intro](intro.md), [Usage](usage.md) and ![shot](shot.png).\n",
+    );
+    write_file(&temp, "intro.md", "# Intro\n\nNext: [Usage](usage.md).\n");
+    write_file(&temp, "usage.md", "# Usage\n");
+    write_file(&temp, "shot.png", "png");
+    write_file(
+        &temp,
+        "moves.csv",
+        "# planned layout\nsource,destination\nintro.md,docs/intro.md\n\"usage.md\",docs/usage.md\n",
+    );
+    write_file(
+        &temp,
+        "assets.json",
+        r#"[{"from": "shot.png", "to": "docs/img/shot.png"}]"#,
+    );
+
+    let mut moves = load_move_map(&temp.path().join("moves.csv")).expect("csv map");
+    assert_eq!(
+        moves,
+        vec![
+            FileMove {
+                from: PathBuf::from("intro.md"),
+                to: PathBuf::from("docs/intro.md"),
+            },
+            FileMove {
+                from: PathBuf::from("usage.md"),
+                to: PathBuf::from("docs/usage.md"),
+            },
+        ]
+    );
+    moves.extend(load_move_map(&temp.path().join("assets.json")).expect("json map"));
+
+    let ops = load_ops(&temp);
+    ops.mv(batch_options(moves)).expect("batch move");
+
+    let readme = fs::read_to_string(temp.path().join("README.md")).expect("read readme");
+    assert_eq!(
+        readme,
+        "# Index\n\n[Intro](docs/intro.md), [Usage](docs/usage.md) and ![shot](docs/img/shot.png).\n"
+    );
+    let intro = fs::read_to_string(temp.path().join("docs/intro.md")).expect("read intro");
+    assert!(intro.contains("[Usage](usage.md)"));
+    assert!(temp.path().join("docs/img/shot.png").exists());
+}
+
+#[test]
+fn mv_rejects_conflicting_batches_without_touching_files() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(&temp, "a.md", "# A\n\n[B](b.md)\n");
+    write_file(&temp, "b.md", "# B\n");
+    write_file(&temp, "c.md", "# C\n");
+
+    let ops = load_ops(&temp);
+    let chained = ops.mv(batch_options(vec![
+        FileMove {
+            from: PathBuf::from("a.md"),
+            to: PathBuf::from("b.md"),
+        },
+        FileMove {
+            from: PathBuf::from("b.md"),
+            to: PathBuf::from("c2.md"),
+        },
+    ]));
+    assert!(
+        matches!(chained, Err(OperationError::InvalidInput(message)) if message.contains("chained moves"))
+    );
+
+    let duplicate = ops.mv(batch_options(vec![
+        FileMove {
+            from: PathBuf::from("a.md"),
+            to: PathBuf::from("d.md"),
+        },
+        FileMove {
+            from: PathBuf::from("c.md"),
+            to: PathBuf::from("d.md"),
+        },
+    ]));
+    assert!(
+        matches!(duplicate, Err(OperationError::InvalidInput(message)) if message.contains("multiple moves target 'd.md'"))
+    );
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join("a.md")).unwrap(),
+        "# A\n\n[B](b.md)\n"
+    );
+    assert!(!temp.path().join("d.md").exists());
+}
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .env("GIT_AUTHOR_NAME", "Test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "Test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .output()
+        .expect("run git");
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8(output.stdout).expect("utf-8 output")
+}
+
+fn git_fixture() -> TempDir {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        &temp,
+        "guide.md",
+        "# Guide\n\nSee [Intro](intro.md#overview).\n",
+    );
+    write_file(&temp, "intro.md", "# Intro\n\n## Overview\n\nText.\n");
+    write_file(&temp, "notes.md", "# Notes\n\nUnrelated.\n");
+    git(temp.path(), &["init", "-q"]);
+    git(temp.path(), &["add", "."]);
+    git(temp.path(), &["commit", "-q", "-m", "init"]);
+    temp
+}
+
+fn git_move(git_mode: Option<bool>, allow_dirty: bool) -> MvOptions {
+    MvOptions {
+        create_backup: true,
+        git: git_mode,
+        allow_dirty,
+        ..batch_options(vec![FileMove {
+            from: PathBuf::from("intro.md"),
+            to: PathBuf::from("docs/intro.md"),
+        }])
+    }
+}
+
+#[test]
+fn mv_in_git_repository_uses_git_mv_and_stages_referrers() {
+    let temp = git_fixture();
+
+    load_ops(&temp).mv(git_move(None, false)).expect("git move");
+
+    let status = git(temp.path(), &["status", "--porcelain"]);
+    let mut lines: Vec<&str> = status.lines().collect();
+    lines.sort();
+    assert_eq!(lines, vec!["M  guide.md", "R  intro.md -> docs/intro.md"]);
+    assert!(!temp.path().join("guide.md.bak").exists());
+    assert!(!temp.path().join("intro.md.bak").exists());
+    assert!(fs::read_to_string(temp.path().join("guide.md"))
+        .unwrap()
+        .contains("(docs/intro.md#overview)"));
+}
+
+#[test]
+fn mv_git_mode_refuses_dirty_files_unless_allowed() {
+    let temp = git_fixture();
+    write_file(
+        &temp,
+        "guide.md",
+        "# Guide\n\nSee [Intro](intro.md#overview) today.\n",
+    );
+    let ops = load_ops(&temp);
+
+    // Dry runs list the dirty files alongside the preview instead of failing.
+    let preview = ops
+        .mv(MvOptions {
+            dry_run: true,
+            ..git_move(Some(true), false)
+        })
+        .expect("dry run");
+    assert_eq!(preview.dirty, vec![PathBuf::from("guide.md")]);
+    assert!(preview.changes.iter().any(|change| change.diff.is_some()));
+
+    let refused = ops.mv(git_move(Some(true), false));
+    assert!(matches!(
+        &refused,
+        Err(OperationError::InvalidInput(message))
+            if message.contains("'guide.md'") && message.contains("--allow-dirty")
+    ));
+    assert!(temp.path().join("intro.md").exists());
+
+    ops.mv(git_move(Some(true), true)).expect("allowed move");
+    assert!(temp.path().join("docs/intro.md").exists());
+    assert!(git(temp.path(), &["status", "--porcelain"]).contains("M  guide.md"));
+
+    let plain = TempDir::new().expect("tempdir");
+    write_file(&plain, "intro.md", "# Intro\n");
+    let outside = load_ops(&plain).mv(git_move(Some(true), false));
+    assert!(matches!(
+        &outside,
+        Err(OperationError::InvalidInput(message)) if message.contains("git work tree")
+    ));
+}
+
+#[test]
+fn mv_git_rollback_restores_overwritten_tracked_destination() {
+    let temp = git_fixture();
+    write_file(&temp, "zeta.md", "# Zeta\n");
+    git(temp.path(), &["add", "zeta.md"]);
+    git(temp.path(), &["commit", "-q", "-m", "zeta"]);
+    let ops = load_ops(&temp);
+
+    // The second move fails (its parent is a file) after `notes.md` was overwritten.
+    let result = ops.mv(MvOptions {
+        force: true,
+        git: Some(true),
+        ..batch_options(vec![
+            FileMove {
+                from: PathBuf::from("intro.md"),
+                to: PathBuf::from("notes.md"),
+            },
+            FileMove {
+                from: PathBuf::from("zeta.md"),
+                to: PathBuf::from("guide.md/zeta.md"),
+            },
+        ])
+    });
+    assert!(result.is_err());
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join("notes.md")).unwrap(),
+        "# Notes\n\nUnrelated.\n"
+    );
+    assert!(temp.path().join("intro.md").exists());
+    assert_eq!(git(temp.path(), &["status", "--porcelain"]), "");
+}
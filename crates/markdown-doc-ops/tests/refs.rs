@@ -1,9 +1,12 @@
 use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use markdown_doc_config::{Config, LoadOptions};
-use markdown_doc_ops::{Operations, RefsOptions, ScanOptions};
+use markdown_doc_ops::{
+    OperationError, Operations, OutboundLinkKind, OutboundOptions, OutboundStatus, RefsOptions,
+    ScanOptions,
+};
 use tempfile::TempDir;
 
 fn write_file(dir: &TempDir, path: &str, contents: &str) {
@@ -100,7 +103,7 @@ fn refs_anchor_only_matches_slug() {
     assert!(outcome
         .matches
         .iter()
-        .any(|m| m.source == PathBuf::from("guide.md")));
+        .any(|m| m.source == Path::new("guide.md")));
 }
 
 #[test]
@@ -129,3 +132,124 @@ fn refs_returns_exit_code_one_when_no_matches() {
     assert_eq!(outcome.exit_code, 1);
     assert!(outcome.matches.is_empty());
 }
+
+fn outbound_fixture() -> TempDir {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        &temp,
+        "README.md",
+        "# Index\n\n[Setup](docs/guide.md#setup), [bad](docs/guide.md#nope), [gone](missing.md), [web](https://example.com).\n\n![logo](img/logo.png)\n\nUse [ref][g].\n\n[g]: docs/guide.md\n",
+    );
+    write_file(&temp, "img/logo.png", "png");
+    write_file(
+        &temp,
+        "docs/guide.md",
+        "# Guide\n\n## Setup\n\n[Home](../README.md#index), [top](#setup).\n",
+    );
+    write_file(&temp, "docs/faq.md", "# FAQ\n\n[Gone](old.md)\n");
+    temp
+}
+
+fn outbound_options(from: &str, aggregate: bool) -> OutboundOptions {
+    OutboundOptions {
+        scan: ScanOptions::default(),
+        from: PathBuf::from(from),
+        aggregate,
+    }
+}
+
+#[test]
+fn refs_from_groups_outbound_links_by_resolution() {
+    let temp = outbound_fixture();
+    let report = load_ops(&temp)
+        .outbound(outbound_options("README.md", false))
+        .expect("outbound");
+
+    assert_eq!(report.groups.len(), 1);
+    let group = &report.groups[0];
+    assert_eq!(group.path, PathBuf::from("README.md"));
+    let targets = |links: &[markdown_doc_ops::OutboundLink]| {
+        links
+            .iter()
+            .map(|link| (link.line, link.raw_target.clone(), link.kind, link.status))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        targets(&group.resolved),
+        vec![
+            (
+                3,
+                "docs/guide.md#setup".to_string(),
+                OutboundLinkKind::Link,
+                OutboundStatus::Resolved
+            ),
+            (
+                5,
+                "img/logo.png".to_string(),
+                OutboundLinkKind::Image,
+                OutboundStatus::Resolved
+            ),
+            (
+                9,
+                "docs/guide.md".to_string(),
+                OutboundLinkKind::Definition,
+                OutboundStatus::Resolved
+            ),
+        ]
+    );
+    assert_eq!(
+        targets(&group.unresolved),
+        vec![
+            (
+                3,
+                "docs/guide.md#nope".to_string(),
+                OutboundLinkKind::Link,
+                OutboundStatus::MissingAnchor
+            ),
+            (
+                3,
+                "missing.md".to_string(),
+                OutboundLinkKind::Link,
+                OutboundStatus::MissingFile
+            ),
+        ]
+    );
+    assert_eq!(report.unresolved_count, 2);
+    assert_eq!(group.external.len(), 1);
+    assert_eq!(group.external[0].raw_target, "https://example.com");
+}
+
+#[test]
+fn refs_from_directory_reports_each_document_or_aggregates() {
+    let temp = outbound_fixture();
+    let ops = load_ops(&temp);
+
+    let report = ops
+        .outbound(outbound_options("docs", false))
+        .expect("per-file");
+    let paths: Vec<_> = report
+        .groups
+        .iter()
+        .map(|group| group.path.clone())
+        .collect();
+    assert_eq!(
+        paths,
+        vec![PathBuf::from("docs/faq.md"), PathBuf::from("docs/guide.md")]
+    );
+    assert_eq!(report.groups[1].resolved.len(), 2);
+    assert_eq!(report.unresolved_count, 1);
+
+    let report = ops
+        .outbound(outbound_options("docs", true))
+        .expect("aggregate");
+    assert_eq!(report.groups.len(), 1);
+    assert_eq!(report.groups[0].path, PathBuf::from("docs"));
+    assert_eq!(report.groups[0].resolved.len(), 2);
+    assert_eq!(
+        report.groups[0].unresolved[0].source,
+        PathBuf::from("docs/faq.md")
+    );
+
+    let invalid = ops.outbound(outbound_options("img/logo.png", false));
+    assert!(matches!(invalid, Err(OperationError::InvalidInput(_))));
+}
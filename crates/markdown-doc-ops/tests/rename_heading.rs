@@ -0,0 +1,130 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use markdown_doc_config::{Config, LoadOptions};
+use markdown_doc_ops::{
+    MvFileStatus, OperationError, Operations, RenameHeadingOptions, ScanOptions,
+};
+use tempfile::TempDir;
+
+fn write_file(dir: &Path, name: &str, contents: &str) {
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent directory");
+    }
+    fs::write(path, contents).expect("write file");
+}
+
+fn read(dir: &Path, name: &str) -> String {
+    fs::read_to_string(dir.join(name)).expect("read file")
+}
+
+fn operations(dir: &Path) -> Operations {
+    let config = Config::load(LoadOptions::default().with_working_dir(dir)).expect("load config");
+    Operations::new(config)
+}
+
+fn rename_options(heading: &str, new_heading: &str, dry_run: bool) -> RenameHeadingOptions {
+    RenameHeadingOptions {
+        scan: ScanOptions::default(),
+        path: PathBuf::from("guide.md"),
+        heading: heading.to_string(),
+        new_heading: new_heading.to_string(),
+        dry_run,
+        create_backup: false,
+    }
+}
+
+const GUIDE: &str = "# Guide\n\nSee [setup](#getting-started) and [ref][start].\n\n## Getting Started ##\n\nSteps.\n\n## Usage\n\nText.\n\n[start]: #getting-started \"Start here\"\n";
+
+#[test]
+fn rename_heading_rewrites_inbound_and_same_file_links() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "guide.md", GUIDE);
+    write_file(
+        temp.path(),
+        "docs/index.md",
+        "# Index\n\n[Start](../guide.md#getting-started), [usage](../guide.md#usage), [home](../guide.md).\n",
+    );
+    write_file(temp.path(), "other.md", "# Other\n\nNo links.\n");
+
+    let outcome = operations(temp.path())
+        .rename_heading(rename_options("Getting started", "Quick Start", false))
+        .expect("rename");
+    let changed: Vec<_> = outcome
+        .changes
+        .iter()
+        .map(|change| (change.original_path.clone(), change.status))
+        .collect();
+    assert_eq!(
+        changed,
+        vec![
+            (PathBuf::from("docs/index.md"), MvFileStatus::Updated),
+            (PathBuf::from("guide.md"), MvFileStatus::Updated),
+        ]
+    );
+
+    assert_eq!(
+        read(temp.path(), "guide.md"),
+        "# Guide\n\nSee [setup](#quick-start) and [ref][start].\n\n## Quick Start ##\n\nSteps.\n\n## Usage\n\nText.\n\n[start]: #quick-start \"Start here\"\n"
+    );
+    assert_eq!(
+        read(temp.path(), "docs/index.md"),
+        "# Index\n\n[Start](../guide.md#quick-start), [usage](../guide.md#usage), [home](../guide.md).\n"
+    );
+}
+
+#[test]
+fn rename_heading_follows_duplicate_suffix_shifts_and_explicit_ids() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        "guide.md",
+        "# Guide\n\n## Setup\n\nOne.\n\n## Setup\n\nTwo [first](#setup), [second](#setup-1), [pinned](#pinned).\n\n## Pinned {#pinned}\n",
+    );
+
+    let ops = operations(temp.path());
+    let preview = ops
+        .rename_heading(rename_options("#setup", "Install", true))
+        .expect("dry run");
+    assert!(preview.dry_run);
+    let diff = preview.changes[0].diff.as_deref().expect("diff");
+    assert!(diff.contains("+## Install"));
+    assert!(read(temp.path(), "guide.md").contains("## Setup\n\nOne."));
+
+    ops.rename_heading(rename_options("#setup", "Install", false))
+        .expect("rename");
+    // The second "Setup" loses its suffix, so links to it follow as well.
+    assert_eq!(
+        read(temp.path(), "guide.md"),
+        "# Guide\n\n## Install\n\nOne.\n\n## Setup\n\nTwo [first](#install), [second](#setup), [pinned](#pinned).\n\n## Pinned {#pinned}\n"
+    );
+
+    // An explicit id keeps the anchor, so only the heading text changes.
+    let outcome = ops
+        .rename_heading(rename_options("Pinned", "Fixed", false))
+        .expect("rename pinned");
+    assert_eq!(outcome.changes.len(), 1);
+    assert!(read(temp.path(), "guide.md").ends_with("[pinned](#pinned).\n\n## Fixed {#pinned}\n"));
+}
+
+#[test]
+fn rename_heading_rejects_missing_and_ambiguous_headings() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        "guide.md",
+        "# Guide\n\n## Notes\n\nA.\n\n## Notes\n\nB.\n",
+    );
+    let ops = operations(temp.path());
+
+    let missing = ops.rename_heading(rename_options("Missing", "New", false));
+    assert!(
+        matches!(&missing, Err(OperationError::InvalidInput(message)) if message.contains("no heading 'Missing'"))
+    );
+
+    let ambiguous = ops.rename_heading(rename_options("Notes", "New", false));
+    assert!(
+        matches!(&ambiguous, Err(OperationError::InvalidInput(message)) if message.contains("#notes, #notes-1"))
+    );
+}
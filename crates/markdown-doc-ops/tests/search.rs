@@ -0,0 +1,136 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use markdown_doc_config::{Config, LoadOptions};
+use markdown_doc_ops::{Operations, ScanOptions, SearchOptions, SearchOutcome};
+use tempfile::TempDir;
+
+fn write_file(dir: &Path, name: &str, contents: &str) {
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent directory");
+    }
+    fs::write(path, contents).expect("write file");
+}
+
+fn search(dir: &Path, query: &str, paths: Vec<PathBuf>) -> SearchOutcome {
+    let config = Config::load(LoadOptions::default().with_working_dir(dir)).expect("load config");
+    Operations::new(config)
+        .search(SearchOptions {
+            scan: ScanOptions {
+                paths,
+                ..ScanOptions::default()
+            },
+            query: query.to_string(),
+            limit: 10,
+            index_path: None,
+            refresh: true,
+        })
+        .expect("search")
+}
+
+const GUIDE: &str = "---\ntitle: Deployment handbook\n---\n# Guide\n\nIntro text.\n\n## Deployment\n\nRead this first.\nUse the staging cluster.\n\n## Rollback\n\nAfter a failed deployment, restore the snapshot.\n";
+
+#[test]
+fn search_ranks_sections_and_boosts_headings() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "guide.md", GUIDE);
+    write_file(
+        temp.path(),
+        "notes.md",
+        "# Notes\n\nNothing relevant here.\n",
+    );
+
+    let outcome = search(temp.path(), "Deployment", Vec::new());
+    assert_eq!(outcome.exit_code, 0);
+    let hits: Vec<_> = outcome
+        .results
+        .iter()
+        .map(|result| {
+            (
+                result.heading.as_deref(),
+                result.anchor.as_deref(),
+                result.line,
+            )
+        })
+        .collect();
+    // Front matter is not indexed, so only the two body sections match.
+    assert_eq!(
+        hits,
+        vec![
+            (Some("Deployment"), Some("deployment"), 8),
+            (Some("Rollback"), Some("rollback"), 13),
+        ]
+    );
+    assert!(outcome.results[0].score > outcome.results[1].score);
+    assert_eq!(outcome.results[0].terms, vec!["deployment".to_string()]);
+    assert_eq!(outcome.index.files, 2);
+    assert_eq!(outcome.index.sections, 4);
+}
+
+#[test]
+fn search_snippet_highlights_matching_lines() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "guide.md", GUIDE);
+
+    let outcome = search(temp.path(), "snapshot", Vec::new());
+    let result = &outcome.results[0];
+    assert_eq!(result.snippet.len(), 1);
+    let line = &result.snippet[0];
+    assert_eq!(line.line, 15);
+    assert_eq!(
+        line.text,
+        "After a failed deployment, restore the snapshot."
+    );
+    assert_eq!(line.highlights, vec![(39, 47)]);
+}
+
+#[test]
+fn search_index_refreshes_only_changed_documents() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "guide.md", GUIDE);
+    write_file(temp.path(), "notes.md", "# Notes\n\nPlain text.\n");
+
+    let first = search(temp.path(), "notes", Vec::new());
+    assert_eq!(first.index.refreshed, 2);
+    assert!(temp.path().join(".markdown-doc/index.json").exists());
+    assert!(first.index.bytes > 0);
+
+    let second = search(temp.path(), "notes", Vec::new());
+    assert_eq!(second.index.refreshed, 0);
+
+    write_file(
+        temp.path(),
+        "notes.md",
+        "# Notes\n\nNow mentions the snapshot too.\n",
+    );
+    let third = search(temp.path(), "snapshot", Vec::new());
+    assert_eq!(third.index.refreshed, 1);
+    let files: Vec<_> = third.results.iter().map(|r| r.path.clone()).collect();
+    assert_eq!(
+        files,
+        vec![PathBuf::from("notes.md"), PathBuf::from("guide.md")]
+    );
+
+    fs::remove_file(temp.path().join("notes.md")).unwrap();
+    let fourth = search(temp.path(), "snapshot", Vec::new());
+    assert_eq!(fourth.index.files, 1);
+    assert_eq!(fourth.results.len(), 1);
+}
+
+#[test]
+fn search_path_filter_limits_results_and_reports_no_matches() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "docs/a.md", "# Alpha\n\nShared term.\n");
+    write_file(temp.path(), "other/b.md", "# Beta\n\nShared term.\n");
+
+    let outcome = search(temp.path(), "shared", vec![PathBuf::from("docs")]);
+    let files: Vec<_> = outcome.results.iter().map(|r| r.path.clone()).collect();
+    assert_eq!(files, vec![PathBuf::from("docs/a.md")]);
+    // The index still covers the whole project.
+    assert_eq!(outcome.index.files, 2);
+
+    let missing = search(temp.path(), "absent", Vec::new());
+    assert!(missing.results.is_empty());
+    assert_eq!(missing.exit_code, 1);
+}
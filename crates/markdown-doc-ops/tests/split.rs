@@ -0,0 +1,169 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use markdown_doc_config::{Config, LoadOptions};
+use markdown_doc_ops::{
+    MergeOptions, MvFileStatus, OperationError, Operations, ScanOptions, SplitOptions,
+};
+use tempfile::TempDir;
+
+fn write_file(dir: &Path, name: &str, contents: &str) {
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent directory");
+    }
+    fs::write(path, contents).expect("write file");
+}
+
+fn read(dir: &Path, name: &str) -> String {
+    fs::read_to_string(dir.join(name)).expect("read file")
+}
+
+fn operations(dir: &Path) -> Operations {
+    let config = Config::load(LoadOptions::default().with_working_dir(dir)).expect("load config");
+    Operations::new(config)
+}
+
+fn split_options(section: &str, destination: &str, stub: bool) -> SplitOptions {
+    SplitOptions {
+        scan: ScanOptions::default(),
+        source: PathBuf::from("README.md"),
+        section: section.to_string(),
+        destination: PathBuf::from(destination),
+        stub,
+        dry_run: false,
+        create_backup: false,
+    }
+}
+
+const README: &str = "# Project\n\nSee [configuration](#configuration) and [options][opts].\n\n## Install\n\nRun it.\n\n## Configuration\n\nSee [install](#install), [options](#options) and ![diagram](img/config.png).\n\n### Options\n\nDetails.\n\n## License\n\nMIT\n\n[opts]: #options\n";
+
+#[test]
+fn split_moves_section_and_redirects_inbound_anchors() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "README.md", README);
+    write_file(
+        temp.path(),
+        "docs/guide.md",
+        "# Guide\n\n[Config](../README.md#configuration), [opts](../README.md#options), [home](../README.md).\n",
+    );
+
+    let outcome = operations(temp.path())
+        .split(split_options("^config", "docs/configuration.md", false))
+        .expect("split");
+    let statuses: Vec<_> = outcome
+        .changes
+        .iter()
+        .map(|change| (change.original_path.clone(), change.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            (PathBuf::from("README.md"), MvFileStatus::Updated),
+            (
+                PathBuf::from("docs/configuration.md"),
+                MvFileStatus::Created
+            ),
+            (PathBuf::from("docs/guide.md"), MvFileStatus::Updated),
+        ]
+    );
+
+    assert_eq!(
+        read(temp.path(), "README.md"),
+        "# Project\n\nSee [configuration](docs/configuration.md#configuration) and [options][opts].\n\n## Install\n\nRun it.\n\n## License\n\nMIT\n\n[opts]: docs/configuration.md#options\n"
+    );
+    // Headings move up a level; relative links follow the text to its new directory.
+    assert_eq!(
+        read(temp.path(), "docs/configuration.md"),
+        "# Configuration\n\nSee [install](../README.md#install), [options](#options) and ![diagram](../img/config.png).\n\n## Options\n\nDetails.\n"
+    );
+    assert_eq!(
+        read(temp.path(), "docs/guide.md"),
+        "# Guide\n\n[Config](configuration.md#configuration), [opts](configuration.md#options), [home](../README.md).\n"
+    );
+}
+
+#[test]
+fn split_appends_to_existing_document_and_leaves_stub() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "README.md", README);
+    write_file(
+        temp.path(),
+        "reference.md",
+        "# Reference\n\n## Options\n\nOlder notes.\n",
+    );
+
+    operations(temp.path())
+        .split(split_options("#configuration", "reference.md", true))
+        .expect("split");
+
+    let readme = read(temp.path(), "README.md");
+    assert!(readme.contains(
+        "## Configuration\n\nMoved to [Configuration](reference.md#configuration).\n\n## License"
+    ));
+    assert!(readme.contains("See [configuration](reference.md#configuration)"));
+    // The destination already has an `Options` heading, so the moved one is suffixed.
+    assert!(readme.contains("[opts]: reference.md#options-1\n"));
+    assert_eq!(
+        read(temp.path(), "reference.md"),
+        "# Reference\n\n## Options\n\nOlder notes.\n\n## Configuration\n\nSee [install](README.md#install), [options](#options-1) and ![diagram](img/config.png).\n\n### Options\n\nDetails.\n"
+    );
+}
+
+#[test]
+fn merge_folds_document_into_destination() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        "README.md",
+        "# Project\n\nRead the [FAQ](faq.md) and [billing](faq.md#billing).\n",
+    );
+    write_file(
+        temp.path(),
+        "faq.md",
+        "---\ntitle: FAQ\n---\nFAQ\n===\n\n## Billing\n\nBack to [project](README.md#project).\n",
+    );
+
+    let outcome = operations(temp.path())
+        .merge(MergeOptions {
+            scan: ScanOptions::default(),
+            source: PathBuf::from("faq.md"),
+            destination: PathBuf::from("README.md"),
+            dry_run: false,
+            create_backup: false,
+        })
+        .expect("merge");
+    assert_eq!(outcome.changes[1].status, MvFileStatus::Removed);
+
+    assert!(!temp.path().join("faq.md").exists());
+    assert_eq!(
+        read(temp.path(), "README.md"),
+        "# Project\n\nRead the [FAQ](#faq) and [billing](#billing).\n\n## FAQ\n\n### Billing\n\nBack to [project](#project).\n"
+    );
+}
+
+#[test]
+fn split_rejects_ambiguous_patterns_and_dry_run_writes_nothing() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "README.md", README);
+
+    let ops = operations(temp.path());
+    let ambiguous = ops.split(split_options("i", "other.md", false));
+    assert!(
+        matches!(&ambiguous, Err(OperationError::InvalidInput(message)) if message.contains("use #anchor")),
+        "unexpected result: {:?}",
+        ambiguous.as_ref().err()
+    );
+
+    let mut options = split_options("license", "license.md", false);
+    options.dry_run = true;
+    let outcome = ops.split(options).expect("dry run");
+    assert!(outcome.dry_run);
+    assert!(outcome.changes[1]
+        .diff
+        .as_deref()
+        .unwrap()
+        .contains("+# License"));
+    assert!(!temp.path().join("license.md").exists());
+    assert_eq!(read(temp.path(), "README.md"), README);
+}
@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use markdown_doc_config::{Config, LintRule, LoadOptions};
+use markdown_doc_ops::{Operations, ScanOptions, StatsOptions, StatsReport};
+use tempfile::TempDir;
+
+fn write_file(dir: &Path, name: &str, contents: &str) {
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent directory");
+    }
+    fs::write(path, contents).expect("write file");
+}
+
+fn stats(dir: &Path, paths: Vec<PathBuf>, configure: impl FnOnce(&mut Config)) -> StatsReport {
+    let mut config =
+        Config::load(LoadOptions::default().with_working_dir(dir)).expect("load config");
+    config.lint.rules = vec![LintRule::BrokenLinks, LintRule::BrokenAnchors];
+    configure(&mut config);
+    Operations::new(config)
+        .stats(StatsOptions {
+            scan: ScanOptions {
+                paths,
+                ..ScanOptions::default()
+            },
+            top: 2,
+        })
+        .expect("stats")
+}
+
+fn write_project(dir: &Path) {
+    write_file(
+        dir,
+        "README.md",
+        "# Project\n\nStart with the [guide](docs/guide.md) and the [setup](docs/guide.md#setup).\n",
+    );
+    write_file(
+        dir,
+        "docs/guide.md",
+        concat!(
+            "---\ntitle: Guide\n---\n",
+            "# Guide\n\nShort intro.\n\n",
+            "## Setup\n\nInstall the tool and run it once to create the config.\n\n",
+            "### Options\n\nSee [upstream](https://example.com), [missing](missing.md) ",
+            "and [gone](../README.md#nope).\n\n![diagram](diagram.png)\n",
+        ),
+    );
+    write_file(dir, "docs/notes.md", "# Notes\n\nNobody links here.\n");
+}
+
+#[test]
+fn stats_counts_words_headings_and_links_per_file() {
+    let temp = TempDir::new().expect("tempdir");
+    write_project(temp.path());
+
+    let report = stats(temp.path(), Vec::new(), |_| {});
+    let paths: Vec<_> = report.files.iter().map(|file| file.path.clone()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            PathBuf::from("README.md"),
+            PathBuf::from("docs/guide.md"),
+            PathBuf::from("docs/notes.md"),
+        ]
+    );
+
+    let guide = &report.files[1];
+    // Front matter and link destinations are not counted.
+    assert_eq!(guide.words, 22);
+    assert_eq!(guide.headings, [1, 1, 1, 0, 0, 0]);
+    assert_eq!(guide.links.internal, 2);
+    assert_eq!(guide.links.external, 1);
+    assert_eq!(guide.links.broken, 2);
+    assert_eq!(guide.inbound_links, 2);
+    assert!(!guide.orphan);
+    assert_eq!(guide.lint_findings, 2);
+
+    let readme = &report.files[0];
+    assert_eq!(readme.links.internal, 2);
+    assert_eq!(readme.links.broken, 0);
+    assert_eq!(readme.inbound_links, 1);
+
+    assert!(report.files[2].orphan);
+    assert_eq!(report.files[2].inbound_links, 0);
+}
+
+#[test]
+fn stats_summary_aggregates_and_ranks_sections() {
+    let temp = TempDir::new().expect("tempdir");
+    write_project(temp.path());
+
+    let report = stats(temp.path(), Vec::new(), |_| {});
+    let summary = &report.summary;
+    assert_eq!(summary.files, 3);
+    assert_eq!(summary.headings, [3, 1, 1, 0, 0, 0]);
+    assert_eq!(summary.links.internal, 4);
+    assert_eq!(summary.links.external, 1);
+    assert_eq!(summary.links.broken, 2);
+    assert_eq!(summary.orphans, 1);
+    assert_eq!(summary.lint_findings, 2);
+    let expected_density = 2.0 * 1000.0 / summary.words as f64;
+    assert!((summary.lint_density - expected_density).abs() < 1e-9);
+    assert_eq!(summary.schema_conforming, 3);
+    assert!((summary.schema_conformance - 1.0).abs() < 1e-9);
+
+    let largest: Vec<_> = report
+        .largest_sections
+        .iter()
+        .map(|section| (section.path.clone(), section.anchor.as_str(), section.line))
+        .collect();
+    assert_eq!(
+        largest,
+        vec![
+            (PathBuf::from("docs/guide.md"), "setup", 8),
+            (PathBuf::from("README.md"), "project", 1),
+        ]
+    );
+    assert_eq!(report.largest_sections[0].words, 11);
+    assert_eq!(report.oldest.len(), 2);
+    assert!(report
+        .oldest
+        .iter()
+        .all(|file| file.last_modified.is_some()));
+    assert!(!report.generated_at.is_empty());
+}
+
+#[test]
+fn stats_reports_schema_conformance_for_selected_paths() {
+    let temp = TempDir::new().expect("tempdir");
+    write_project(temp.path());
+
+    let report = stats(temp.path(), vec![PathBuf::from("docs")], |config| {
+        if let Some(default_schema) = config.schemas.schemas.get_mut("default") {
+            default_schema.required_sections = vec!["Setup".to_string()];
+            default_schema.allow_additional = true;
+        }
+    });
+    let conforming: Vec<_> = report
+        .files
+        .iter()
+        .map(|file| (file.path.clone(), file.schema_conforms))
+        .collect();
+    assert_eq!(
+        conforming,
+        vec![
+            (PathBuf::from("docs/guide.md"), true),
+            (PathBuf::from("docs/notes.md"), false),
+        ]
+    );
+    assert_eq!(report.summary.schema_conforming, 1);
+    assert!((report.summary.schema_conformance - 0.5).abs() < 1e-9);
+    // Inbound links still count sources outside the selection.
+    assert_eq!(report.files[0].inbound_links, 2);
+}
@@ -1,9 +1,9 @@
 use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::Path;
 
-use markdown_doc_config::{Config, LoadOptions};
-use markdown_doc_ops::{Operations, ScanOptions, TocMode, TocOptions};
+use markdown_doc_config::{Config, LoadOptions, TocInsertPosition};
+use markdown_doc_ops::{Operations, ScanOptions, TocMode, TocOptions, TocStatus};
 use tempfile::TempDir;
 
 fn write_file(dir: &TempDir, name: &str, contents: &str) {
@@ -51,10 +51,12 @@ fn toc_respects_ignore_file() {
         scan: ScanOptions {
             paths: Vec::new(),
             staged: false,
+            changed_since: None,
             respect_ignore: true,
         },
         mode: TocMode::Check,
         quiet: false,
+        insert_at: None,
     };
 
     let outcome = ops.toc(options).expect("toc execution with ignore");
@@ -62,14 +64,14 @@ fn toc_respects_ignore_file() {
         outcome
             .changes
             .iter()
-            .any(|change| change.path == PathBuf::from("kept.md")),
+            .any(|change| change.path == Path::new("kept.md")),
         "kept.md should be processed",
     );
     assert!(
         outcome
             .changes
             .iter()
-            .all(|change| change.path != PathBuf::from("ignored.md")),
+            .all(|change| change.path != Path::new("ignored.md")),
         "ignored.md should be filtered when respect_ignore is true",
     );
 
@@ -77,10 +79,12 @@ fn toc_respects_ignore_file() {
         scan: ScanOptions {
             paths: Vec::new(),
             staged: false,
+            changed_since: None,
             respect_ignore: false,
         },
         mode: TocMode::Check,
         quiet: false,
+        insert_at: None,
     };
 
     let outcome_no_ignore = ops.toc(options).expect("toc execution without ignore");
@@ -88,7 +92,200 @@ fn toc_respects_ignore_file() {
         outcome_no_ignore
             .changes
             .iter()
-            .any(|change| change.path == PathBuf::from("ignored.md")),
+            .any(|change| change.path == Path::new("ignored.md")),
         "ignored.md should be processed when respect_ignore is false",
     );
 }
+
+fn toc_options(mode: TocMode) -> TocOptions {
+    TocOptions {
+        scan: ScanOptions {
+            paths: Vec::new(),
+            staged: false,
+            changed_since: None,
+            respect_ignore: true,
+        },
+        mode,
+        quiet: false,
+        insert_at: None,
+    }
+}
+
+fn load_ops(temp: &TempDir) -> Operations {
+    let working_dir = fs::canonicalize(temp.path()).expect("canonicalize working dir");
+    let config =
+        Config::load(LoadOptions::default().with_working_dir(&working_dir)).expect("load config");
+    Operations::new(config)
+}
+
+#[test]
+fn toc_update_applies_inline_options_and_scoped_blocks() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        &temp,
+        ".markdown-doc.toml",
+        "[lint.toc]\nbullet = \"*\"\nexclude = [\"^Changelog$\"]\n",
+    );
+    write_file(
+        &temp,
+        "guide.md",
+        concat!(
+            "# Guide\n\n",
+            "<!-- toc depth=2..3 -->\n<!-- tocstop -->\n\n",
+            "## Install\n\n### Linux\n\n#### Packages\n\n",
+            "## Usage\n\n<!-- toc numbered -->\n<!-- tocstop -->\n\n",
+            "### Basics\n\n### Advanced\n\n#### Tuning\n\n",
+            "## Changelog\n\n```markdown\n<!-- toc -->\n<!-- tocstop -->\n```\n",
+        ),
+    );
+
+    let ops = load_ops(&temp);
+    let outcome = ops.toc(toc_options(TocMode::Update)).expect("toc update");
+    assert_eq!(outcome.exit_code, 0, "{}", outcome.rendered);
+
+    let updated = fs::read_to_string(temp.path().join("guide.md")).expect("read guide");
+    assert!(updated.contains(
+        "<!-- toc depth=2..3 -->\n* [Install](#install)\n  * [Linux](#linux)\n* [Usage](#usage)\n  * [Basics](#basics)\n  * [Advanced](#advanced)\n<!-- tocstop -->\n"
+    ), "{updated}");
+    assert!(updated.contains(
+        "<!-- toc numbered -->\n1. [Basics](#basics)\n2. [Advanced](#advanced)\n   1. [Tuning](#tuning)\n<!-- tocstop -->\n"
+    ), "{updated}");
+    assert!(updated.contains("```markdown\n<!-- toc -->\n<!-- tocstop -->\n```"));
+
+    let outcome = ops.toc(toc_options(TocMode::Check)).expect("toc check");
+    assert_eq!(outcome.exit_code, 0, "{}", outcome.rendered);
+    assert_eq!(outcome.changes[0].status, TocStatus::UpToDate);
+}
+
+#[test]
+fn toc_numbered_children_align_with_wide_parent_markers() {
+    let temp = TempDir::new().expect("tempdir");
+    let mut contents = String::from("# Guide\n\n<!-- toc numbered -->\n<!-- tocstop -->\n\n");
+    for n in 1..=10 {
+        contents.push_str(&format!("## Step {n}\n\n"));
+    }
+    contents.push_str("### Detail\n\n#### Note\n");
+    write_file(&temp, "guide.md", &contents);
+
+    let ops = load_ops(&temp);
+    let outcome = ops.toc(toc_options(TocMode::Update)).expect("toc update");
+    assert_eq!(outcome.exit_code, 0, "{}", outcome.rendered);
+
+    let updated = fs::read_to_string(temp.path().join("guide.md")).expect("read guide");
+    assert!(
+        updated.contains(
+            "9. [Step 9](#step-9)\n10. [Step 10](#step-10)\n    1. [Detail](#detail)\n       1. [Note](#note)\n<!-- tocstop -->\n"
+        ),
+        "{updated}"
+    );
+}
+
+#[test]
+fn toc_skips_files_without_markers_when_configured() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(&temp, "plain.md", "# Plain\n\n## Section\n");
+
+    let outcome = load_ops(&temp)
+        .toc(toc_options(TocMode::Check))
+        .expect("toc check");
+    assert_eq!(outcome.exit_code, 1);
+    assert_eq!(outcome.changes[0].status, TocStatus::MissingMarkers);
+
+    write_file(
+        &temp,
+        ".markdown-doc.toml",
+        "[lint.toc]\nskip_missing = true\n",
+    );
+    let outcome = load_ops(&temp)
+        .toc(toc_options(TocMode::Check))
+        .expect("toc check");
+    assert_eq!(outcome.exit_code, 0);
+    assert_eq!(outcome.changes[0].status, TocStatus::Skipped);
+}
+
+#[test]
+fn toc_reports_invalid_inline_options() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        &temp,
+        "doc.md",
+        "<!-- toc depth=4..2 style=fancy -->\n<!-- tocstop -->\n\n## Section\n",
+    );
+
+    let outcome = load_ops(&temp)
+        .toc(toc_options(TocMode::Update))
+        .expect("toc update");
+    assert_eq!(outcome.exit_code, 1);
+    assert_eq!(outcome.changes[0].status, TocStatus::InvalidOptions);
+    assert!(outcome
+        .rendered
+        .contains("doc.md:1: unknown TOC option 'style'"));
+    assert!(outcome
+        .rendered
+        .contains("min_depth (4) cannot exceed max_depth (2)"));
+}
+
+#[test]
+fn toc_insert_places_blocks_at_configured_positions() {
+    let document = "# Guide\n\nIntro paragraph.\n\nMore detail.\n\n## Install\n\n## Usage\n";
+    let cases = [
+        (
+            None,
+            "# Guide\n\nIntro paragraph.\n\n<!-- toc -->\n- [Install](#install)\n- [Usage](#usage)\n<!-- tocstop -->\n\nMore detail.\n\n## Install\n\n## Usage\n",
+        ),
+        (
+            Some(TocInsertPosition::BeforeFirstSection),
+            "# Guide\n\nIntro paragraph.\n\nMore detail.\n\n<!-- toc -->\n- [Install](#install)\n- [Usage](#usage)\n<!-- tocstop -->\n\n## Install\n\n## Usage\n",
+        ),
+        (
+            Some(TocInsertPosition::ContentsHeading),
+            "# Guide\n\nIntro paragraph.\n\nMore detail.\n\n## Contents\n\n<!-- toc exclude=\"^Contents$\" -->\n- [Install](#install)\n- [Usage](#usage)\n<!-- tocstop -->\n\n## Install\n\n## Usage\n",
+        ),
+    ];
+
+    for (position, expected) in cases {
+        let temp = TempDir::new().expect("tempdir");
+        write_file(&temp, "guide.md", document);
+        let ops = load_ops(&temp);
+        let outcome = ops
+            .toc(TocOptions {
+                insert_at: position,
+                ..toc_options(TocMode::Insert)
+            })
+            .expect("toc insert");
+        assert_eq!(outcome.exit_code, 0, "{}", outcome.rendered);
+        assert_eq!(outcome.changes[0].status, TocStatus::Inserted);
+
+        let updated = fs::read_to_string(temp.path().join("guide.md")).expect("read guide");
+        assert_eq!(updated, expected, "{position:?}");
+
+        // The inserted block is picked up by later runs.
+        let outcome = ops.toc(toc_options(TocMode::Insert)).expect("toc rerun");
+        assert_eq!(outcome.changes[0].status, TocStatus::UpToDate);
+    }
+}
+
+#[test]
+fn toc_insert_preserves_crlf_and_existing_contents_heading() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        &temp,
+        "guide.md",
+        "---\r\ntitle: Guide\r\n---\r\n## Table of Contents\r\n\r\n## Setup\r\n",
+    );
+    write_file(
+        &temp,
+        ".markdown-doc.toml",
+        "[lint.toc]\ninsert_at = \"contents-heading\"\n",
+    );
+
+    let outcome = load_ops(&temp)
+        .toc(toc_options(TocMode::Insert))
+        .expect("toc insert");
+    assert_eq!(outcome.exit_code, 0, "{}", outcome.rendered);
+    let updated = fs::read_to_string(temp.path().join("guide.md")).expect("read guide");
+    assert_eq!(
+        updated,
+        "---\r\ntitle: Guide\r\n---\r\n## Table of Contents\r\n\r\n<!-- toc exclude=\"^Table of Contents$\" -->\r\n- [Setup](#setup)\r\n<!-- tocstop -->\r\n\r\n## Setup\r\n"
+    );
+}
@@ -0,0 +1,256 @@
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use markdown_doc_config::{CatalogTimestamp, Config, LintRule, LoadOptions};
+use markdown_doc_ops::{CatalogStatus, Operations, ScanOptions, WatchOptions};
+use tempfile::TempDir;
+
+fn write_file(dir: &Path, name: &str, contents: &str) {
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent directory");
+    }
+    fs::write(path, contents).expect("write file");
+}
+
+fn operations(dir: &Path) -> Operations {
+    let mut config =
+        Config::load(LoadOptions::default().with_working_dir(dir)).expect("load config");
+    config.lint.rules = vec![LintRule::BrokenLinks, LintRule::BrokenAnchors];
+    config.catalog.timestamp = CatalogTimestamp::Omit;
+    Operations::new(config)
+}
+
+fn watch_options(lint: bool, toc: bool, catalog: bool) -> WatchOptions {
+    WatchOptions {
+        scan: ScanOptions::default(),
+        lint,
+        toc,
+        catalog,
+        debounce: Duration::from_millis(100),
+    }
+}
+
+const GUIDE: &str = "# Guide\n\n## Setup\n\nInstall it.\n";
+const INDEX: &str = "# Index\n\nRead the [setup](guide.md#setup) notes.\n";
+
+#[test]
+fn watch_relints_changed_documents_and_their_linkers() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "guide.md", GUIDE);
+    write_file(temp.path(), "index.md", INDEX);
+    write_file(temp.path(), "other.md", "# Other\n\nUnrelated.\n");
+
+    let ops = operations(temp.path());
+    let mut session = ops.watch_session(watch_options(true, false, false));
+    let initial = session.scan().expect("initial scan");
+    assert_eq!(initial.changed.len(), 3);
+    assert_eq!(initial.error_count, 0);
+    assert!(initial.rendered.starts_with("👀 watching 3 documents"));
+
+    write_file(
+        temp.path(),
+        "guide.md",
+        "# Guide\n\n## Installation\n\nInstall it.\n",
+    );
+    let report = session
+        .process(&[temp.path().join("guide.md")])
+        .expect("process change");
+    assert_eq!(report.changed, vec![PathBuf::from("guide.md")]);
+    assert_eq!(report.dependents, vec![PathBuf::from("index.md")]);
+    assert_eq!(report.added.len(), 1);
+    assert_eq!(report.added[0].path, PathBuf::from("index.md"));
+    assert_eq!(report.added[0].rule, LintRule::BrokenAnchors);
+    assert_eq!(report.error_count, 1);
+    assert!(report.rendered.contains("🔄 guide.md changed"));
+    assert!(report.rendered.contains("+ ❌ index.md:3 [broken-anchors]"));
+    assert!(report
+        .rendered
+        .ends_with("lint: 1 errors, 0 warnings (+1 new, -0 resolved)"));
+
+    // Moving the broken link to another line is not a new finding.
+    write_file(
+        temp.path(),
+        "index.md",
+        "# Index\n\nIntro.\n\nRead the [setup](guide.md#setup) notes.\n",
+    );
+    let report = session
+        .process(&[PathBuf::from("index.md")])
+        .expect("process edit");
+    assert!(report.added.is_empty());
+    assert!(report.resolved.is_empty());
+
+    write_file(temp.path(), "guide.md", GUIDE);
+    let report = session
+        .process(&[PathBuf::from("guide.md")])
+        .expect("process revert");
+    assert_eq!(report.resolved.len(), 1);
+    assert_eq!(report.error_count, 0);
+}
+
+#[test]
+fn watch_relints_documents_whose_orphan_status_changes() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        "README.md",
+        "# Home\n\nSee the [guide](guide.md).\n",
+    );
+    write_file(temp.path(), "guide.md", GUIDE);
+
+    let mut config =
+        Config::load(LoadOptions::default().with_working_dir(temp.path())).expect("load config");
+    config.lint.rules = vec![LintRule::Orphans];
+    let ops = Operations::new(config);
+    let mut session = ops.watch_session(watch_options(true, false, false));
+    assert_eq!(session.scan().expect("initial scan").error_count, 0);
+
+    // `guide.md` is untouched and links nowhere, but loses its only inbound link.
+    write_file(temp.path(), "README.md", "# Home\n\nNothing here.\n");
+    let report = session
+        .process(&[PathBuf::from("README.md")])
+        .expect("process change");
+    assert!(report.dependents.is_empty());
+    assert_eq!(report.reclassified, vec![PathBuf::from("guide.md")]);
+    assert_eq!(report.added.len(), 1);
+    assert_eq!(report.added[0].path, PathBuf::from("guide.md"));
+    assert_eq!(report.added[0].rule, LintRule::Orphans);
+    assert!(report
+        .rendered
+        .contains("re-linting 1 document(s) whose orphan/duplicate status changed: guide.md"));
+
+    write_file(
+        temp.path(),
+        "README.md",
+        "# Home\n\nSee the [guide](guide.md).\n",
+    );
+    let report = session
+        .process(&[PathBuf::from("README.md")])
+        .expect("process revert");
+    assert_eq!(report.reclassified, vec![PathBuf::from("guide.md")]);
+    assert_eq!(report.resolved.len(), 1);
+    assert_eq!(report.error_count, 0);
+}
+
+#[test]
+fn watch_skips_unchanged_ignored_and_non_markdown_files() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), ".markdown-doc-ignore", "drafts/\n");
+    write_file(temp.path(), "guide.md", GUIDE);
+    write_file(
+        temp.path(),
+        "drafts/wip.md",
+        "# WIP\n\n[broken](missing.md)\n",
+    );
+    write_file(temp.path(), "notes.txt", "not markdown\n");
+
+    let ops = operations(temp.path());
+    let mut session = ops.watch_session(watch_options(true, false, false));
+    let initial = session.scan().expect("initial scan");
+    assert_eq!(initial.changed, vec![PathBuf::from("guide.md")]);
+
+    // Rewriting identical contents (e.g. an editor save) does nothing.
+    write_file(temp.path(), "guide.md", GUIDE);
+    write_file(temp.path(), "drafts/wip.md", "# WIP\n\n[still](gone.md)\n");
+    let report = session
+        .process(&[
+            PathBuf::from("guide.md"),
+            PathBuf::from("drafts/wip.md"),
+            PathBuf::from("notes.txt"),
+        ])
+        .expect("process");
+    assert!(report.is_empty());
+    assert!(report.rendered.is_empty());
+}
+
+#[test]
+fn watch_updates_toc_and_catalog_and_tracks_removals() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(
+        temp.path(),
+        "guide.md",
+        "# Guide\n\n<!-- toc -->\n- [Setup](#setup)\n<!-- tocstop -->\n\n## Setup\n\nText.\n",
+    );
+    write_file(temp.path(), "index.md", INDEX);
+
+    let ops = operations(temp.path());
+    let mut session = ops.watch_session(watch_options(true, true, true));
+    let initial = session.scan().expect("initial scan");
+    assert_eq!(initial.catalog, Some(CatalogStatus::Written));
+    let catalog_path = temp.path().join("DOC_CATALOG.md");
+    assert!(fs::read_to_string(&catalog_path)
+        .unwrap()
+        .contains("[Setup](#setup)"));
+
+    write_file(
+        temp.path(),
+        "guide.md",
+        "# Guide\n\n<!-- toc -->\n- [Setup](#setup)\n<!-- tocstop -->\n\n## Setup\n\nText.\n\n## Usage\n\nMore.\n",
+    );
+    let report = session
+        .process(&[PathBuf::from("guide.md")])
+        .expect("process change");
+    assert_eq!(report.toc_updated, vec![PathBuf::from("guide.md")]);
+    assert_eq!(report.catalog, Some(CatalogStatus::Written));
+    assert!(fs::read_to_string(temp.path().join("guide.md"))
+        .unwrap()
+        .contains("- [Usage](#usage)\n<!-- tocstop -->"));
+    assert!(fs::read_to_string(&catalog_path)
+        .unwrap()
+        .contains("[Usage](#usage)"));
+
+    // The session's own writes do not trigger another pass.
+    let report = session
+        .process(&[PathBuf::from("guide.md"), PathBuf::from("DOC_CATALOG.md")])
+        .expect("process own writes");
+    assert!(report.is_empty());
+
+    fs::remove_file(temp.path().join("guide.md")).unwrap();
+    let report = session
+        .process(&[temp.path().join("guide.md")])
+        .expect("process removal");
+    assert_eq!(report.removed, vec![PathBuf::from("guide.md")]);
+    assert_eq!(report.dependents, vec![PathBuf::from("index.md")]);
+    assert_eq!(report.added.len(), 1);
+    assert_eq!(report.added[0].rule, LintRule::BrokenLinks);
+    assert!(report.rendered.contains("🗑️  guide.md removed"));
+    assert!(!fs::read_to_string(&catalog_path)
+        .unwrap()
+        .contains("guide.md"));
+}
+
+#[test]
+fn watch_run_processes_filesystem_events() {
+    let temp = TempDir::new().expect("tempdir");
+    write_file(temp.path(), "guide.md", GUIDE);
+    let root = temp.path().to_path_buf();
+
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let ops = operations(&root);
+        let mut session = ops.watch_session(watch_options(true, false, false));
+        session.scan().expect("initial scan");
+        sender.send(None).unwrap();
+        session
+            .run(|report| {
+                sender.send(Some(report.changed.clone())).unwrap();
+                ControlFlow::Break(())
+            })
+            .expect("watch");
+    });
+
+    receiver
+        .recv_timeout(Duration::from_secs(10))
+        .expect("initial scan finished");
+    // Give the watcher a moment to register before writing.
+    std::thread::sleep(Duration::from_millis(300));
+    write_file(temp.path(), "guide.md", "# Guide\n\nRewritten.\n");
+
+    let changed = receiver
+        .recv_timeout(Duration::from_secs(10))
+        .expect("watch report");
+    assert_eq!(changed, Some(vec![PathBuf::from("guide.md")]));
+}
@@ -0,0 +1,126 @@
+//! Explicit anchors: heading attribute blocks (`## Setup {#install}`) and HTML
+//! `<a id="...">` / `<a name="...">` targets.
+
+use std::ops::Range;
+
+use pulldown_cmark::{Event, Options, Parser};
+
+/// Anchor declared with an HTML `<a id>` or `<a name>` tag rather than a heading.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HtmlAnchor {
+    pub id: String,
+    /// Byte range of the opening `<a ...>` tag.
+    pub byte_range: Range<usize>,
+}
+
+/// Collect `<a id>` / `<a name>` targets outside code blocks and inline code.
+pub fn html_anchors(contents: &str) -> Vec<HtmlAnchor> {
+    let mut anchors = Vec::new();
+    for (event, range) in Parser::new_ext(contents, Options::all()).into_offset_iter() {
+        if let Event::Html(html) = event {
+            let offset = contents
+                .get(range.clone())
+                .and_then(|source| source.find(html.as_ref()))
+                .map_or(range.start, |found| range.start + found);
+            anchors.extend(anchor_tags(&html).into_iter().map(|(id, tag)| HtmlAnchor {
+                id,
+                byte_range: offset + tag.start..offset + tag.end,
+            }));
+        }
+    }
+    anchors
+}
+
+/// Split a trailing attribute block off heading text, returning the text and the
+/// `#id` it declares. Blocks made only of `#id`, `.class`, `key=value`, or `-`
+/// tokens (Pandoc, kramdown, and Python-Markdown `attr_list` syntax) are stripped.
+pub(crate) fn split_heading_attributes(text: &str) -> (&str, Option<String>) {
+    let trimmed = text.trim_end();
+    let Some(open) = trimmed.strip_suffix('}').and_then(|inner| inner.rfind('{')) else {
+        return (text, None);
+    };
+    let block = &trimmed[open + 1..trimmed.len() - 1];
+    let tokens: Vec<&str> = block.split_whitespace().collect();
+    let is_attribute = |token: &&str| {
+        (token.len() > 1 && (token.starts_with('#') || token.starts_with('.')))
+            || token.contains('=')
+            || *token == "-"
+    };
+    if tokens.is_empty() || !tokens.iter().all(is_attribute) {
+        return (text, None);
+    }
+    let id = tokens
+        .iter()
+        .find_map(|token| token.strip_prefix('#'))
+        .map(str::to_string);
+    (trimmed[..open].trim_end(), id)
+}
+
+/// `(id, byte range within html)` for every `<a>` tag carrying `id` or `name`.
+fn anchor_tags(html: &str) -> Vec<(String, Range<usize>)> {
+    let lower = html.to_ascii_lowercase();
+    let mut tags = Vec::new();
+    let mut search = 0;
+    while let Some(found) = lower[search..].find("<a") {
+        let start = search + found;
+        search = start + 2;
+        let after = lower[search..].chars().next();
+        if !after.is_some_and(char::is_whitespace) {
+            continue;
+        }
+        let Some(close) = lower[search..].find('>') else {
+            break;
+        };
+        let end = search + close + 1;
+        let attributes = parse_attributes(&html[search..end - 1]);
+        let id = attributes
+            .iter()
+            .find(|(name, _)| name == "id")
+            .or_else(|| attributes.iter().find(|(name, _)| name == "name"))
+            .map(|(_, value)| value.clone());
+        if let Some(id) = id.filter(|id| !id.is_empty()) {
+            tags.push((id, start..end));
+        }
+        search = end;
+    }
+    tags
+}
+
+fn parse_attributes(source: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = source.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|ch: char| ch.is_whitespace() || ch == '=' || ch == '/')
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(after_eq) = rest.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            match after_eq.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let body = &after_eq[1..];
+                    let end = body.find(quote).unwrap_or(body.len());
+                    value = body[..end].to_string();
+                    rest = body.get(end + 1..).unwrap_or("");
+                }
+                _ => {
+                    let end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
+                    value = after_eq[..end].to_string();
+                    rest = &after_eq[end..];
+                }
+            }
+        } else if name.is_empty() {
+            // Stray `/` or other punctuation.
+            rest = rest.get(1..).unwrap_or("");
+            continue;
+        }
+        if !name.is_empty() {
+            attributes.push((name, value.trim().to_string()));
+        }
+        rest = rest.trim_start();
+    }
+    attributes
+}
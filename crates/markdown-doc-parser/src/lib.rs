@@ -5,16 +5,24 @@
 //! path filters, normalises headings, generates anchors, and captures byte ranges
 //! for every section that begins with a Markdown heading.
 
+mod explicit;
+mod slug;
+
 use std::io::Cursor;
 use std::io::{self, BufReader, Read};
 use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 use globset::GlobMatcher;
-use markdown_doc_config::{Config, PatternList};
-use markdown_extract::{collect_headings_from_reader, HeadingKind, MarkdownHeading, ParsedHeading};
+use markdown_doc_config::{AnchorDialect, Config, PatternList};
+use markdown_extract::{collect_headings_from_reader, MarkdownHeading, ParsedHeading};
+
+use crate::explicit::split_heading_attributes;
+
+pub use markdown_extract::{normalize_heading_text, HeadingKind};
 
-pub use markdown_extract::normalize_heading_text;
+pub use crate::explicit::{html_anchors, HtmlAnchor};
+pub use crate::slug::{slugify, AnchorSlugger};
 
 /// High-level parser context configured with resolved settings.
 #[derive(Clone)]
@@ -123,6 +131,7 @@ impl ParserContext {
             &absolute_path,
             &relative,
             file_len,
+            &mut AnchorSlugger::new(self.config.project.anchor_dialect),
         );
         Ok(sections)
     }
@@ -148,9 +157,12 @@ impl ParserContext {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SectionHeading {
     pub depth: usize,
+    /// Heading text as written, with inline markup kept and any `{#id}` block removed.
     pub raw: String,
     pub normalized: String,
     pub anchor: String,
+    /// Id declared with a trailing attribute block (`{#id}`), which becomes the anchor.
+    pub explicit_id: Option<String>,
     pub kind: HeadingKind,
     pub byte_range: Range<usize>,
 }
@@ -173,12 +185,22 @@ impl DocumentSection {
 }
 
 impl SectionHeading {
-    fn from_markdown(heading: &MarkdownHeading) -> Self {
+    fn from_markdown(heading: &MarkdownHeading, slugger: &mut AnchorSlugger) -> Self {
+        // `## Setup {#install}` is titled "Setup" and anchored at `#install`.
+        let (normalized, explicit_id) = split_heading_attributes(&heading.normalized);
+        let anchor = match &explicit_id {
+            Some(id) => {
+                slugger.reserve(id);
+                id.clone()
+            }
+            None => slugger.slug(normalized),
+        };
         SectionHeading {
             depth: heading.depth,
-            raw: heading.raw.clone(),
-            normalized: heading.normalized.clone(),
-            anchor: generate_anchor(&heading.normalized),
+            raw: split_heading_attributes(&heading.raw).0.to_string(),
+            normalized: normalized.to_string(),
+            anchor,
+            explicit_id,
             kind: heading.kind,
             byte_range: heading.start..heading.end,
         }
@@ -190,26 +212,10 @@ impl SectionHeading {
     }
 }
 
-/// Convert normalized heading text into a stable anchor identifier.
+/// Convert normalized heading text into a GitHub-style anchor identifier, without
+/// duplicate suffixing. See [`slugify`] for other dialects.
 pub fn generate_anchor(normalized: &str) -> String {
-    let mut anchor = String::new();
-    let mut last_was_dash = false;
-
-    for ch in normalized.chars().flat_map(|c| c.to_lowercase()) {
-        if ch.is_alphanumeric() {
-            anchor.push(ch);
-            last_was_dash = false;
-        } else if (ch.is_whitespace() || ch == '-') && !anchor.is_empty() && !last_was_dash {
-            anchor.push('-');
-            last_was_dash = true;
-        }
-    }
-
-    if anchor.ends_with('-') {
-        anchor.pop();
-    }
-
-    anchor
+    slugify(AnchorDialect::Github, normalized)
 }
 
 fn build_sections(
@@ -218,6 +224,7 @@ fn build_sections(
     absolute: &Path,
     relative: &Path,
     file_len: usize,
+    slugger: &mut AnchorSlugger,
 ) -> Vec<DocumentSection> {
     headings
         .iter()
@@ -229,7 +236,7 @@ fn build_sections(
             DocumentSection {
                 absolute_path: absolute.to_path_buf(),
                 relative_path: relative.to_path_buf(),
-                heading: SectionHeading::from_markdown(&parsed.heading),
+                heading: SectionHeading::from_markdown(&parsed.heading, slugger),
                 byte_range: section_start..section_end,
                 lines: section_lines,
             }
@@ -0,0 +1,145 @@
+//! Heading slug algorithms for the renderers documents are commonly published with.
+//!
+//! Each dialect mirrors the reference implementation's output for plain heading text
+//! (github-slugger, GitLab's `TableOfContentsFilter`, Python-Markdown's `toc`
+//! extension, Pandoc's `auto_identifiers`, and the Azure DevOps wiki).
+
+use std::collections::HashMap;
+
+use markdown_doc_config::AnchorDialect;
+use unicode_normalization::UnicodeNormalization;
+
+/// Convert normalized heading text into an anchor using `dialect`, without
+/// duplicate suffixing.
+pub fn slugify(dialect: AnchorDialect, text: &str) -> String {
+    match dialect {
+        AnchorDialect::Github => github(text),
+        AnchorDialect::Gitlab => gitlab(text),
+        AnchorDialect::Mkdocs => mkdocs(text),
+        AnchorDialect::Pandoc => pandoc(text),
+        AnchorDialect::AzureDevops => azure_devops(text),
+    }
+}
+
+/// Stateful slug generator for a single document: repeated slugs receive the
+/// dialect's numeric suffix (`usage`, `usage-1`, `usage-2`, ...).
+#[derive(Clone, Debug)]
+pub struct AnchorSlugger {
+    dialect: AnchorDialect,
+    occurrences: HashMap<String, usize>,
+}
+
+impl AnchorSlugger {
+    pub fn new(dialect: AnchorDialect) -> Self {
+        AnchorSlugger {
+            dialect,
+            occurrences: HashMap::new(),
+        }
+    }
+
+    /// Slug for the next heading with `text`, unique within this document.
+    pub fn slug(&mut self, text: &str) -> String {
+        let base = slugify(self.dialect, text);
+        self.unique(base)
+    }
+
+    /// Record an id set explicitly (`{#id}`) so later generated slugs avoid it.
+    pub fn reserve(&mut self, id: &str) {
+        self.occurrences.entry(id.to_string()).or_insert(0);
+    }
+
+    fn unique(&mut self, base: String) -> String {
+        let separator = match self.dialect {
+            AnchorDialect::Mkdocs => '_',
+            _ => '-',
+        };
+        let mut result = base.clone();
+        // Python-Markdown never emits an empty id.
+        let empty_taken = self.dialect == AnchorDialect::Mkdocs && base.is_empty();
+        while (empty_taken && result.is_empty()) || self.occurrences.contains_key(&result) {
+            let count = self.occurrences.entry(base.clone()).or_insert(0);
+            *count += 1;
+            result = format!("{base}{separator}{count}");
+        }
+        self.occurrences.insert(result.clone(), 0);
+        result
+    }
+}
+
+/// github-slugger: lowercase, drop everything but letters, numbers, `_`, `-`, and
+/// spaces, then turn each space into `-`.
+fn github(text: &str) -> String {
+    text.chars()
+        .flat_map(char::to_lowercase)
+        .filter(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | ' '))
+        .map(|ch| if ch == ' ' { '-' } else { ch })
+        .collect()
+}
+
+/// GitLab: GitHub's character set with runs of hyphens squeezed to one.
+fn gitlab(text: &str) -> String {
+    squeeze_hyphens(&github(text))
+}
+
+/// Python-Markdown: NFKD + ASCII fold, drop non-word characters, trim, lowercase,
+/// and collapse whitespace/hyphen runs into `-`.
+fn mkdocs(text: &str) -> String {
+    let folded: String = text
+        .nfkd()
+        .filter(char::is_ascii)
+        .filter(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-') || ch.is_whitespace())
+        .collect();
+    let trimmed = folded.trim().to_ascii_lowercase();
+
+    let mut slug = String::with_capacity(trimmed.len());
+    let mut in_run = false;
+    for ch in trimmed.chars() {
+        if ch == '-' || ch.is_whitespace() {
+            if !in_run {
+                slug.push('-');
+            }
+            in_run = true;
+        } else {
+            slug.push(ch);
+            in_run = false;
+        }
+    }
+    slug
+}
+
+/// Pandoc: keep alphanumerics, `_`, `-`, and `.`, join the remaining words with `-`,
+/// lowercase, and strip everything before the first letter; empty identifiers become
+/// `section`.
+fn pandoc(text: &str) -> String {
+    let filtered: String = text
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.') || ch.is_whitespace())
+        .collect();
+    let joined = filtered.split_whitespace().collect::<Vec<_>>().join("-");
+    match joined.find(char::is_alphabetic) {
+        Some(start) => joined[start..].to_string(),
+        None => "section".into(),
+    }
+}
+
+/// Azure DevOps wiki: lowercase with whitespace replaced by `-`; punctuation is kept
+/// (the wiki percent-encodes it in URLs, and fragments are decoded before comparison).
+fn azure_devops(text: &str) -> String {
+    text.trim()
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|ch| if ch.is_whitespace() { '-' } else { ch })
+        .collect()
+}
+
+fn squeeze_hyphens(value: &str) -> String {
+    let mut squeezed = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if ch == '-' && squeezed.ends_with('-') {
+            continue;
+        }
+        squeezed.push(ch);
+    }
+    squeezed
+}
@@ -0,0 +1,198 @@
+use std::fs;
+use std::path::Path;
+
+use markdown_doc_config::{AnchorDialect, Config, LoadOptions};
+use markdown_doc_parser::{html_anchors, slugify, AnchorSlugger, ParserContext};
+use tempfile::TempDir;
+
+/// `(heading text, expected slug)` pairs taken from each renderer's documented
+/// behaviour and test fixtures.
+const CONFORMANCE: &[(AnchorDialect, &[(&str, &str)])] = &[
+    (
+        AnchorDialect::Github,
+        &[
+            ("Hello World", "hello-world"),
+            ("Hello, World!", "hello-world"),
+            ("Foo - bar", "foo---bar"),
+            ("C++ & Rust", "c--rust"),
+            ("snake_case names", "snake_case-names"),
+            ("Über Café", "über-café"),
+            ("1.2.3 Release", "123-release"),
+            ("What's new?", "whats-new"),
+            ("emoji 🎉 party", "emoji--party"),
+        ],
+    ),
+    (
+        AnchorDialect::Gitlab,
+        &[
+            ("Hello, World!", "hello-world"),
+            ("Foo - bar", "foo-bar"),
+            ("C++ & Rust", "c-rust"),
+            ("snake_case names", "snake_case-names"),
+            ("Über Café", "über-café"),
+            ("1.2.3 Release", "123-release"),
+        ],
+    ),
+    (
+        AnchorDialect::Mkdocs,
+        &[
+            ("Hello, World!", "hello-world"),
+            ("Foo - bar", "foo-bar"),
+            ("C++ & Rust", "c-rust"),
+            ("snake_case names", "snake_case-names"),
+            ("Über Café", "uber-cafe"),
+            ("1.2.3 Release", "123-release"),
+        ],
+    ),
+    (
+        AnchorDialect::Pandoc,
+        &[
+            ("Heading identifiers in HTML", "heading-identifiers-in-html"),
+            ("Maître d'hôtel", "maître-dhôtel"),
+            ("Dogs?--in my house?", "dogs--in-my-house"),
+            ("HTML, S5, or RTF?", "html-s5-or-rtf"),
+            ("3. Applications", "applications"),
+            ("33", "section"),
+            ("1.2.3 Release", "release"),
+        ],
+    ),
+    (
+        AnchorDialect::AzureDevops,
+        &[
+            ("Hello World", "hello-world"),
+            ("Getting Started (v2)", "getting-started-(v2)"),
+            ("C++ & Rust", "c++-&-rust"),
+        ],
+    ),
+];
+
+#[test]
+fn dialects_match_reference_slugs() {
+    for (dialect, cases) in CONFORMANCE {
+        for (text, expected) in *cases {
+            assert_eq!(
+                slugify(*dialect, text),
+                *expected,
+                "{} slug for {text:?}",
+                dialect.as_str()
+            );
+        }
+    }
+}
+
+#[test]
+fn repeated_headings_receive_numeric_suffixes() {
+    let mut github = AnchorSlugger::new(AnchorDialect::Github);
+    let slugs: Vec<String> = ["Usage", "Usage", "Usage", "Usage 1"]
+        .iter()
+        .map(|text| github.slug(text))
+        .collect();
+    assert_eq!(slugs, ["usage", "usage-1", "usage-2", "usage-1-1"]);
+
+    let mut mkdocs = AnchorSlugger::new(AnchorDialect::Mkdocs);
+    let slugs: Vec<String> = ["Usage", "Usage", "Usage"]
+        .iter()
+        .map(|text| mkdocs.slug(text))
+        .collect();
+    assert_eq!(slugs, ["usage", "usage_1", "usage_2"]);
+}
+
+fn write_config(dir: &Path, contents: &str) -> Config {
+    fs::write(dir.join(".markdown-doc.toml"), contents).expect("write config");
+    Config::load(LoadOptions::default().with_working_dir(dir)).expect("load config")
+}
+
+#[test]
+fn parser_applies_configured_dialect_per_document() {
+    let temp = TempDir::new().expect("tempdir");
+    let contents = "# Setup & Usage\n\n## Usage\n\n## Usage\n";
+
+    let config = write_config(temp.path(), "[project]\nanchor_dialect = \"mkdocs\"\n");
+    assert_eq!(config.project.anchor_dialect, AnchorDialect::Mkdocs);
+    let parser = ParserContext::new(config);
+    let anchors: Vec<String> = parser
+        .sections_from_str(Path::new("doc.md"), contents)
+        .into_iter()
+        .map(|section| section.heading.anchor)
+        .collect();
+    assert_eq!(anchors, ["setup-usage", "usage", "usage_1"]);
+
+    let config = write_config(temp.path(), "[project]\n");
+    assert_eq!(config.project.anchor_dialect, AnchorDialect::Github);
+    let parser = ParserContext::new(config);
+    let anchors: Vec<String> = parser
+        .sections_from_str(Path::new("doc.md"), contents)
+        .into_iter()
+        .map(|section| section.heading.anchor)
+        .collect();
+    assert_eq!(anchors, ["setup--usage", "usage", "usage-1"]);
+
+    // Suffix counters are per document.
+    let again = parser.sections_from_str(Path::new("other.md"), "## Usage\n");
+    assert_eq!(again[0].heading.anchor, "usage");
+}
+
+#[test]
+fn unknown_dialect_is_a_validation_error() {
+    let temp = TempDir::new().expect("tempdir");
+    fs::write(
+        temp.path().join(".markdown-doc.toml"),
+        "[project]\nanchor_dialect = \"bitbucket\"\n",
+    )
+    .expect("write config");
+    let err = Config::load(LoadOptions::default().with_working_dir(temp.path()))
+        .expect_err("invalid dialect");
+    assert!(err.to_string().contains("anchor_dialect"), "{err}");
+}
+
+#[test]
+fn attribute_blocks_set_explicit_heading_ids() {
+    let temp = TempDir::new().expect("tempdir");
+    let parser = ParserContext::new(write_config(temp.path(), "[project]\n"));
+    let sections = parser.sections_from_str(
+        Path::new("doc.md"),
+        "## Setup {#install .wide}\n\n## Braces {not attributes}\n\n## Setup\n",
+    );
+    let headings: Vec<(&str, &str, Option<&str>)> = sections
+        .iter()
+        .map(|section| {
+            (
+                section.heading.normalized.as_str(),
+                section.heading.anchor.as_str(),
+                section.heading.explicit_id.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        headings,
+        [
+            ("Setup", "install", Some("install")),
+            ("Braces {not attributes}", "braces-not-attributes", None),
+            ("Setup", "setup", None),
+        ]
+    );
+}
+
+#[test]
+fn explicit_heading_ids_take_part_in_duplicate_suffixing() {
+    let temp = TempDir::new().expect("tempdir");
+    let parser = ParserContext::new(write_config(temp.path(), "[project]\n"));
+    let sections = parser.sections_from_str(
+        Path::new("doc.md"),
+        "## Setup {#install}\n\n## Install\n\n## Install\n",
+    );
+    let anchors: Vec<&str> = sections
+        .iter()
+        .map(|section| section.heading.anchor.as_str())
+        .collect();
+    assert_eq!(anchors, ["install", "install-1", "install-2"]);
+}
+
+#[test]
+fn html_anchors_skip_code() {
+    let contents = "<a id=\"top\"></a>\n\nText <a name='inline' href=\"#x\">here</a>.\n\n```\n<a id=\"code\"></a>\n```\n\n`<a id=\"span\">`\n\n<a href=\"#top\">no id</a>\n";
+    let anchors = html_anchors(contents);
+    let ids: Vec<&str> = anchors.iter().map(|anchor| anchor.id.as_str()).collect();
+    assert_eq!(ids, ["top", "inline"]);
+    assert!(contents[anchors[1].byte_range.clone()].starts_with("<a name='inline'"));
+}
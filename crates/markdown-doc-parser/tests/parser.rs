@@ -84,7 +84,8 @@ Final line.
     let fourth = &sections[3];
     assert_eq!(fourth.heading.depth, 3);
     assert_eq!(fourth.heading.normalized, "São Tomé & Príncipe");
-    assert_eq!(fourth.heading.anchor, "são-tomé-príncipe");
+    // GitHub drops the `&` but keeps both surrounding spaces as hyphens.
+    assert_eq!(fourth.heading.anchor, "são-tomé--príncipe");
     assert_eq!(
         fourth.byte_range.start,
         contents
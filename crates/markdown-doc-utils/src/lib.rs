@@ -5,6 +5,7 @@ use std::io::{self, Write};
 use std::path::Path;
 
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use sha2::{Digest, Sha256};
 use tempfile::Builder;
 
 /// Execute a function over an iterator in parallel.
@@ -44,3 +45,14 @@ pub fn atomic_write(path: &Path, contents: &str) -> io::Result<()> {
 
     tmp.persist(path).map(|_| ()).map_err(|err| err.error)
 }
+
+/// Compute a stable, hex-encoded SHA-256 digest of the provided bytes. Digests are
+/// persisted (baselines, caches), so the algorithm must not change between releases.
+pub fn content_hash(data: &[u8]) -> String {
+    let digest = Sha256::digest(data);
+    let mut hex = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        hex.push_str(&format!("{byte:02x}"));
+    }
+    hex
+}
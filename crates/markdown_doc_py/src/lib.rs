@@ -33,11 +33,17 @@ fn toc(path: &str, mode: &str, no_ignore: bool, quiet: bool) -> PyResult<TocResu
     let scan = ScanOptions {
         paths: vec![PathBuf::from(path)],
         staged: false,
+        changed_since: None,
         respect_ignore: !no_ignore,
     };
 
     let mode = parse_mode(mode)?;
-    let options = TocOptions { scan, mode, quiet };
+    let options = TocOptions {
+        scan,
+        mode,
+        quiet,
+        insert_at: None,
+    };
 
     let outcome = operations.toc(options).map_err(map_operation_error)?;
 
@@ -57,6 +63,7 @@ fn parse_mode(mode: &str) -> PyResult<TocMode> {
         "check" => Ok(TocMode::Check),
         "update" => Ok(TocMode::Update),
         "diff" => Ok(TocMode::Diff),
+        "insert" => Ok(TocMode::Insert),
         other => Err(MarkdownDocError::new_err(format!(
             "unsupported toc mode: {other}"
         ))),
@@ -74,6 +81,7 @@ fn map_operation_error(err: OperationError) -> PyErr {
         }
         OperationError::InvalidInput(message) => MarkdownDocError::new_err(message),
         OperationError::Rewrite(rewrite_err) => MarkdownDocError::new_err(rewrite_err.to_string()),
+        err @ OperationError::Template(_) => MarkdownDocError::new_err(err.to_string()),
         OperationError::Other(message) => MarkdownDocError::new_err(message),
     }
 }
@@ -87,13 +95,13 @@ impl TocResult {
 
         for change in &outcome.changes {
             match change.status {
-                TocStatus::MissingMarkers => {
+                TocStatus::MissingMarkers | TocStatus::InvalidOptions => {
                     has_error = true;
                 }
-                TocStatus::NeedsUpdate | TocStatus::Updated => {
+                TocStatus::NeedsUpdate | TocStatus::Updated | TocStatus::Inserted => {
                     has_changes = true;
                 }
-                TocStatus::UpToDate => {}
+                TocStatus::UpToDate | TocStatus::Skipped => {}
             }
 
             if let Some(diff) = &change.diff {
@@ -128,6 +136,7 @@ impl TocResult {
                 TocMode::Check => "check".to_string(),
                 TocMode::Update => "update".to_string(),
                 TocMode::Diff => "diff".to_string(),
+                TocMode::Insert => "insert".to_string(),
             },
             status,
             diff,
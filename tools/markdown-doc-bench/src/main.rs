@@ -6,7 +6,7 @@ use clap::Parser;
 use markdown_doc_config::{Config, LoadOptions};
 use markdown_doc_core::MarkdownDoc;
 use markdown_doc_format::{CatalogFormat, LintFormat};
-use markdown_doc_ops::{CatalogOptions, LintOptions, ScanOptions};
+use markdown_doc_ops::{CatalogMode, CatalogOptions, LintOptions, ScanOptions, SearchOptions};
 
 #[derive(Parser)]
 #[command(
@@ -26,8 +26,20 @@ struct Args {
     /// Number of measured iterations
     #[arg(long, default_value_t = 3)]
     iterations: usize,
+
+    /// Query used by the search benchmarks
+    #[arg(long, default_value = "configuration")]
+    query: String,
+
+    /// Exit with an error when a benchmark misses its target
+    #[arg(long)]
+    check: bool,
 }
 
+/// Phase 4 RFC targets: full index rebuild and steady-state search.
+const INDEX_BUILD_TARGET: Duration = Duration::from_secs(5);
+const SEARCH_TARGET: Duration = Duration::from_millis(500);
+
 fn main() -> Result<()> {
     let args = Args::parse();
 
@@ -42,11 +54,15 @@ fn main() -> Result<()> {
             scan: ScanOptions {
                 paths: vec![target.clone()],
                 staged: false,
+                changed_since: None,
                 respect_ignore: true,
             },
             format: CatalogFormat::Markdown,
             output_path: None,
             write_to_disk: false,
+            mode: CatalogMode::Update,
+            timestamp: None,
+            regen: false,
         };
         ops.catalog(options)?;
         Ok(())
@@ -57,15 +73,54 @@ fn main() -> Result<()> {
             scan: ScanOptions {
                 paths: vec![target.clone()],
                 staged: false,
+                changed_since: None,
                 respect_ignore: true,
             },
             format: LintFormat::Json,
+            baseline: None,
+            write_baseline: false,
+            add_words: false,
+            cache: false,
         };
         ops.lint_broken_links(options)?;
         Ok(())
     })?;
 
-    print_summary(&[catalog_result, lint_result]);
+    let index_path = std::env::temp_dir().join(format!(
+        "markdown-doc-bench-index-{}.json",
+        std::process::id()
+    ));
+    let search_options = |refresh: bool| SearchOptions {
+        scan: ScanOptions {
+            paths: vec![target.clone()],
+            staged: false,
+            changed_since: None,
+            respect_ignore: true,
+        },
+        query: args.query.clone(),
+        limit: 10,
+        index_path: Some(index_path.clone()),
+        refresh,
+    };
+
+    let index_result = benchmark("search-index-build", args.warmup, args.iterations, || {
+        let _ = std::fs::remove_file(&index_path);
+        ops.search(search_options(true))?;
+        Ok(())
+    })?
+    .with_target(INDEX_BUILD_TARGET);
+
+    let search_result = benchmark("search", args.warmup, args.iterations, || {
+        ops.search(search_options(true))?;
+        Ok(())
+    })?
+    .with_target(SEARCH_TARGET);
+    let _ = std::fs::remove_file(&index_path);
+
+    let met = print_summary(&[catalog_result, lint_result, index_result, search_result]);
+    if args.check && !met {
+        anyhow::bail!("one or more benchmarks missed their target");
+    }
     Ok(())
 }
 
@@ -96,6 +151,14 @@ struct BenchResult {
     iterations: usize,
     average: Duration,
     median: Duration,
+    target: Option<Duration>,
+}
+
+impl BenchResult {
+    fn with_target(mut self, target: Duration) -> Self {
+        self.target = Some(target);
+        self
+    }
 }
 
 fn benchmark<F>(name: &str, warmup: usize, iterations: usize, mut f: F) -> Result<BenchResult>
@@ -126,15 +189,27 @@ where
         iterations,
         average,
         median,
+        target: None,
     })
 }
 
-fn print_summary(results: &[BenchResult]) {
+/// Print each result and return whether every benchmark met its target.
+fn print_summary(results: &[BenchResult]) -> bool {
     println!("markdown-doc benchmark results:\n");
+    let mut met = true;
     for result in results {
+        let verdict = match result.target {
+            Some(target) if result.median <= target => format!(" [target < {target:?}: ok]"),
+            Some(target) => {
+                met = false;
+                format!(" [target < {target:?}: MISSED]")
+            }
+            None => String::new(),
+        };
         println!(
-            "• {}: avg {:?} (median {:?}) over {} iterations",
-            result.name, result.average, result.median, result.iterations
+            "• {}: avg {:?} (median {:?}) over {} iterations{}",
+            result.name, result.average, result.median, result.iterations, verdict
         );
     }
+    met
 }